@@ -1,11 +1,46 @@
 import { invoke } from '@tauri-apps/api/core';
+import type { EventTarget } from '@tauri-apps/api/event';
+
+export interface Rect {
+   x: number;
+   y: number;
+   width: number;
+   height: number;
+}
+
+export interface MonitorInfo {
+   name: string | null;
+   width: number;
+   height: number;
+   logicalWidth: number;
+   logicalHeight: number;
+   x: number;
+   y: number;
+   scaleFactor: number;
+   workArea: Rect;
+   primary: boolean;
+}
 
 export interface WindowInfo {
    width: number;
    height: number;
    x: number;
    y: number;
+   innerWidth: number;
+   innerHeight: number;
+   logicalInnerWidth: number;
+   logicalInnerHeight: number;
+   scaleFactor: number;
+   monitor: MonitorInfo | null;
+   theme: 'light' | 'dark' | null;
+   minimized: boolean;
+   maximized: boolean;
+   fullscreen: boolean;
+   decorated: boolean;
+   resizable: boolean;
+   alwaysOnTop: boolean;
    title: string;
+   url: string | null;
    focused: boolean;
    visible: boolean;
 }
@@ -15,6 +50,27 @@ export interface BackendState {
    windows: number;
 }
 
+export interface InjectedFault {
+   rule: string;
+   latencyMs?: number;
+   error?: unknown;
+   hang: boolean;
+}
+
+export interface FaultSummary {
+   rule: string;
+   command: string;
+   calls: number;
+   delayed: number;
+   failed: number;
+   hung: number;
+}
+
+export interface IPCEventsResult {
+   events: IPCEvent[];
+   faults: FaultSummary[];
+}
+
 export interface IPCEvent {
    timestamp: number;
    command: string;
@@ -22,6 +78,9 @@ export interface IPCEvent {
    result?: unknown;
    error?: string;
    duration_ms?: number;
+   source?: 'backend' | 'frontend' | 'mock' | 'fault';
+   fault?: InjectedFault;
+   window?: string;
 }
 
 /**
@@ -47,9 +106,22 @@ export async function getBackendState(): Promise<BackendState> {
 
 /**
  * Emit a custom event for testing
+ *
+ * A `target` (a label or Tauri `EventTarget`) sends the event with `emit_to`;
+ * an array of targets sends it with `emit_filter`.
  */
-export async function emitEvent(eventName: string, payload?: unknown): Promise<string> {
-   return await invoke('plugin:mcp-bridge|emit_event', { eventName, payload });
+export async function emitEvent(
+   eventName: string,
+   payload?: unknown,
+   target?: EventTarget | string | (EventTarget | string)[]
+): Promise<string> {
+   const targets = Array.isArray(target) ? target : undefined;
+   return await invoke('plugin:mcp-bridge|emit_event', {
+      eventName,
+      payload,
+      target: targets ? undefined : target,
+      targets,
+   });
 }
 
 /**
@@ -67,8 +139,8 @@ export async function stopIPCMonitor(): Promise<string> {
 }
 
 /**
- * Get all captured IPC events
+ * Get all captured IPC events, with the faults each fault rule has injected
  */
-export async function getIPCEvents(): Promise<IPCEvent[]> {
+export async function getIPCEvents(): Promise<IPCEventsResult> {
    return await invoke('plugin:mcp-bridge|get_ipc_events');
 }
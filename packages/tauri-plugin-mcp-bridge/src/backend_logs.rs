@@ -0,0 +1,445 @@
+//! Capture of Rust backend log output.
+//!
+//! [`BackendLogLayer`] is a `tracing` layer that keeps the most recent log
+//! records, with their fields and the spans they were recorded in, in a
+//! bounded ring buffer. Records from the `log` crate are captured too once
+//! they are forwarded to `tracing`, which [`install`] does. The buffer can be
+//! queried like the [event tap](crate::event_tap), and records can be streamed
+//! over the WebSocket broadcast channel as `backend_log` messages.
+//!
+//! Apps without a `tracing` subscriber can let the plugin install one with
+//! [`Builder::capture_backend_logs`](crate::Builder::capture_backend_logs).
+//! Apps with their own subscriber add the layer to it instead:
+//!
+//! ```rust,ignore
+//! use tracing_subscriber::prelude::*;
+//!
+//! tracing_subscriber::registry()
+//!     .with(tracing_subscriber::fmt::layer())
+//!     .with(tauri_plugin_mcp_bridge::backend_logs::layer())
+//!     .init();
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tokio::sync::broadcast;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::event_tap::matches_pattern;
+use crate::monitor::current_timestamp;
+
+/// Maximum number of records kept; older records are dropped first.
+pub const MAX_LOG_RECORDS: usize = 2000;
+
+/// Message type of backend log broadcasts.
+pub const BACKEND_LOG_TYPE: &str = "backend_log";
+
+/// A captured backend log record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogRecord {
+    /// Sequence number, increasing for each captured record
+    pub seq: u64,
+    /// Unix timestamp in milliseconds when the record was captured
+    pub timestamp: u64,
+    /// Level: `ERROR`, `WARN`, `INFO`, `DEBUG` or `TRACE`
+    pub level: String,
+    /// Target, usually the module path
+    pub target: String,
+    /// Formatted message
+    pub message: String,
+    /// Structured fields other than the message
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub fields: Map<String, Value>,
+    /// Spans the record was made in, outermost first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<SpanContext>,
+}
+
+/// A span a log record was made in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpanContext {
+    /// Span name
+    pub name: String,
+    /// Span fields
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub fields: Map<String, Value>,
+}
+
+/// Filters for querying captured records.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogQuery {
+    /// Minimum level (e.g. `"warn"` returns warnings and errors)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    /// Glob pattern for targets (e.g. `"my_app::sync*"`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Only records whose message contains this text, ignoring case
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contains: Option<String>,
+    /// Only records with a greater sequence number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<u64>,
+    /// Only the most recent records, at most this many
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// Ring buffer of captured records.
+#[derive(Debug, Default)]
+pub struct LogBuffer {
+    /// Whether captured records are broadcast as `backend_log` messages
+    pub stream: bool,
+    records: VecDeque<LogRecord>,
+    next_seq: u64,
+    sender: Option<broadcast::Sender<String>>,
+}
+
+impl LogBuffer {
+    /// Creates an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the channel records are streamed on.
+    pub fn set_sender(&mut self, sender: broadcast::Sender<String>) {
+        self.sender = Some(sender);
+    }
+
+    /// Stores a record, assigning its sequence number, and broadcasts it if
+    /// streaming is on.
+    pub fn push(&mut self, mut record: LogRecord) {
+        self.next_seq += 1;
+        record.seq = self.next_seq;
+
+        if let (true, Some(sender)) = (self.stream, &self.sender) {
+            if let Ok(mut message) = serde_json::to_value(&record) {
+                message["type"] = BACKEND_LOG_TYPE.into();
+                // No receivers just means no client is connected
+                let _ = sender.send(message.to_string());
+            }
+        }
+
+        if self.records.len() >= MAX_LOG_RECORDS {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    /// Returns the records matching `query`, oldest first.
+    pub fn query(&self, query: &LogQuery) -> Result<Vec<LogRecord>, String> {
+        let level = query
+            .level
+            .as_deref()
+            .map(|level| Level::from_str(level).map_err(|_| format!("Invalid log level '{level}'")))
+            .transpose()?;
+        let contains = query.contains.as_deref().map(str::to_lowercase);
+
+        let matching: Vec<&LogRecord> = self
+            .records
+            .iter()
+            .filter(|record| query.since.map_or(true, |since| record.seq > since))
+            .filter(|record| {
+                level.map_or(true, |level| {
+                    Level::from_str(&record.level).map_or(true, |l| l <= level)
+                })
+            })
+            .filter(|record| {
+                query
+                    .target
+                    .as_deref()
+                    .map_or(true, |p| matches_pattern(p, &record.target))
+            })
+            .filter(|record| {
+                contains
+                    .as_deref()
+                    .map_or(true, |text| record.message.to_lowercase().contains(text))
+            })
+            .collect();
+
+        let skip = query
+            .limit
+            .map_or(0, |limit| matching.len().saturating_sub(limit));
+        Ok(matching.into_iter().skip(skip).cloned().collect())
+    }
+
+    /// Removes all records, returning how many there were.
+    pub fn clear(&mut self) -> usize {
+        let count = self.records.len();
+        self.records.clear();
+        count
+    }
+}
+
+static BUFFER: OnceLock<Mutex<LogBuffer>> = OnceLock::new();
+static CAPTURING: AtomicBool = AtomicBool::new(false);
+
+/// Returns the buffer every [`BackendLogLayer`] records into.
+pub fn log_buffer() -> &'static Mutex<LogBuffer> {
+    BUFFER.get_or_init(|| Mutex::new(LogBuffer::new()))
+}
+
+/// Returns whether a [`BackendLogLayer`] has been created with [`layer`] or
+/// installed with [`install`].
+pub fn is_capturing() -> bool {
+    CAPTURING.load(Ordering::Relaxed)
+}
+
+/// Collects field values as JSON.
+struct FieldVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), Value::from(format!("{value:?}")));
+    }
+}
+
+/// Fields of a span, kept in its extensions.
+struct SpanFields(Map<String, Value>);
+
+/// A `tracing` layer that records events into the [`log_buffer`].
+#[derive(Debug)]
+pub struct BackendLogLayer {
+    _private: (),
+}
+
+impl BackendLogLayer {
+    /// Creates a layer without marking capture as enabled.
+    fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+/// Creates a layer that records events into the [`log_buffer`].
+pub fn layer() -> BackendLogLayer {
+    CAPTURING.store(true, Ordering::Relaxed);
+    BackendLogLayer::new()
+}
+
+impl<S> Layer<S> for BackendLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Map::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanFields(fields));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(SpanFields(fields)) = span.extensions_mut().get_mut::<SpanFields>() {
+                values.record(&mut FieldVisitor(fields));
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        // Records forwarded from the `log` crate carry their real metadata
+        // in `log.*` fields
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+
+        let mut fields = Map::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        fields.retain(|name, _| !name.starts_with("log."));
+        let message = match fields.remove("message") {
+            Some(Value::String(message)) => message,
+            Some(other) => other.to_string(),
+            None => String::new(),
+        };
+
+        let spans = ctx
+            .event_scope(event)
+            .map(|scope| {
+                scope
+                    .from_root()
+                    .map(|span| SpanContext {
+                        name: span.name().to_string(),
+                        fields: span
+                            .extensions()
+                            .get::<SpanFields>()
+                            .map(|f| f.0.clone())
+                            .unwrap_or_default(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let record = LogRecord {
+            seq: 0,
+            timestamp: current_timestamp(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            message,
+            fields,
+            spans,
+        };
+        if let Ok(mut buffer) = log_buffer().lock() {
+            buffer.push(record);
+        }
+    }
+}
+
+/// Installs a global `tracing` subscriber that captures records at
+/// `max_level` and above, and forwards `log` records to it.
+///
+/// Fails if the app already set a global subscriber; add [`layer`] to that
+/// subscriber instead. If the app set its own `log` logger, `log` records are
+/// not captured and an error is returned after the subscriber is installed.
+pub fn install(max_level: Level) -> Result<(), String> {
+    let filter = LevelFilter::from_level(max_level);
+    let subscriber =
+        tracing_subscriber::registry().with(BackendLogLayer::new().with_filter(filter));
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|e| format!("Failed to install backend log capture: {e}"))?;
+    CAPTURING.store(true, Ordering::Relaxed);
+
+    let log_filter = match max_level {
+        Level::ERROR => tracing_log::log::LevelFilter::Error,
+        Level::WARN => tracing_log::log::LevelFilter::Warn,
+        Level::INFO => tracing_log::log::LevelFilter::Info,
+        Level::DEBUG => tracing_log::log::LevelFilter::Debug,
+        Level::TRACE => tracing_log::log::LevelFilter::Trace,
+    };
+    tracing_log::LogTracer::builder()
+        .with_max_level(log_filter)
+        .init()
+        .map_err(|e| format!("Failed to capture `log` records: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(level: &str, target: &str, message: &str) -> LogRecord {
+        LogRecord {
+            seq: 0,
+            timestamp: 1,
+            level: level.to_string(),
+            target: target.to_string(),
+            message: message.to_string(),
+            fields: Map::new(),
+            spans: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_query_filters_and_ring_buffer() {
+        let (tx, mut rx) = broadcast::channel(4);
+        let mut buffer = LogBuffer::new();
+        buffer.set_sender(tx);
+        buffer.push(record("DEBUG", "app::sync", "Polling"));
+        buffer.push(record("WARN", "app::sync", "Sync slow"));
+        buffer.stream = true;
+        buffer.push(record("ERROR", "app::db", "Sync failed: locked"));
+
+        let query = |query: LogQuery| buffer.query(&query).unwrap();
+        let warnings = query(LogQuery {
+            level: Some("warn".into()),
+            ..Default::default()
+        });
+        assert_eq!(warnings.len(), 2);
+        let sync = query(LogQuery {
+            target: Some("app::sync".into()),
+            contains: Some("SYNC".into()),
+            ..Default::default()
+        });
+        assert_eq!(sync[0].seq, 2);
+        let recent = query(LogQuery {
+            since: Some(1),
+            limit: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(recent[0].message, "Sync failed: locked");
+        assert!(buffer
+            .query(&LogQuery {
+                level: Some("loud".into()),
+                ..Default::default()
+            })
+            .is_err());
+
+        let streamed: Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(streamed["type"], BACKEND_LOG_TYPE);
+        assert_eq!(streamed["seq"], 3);
+        assert!(rx.try_recv().is_err());
+
+        for i in 0..MAX_LOG_RECORDS {
+            buffer.push(record("INFO", "app", &i.to_string()));
+        }
+        assert_eq!(buffer.clear(), MAX_LOG_RECORDS);
+    }
+
+    #[test]
+    fn test_layer_records_fields_and_spans() {
+        let subscriber = tracing_subscriber::registry().with(BackendLogLayer::new());
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("sync", account = "ann");
+            let _entered = span.enter();
+            tracing::warn!(target: "bridge_test::layer", attempt = 3, "Retrying in {}ms", 250);
+        });
+
+        let records = log_buffer()
+            .lock()
+            .unwrap()
+            .query(&LogQuery {
+                target: Some("bridge_test::layer".into()),
+                ..Default::default()
+            })
+            .unwrap();
+        let record = &records[0];
+        assert_eq!(record.level, "WARN");
+        assert_eq!(record.message, "Retrying in 250ms");
+        assert_eq!(record.fields["attempt"], 3);
+        assert_eq!(record.spans[0].name, "sync");
+        assert_eq!(record.spans[0].fields["account"], "ann");
+    }
+
+    #[test]
+    fn test_failed_install_does_not_enable_capture() {
+        // The only test that sets a global subscriber or calls `layer()`
+        let _ =
+            tracing::subscriber::set_global_default(tracing::subscriber::NoSubscriber::default());
+        assert!(install(Level::INFO).is_err());
+        assert!(!is_capturing());
+
+        let _ = layer();
+        assert!(is_capturing());
+    }
+}
@@ -54,6 +54,24 @@
 
       window.__MCP_CONSOLE_LOGS__ = [];
 
+      // Masks values whose key matches a redaction key pattern. The patterns
+      // are globs set by the plugin in window.__MCP_REDACT_KEYS__.
+      function redactKeys(key, value) {
+         var patterns = window.__MCP_REDACT_KEYS__ || [];
+
+         for (var i = 0; key && i < patterns.length; i++) {
+            var source = String(patterns[i])
+               .split('*')
+               .map(function(part) { return part.replace(/[.+?^${}()|[\]\\]/g, '\\$&'); })
+               .join('.*');
+
+            if (new RegExp('^' + source + '$', 'i').test(key)) {
+               return '[REDACTED]';
+            }
+         }
+         return value;
+      }
+
       function captureLog(level, origFn) {
          return function() {
             args = Array.prototype.slice.call(arguments);
@@ -64,7 +82,7 @@
                      return String(a);
                   }
                   try {
-                     return JSON.stringify(a);
+                     return JSON.stringify(a, redactKeys);
                   } catch(_) {
                      // JSON.stringify fails on circular references or
                      // null-prototype objects.
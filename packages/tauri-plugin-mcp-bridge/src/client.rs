@@ -0,0 +1,1248 @@
+//! Rust client for the bridge's WebSocket protocol.
+//!
+//! [`BridgeClient`] connects to a running [`WebSocketServer`](crate::websocket::WebSocketServer)
+//! over TCP or a local socket and exposes a typed async method for each
+//! dispatch command, plus a stream of the events the bridge broadcasts. It is
+//! intended for Rust integration tests that drive a Tauri app from the outside.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use futures_util::StreamExt;
+//! use tauri_plugin_mcp_bridge::client::BridgeClient;
+//!
+//! let client = BridgeClient::connect("ws://127.0.0.1:9223").await?;
+//! let windows = client.list_windows().await?;
+//! let title = client.execute_js("document.title", None).await?;
+//!
+//! let mut events = client.events();
+//! while let Some(event) = events.next().await {
+//!     println!("{event}");
+//! }
+//! ```
+
+use crate::backend_logs::{LogQuery, LogRecord, BACKEND_LOG_TYPE};
+use crate::commands::{
+    CreateWebviewParams, CreateWindowParams, CreateWindowResult, EmitAck, EmitEventParams,
+    EventTapStatus, ExportSessionParams, ExportSessionResult, IPCEventsResult, ImportSessionParams,
+    ImportSessionResult, ManageWindowParams, ManageWindowResult, MonitorsResult, NavigationAction,
+    NavigationParams, NavigationResult, ReplayIpcParams, ReplayReport, ResizeWindowParams,
+    ResizeWindowResult, WaitForEventParams, WaitForEventResult, WindowContext, WindowDetailLevel,
+    WindowInfo,
+};
+use crate::discovery::InstanceRecord;
+use crate::event_tap::TauriEventRecord;
+use crate::ipc_faults::FaultRule;
+use crate::ipc_mock::MockRule;
+use crate::local_socket;
+use crate::monitor::{IPCEvent, IPCStats, MonitorSessionInfo};
+use crate::redaction::RedactionRule;
+use crate::script_registry::{ScriptEntry, ScriptType};
+use crate::window_events::{WindowEventMessage, WINDOW_EVENT_TYPE};
+use futures_util::{SinkExt, Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::{self, Message};
+use uuid::Uuid;
+
+/// Default time to wait for a command response.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Errors returned by [`BridgeClient`].
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("Failed to connect: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("WebSocket error: {0}")]
+    WebSocket(#[from] tungstenite::Error),
+
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Connection closed")]
+    Closed,
+
+    #[error("Timed out waiting for '{0}' response")]
+    Timeout(String),
+
+    #[error("Command failed: {0}")]
+    Command(String),
+
+    #[error("Instance has no reachable endpoint")]
+    NoEndpoint,
+}
+
+/// A raw command response from the bridge.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandResponse {
+    /// Whether the command succeeded
+    pub success: bool,
+    /// The command's result
+    #[serde(default)]
+    pub data: Value,
+    /// Error message if the command failed
+    pub error: Option<String>,
+    /// Which window the command ran against, for window-scoped commands
+    pub window_context: Option<WindowContext>,
+}
+
+impl CommandResponse {
+    /// Returns the data, or the bridge's error if the command failed.
+    pub fn into_result(self) -> Result<Value, ClientError> {
+        if self.success {
+            Ok(self.data)
+        } else {
+            Err(ClientError::Command(
+                self.error.unwrap_or_else(|| "Unknown error".to_string()),
+            ))
+        }
+    }
+}
+
+/// Options for [`BridgeClient::capture_native_screenshot`].
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotOptions {
+    /// Image format: "png" or "jpeg"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// JPEG quality (0-100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<u8>,
+    /// Maximum width; larger screenshots are scaled down
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_width: Option<u32>,
+    /// Window to capture (defaults to "main")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_label: Option<String>,
+}
+
+/// A script as reported by `get_scripts`.
+#[derive(Deserialize)]
+struct ScriptListEntry {
+    id: String,
+    #[serde(rename = "type")]
+    script_type: ScriptType,
+    content: String,
+}
+
+type PendingRequests = Arc<Mutex<HashMap<String, oneshot::Sender<Value>>>>;
+
+/// Client for a running bridge.
+///
+/// Responses are matched to requests by ID, so commands can be issued
+/// concurrently through a shared reference. Messages without a
+/// matching request ID are published on [`BridgeClient::events`].
+pub struct BridgeClient {
+    outgoing: mpsc::UnboundedSender<Message>,
+    pending: PendingRequests,
+    events: broadcast::Sender<Value>,
+    timeout: Duration,
+    tasks: [JoinHandle<()>; 2],
+}
+
+impl BridgeClient {
+    /// Connects to a bridge over TCP.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The WebSocket URL (e.g., "ws://127.0.0.1:9223")
+    pub async fn connect(url: &str) -> Result<Self, ClientError> {
+        let (ws, _) = tokio_tungstenite::connect_async(url).await?;
+        Ok(Self::from_websocket(ws))
+    }
+
+    /// Connects to a bridge listening on a Unix domain socket or named pipe.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The socket path or pipe name (see [`local_socket::socket_path`])
+    pub async fn connect_local(path: impl AsRef<Path>) -> Result<Self, ClientError> {
+        let stream = local_socket::connect(path.as_ref()).await?;
+        Self::connect_stream(stream).await
+    }
+
+    /// Connects to the instance described by a discovery record, preferring
+    /// its local socket over TCP.
+    pub async fn connect_instance(record: &InstanceRecord) -> Result<Self, ClientError> {
+        if let Some(path) = &record.socket_path {
+            return Self::connect_local(path).await;
+        }
+
+        match record.port {
+            Some(port) => Self::connect(&format!("ws://127.0.0.1:{port}")).await,
+            None => Err(ClientError::NoEndpoint),
+        }
+    }
+
+    /// Performs the WebSocket handshake over an already connected stream.
+    pub async fn connect_stream<S>(stream: S) -> Result<Self, ClientError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (ws, _) = tokio_tungstenite::client_async("ws://localhost/", stream).await?;
+        Ok(Self::from_websocket(ws))
+    }
+
+    fn from_websocket<S>(ws: tokio_tungstenite::WebSocketStream<S>) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (mut sink, mut stream) = ws.split();
+        let (outgoing, mut outgoing_rx) = mpsc::unbounded_channel::<Message>();
+        let pending: PendingRequests = Arc::default();
+        let (events, _) = broadcast::channel(256);
+
+        let writer = tokio::spawn(async move {
+            while let Some(message) = outgoing_rx.recv().await {
+                let is_close = matches!(message, Message::Close(_));
+                if sink.send(message).await.is_err() || is_close {
+                    break;
+                }
+            }
+            let _ = sink.close().await;
+        });
+
+        let reader_pending = pending.clone();
+        let reader_events = events.clone();
+        let reader = tokio::spawn(async move {
+            while let Some(Ok(message)) = stream.next().await {
+                let Message::Text(text) = message else {
+                    continue;
+                };
+                let Ok(value) = serde_json::from_str::<Value>(&text) else {
+                    continue;
+                };
+
+                let waiter = value
+                    .get("id")
+                    .and_then(|v| v.as_str())
+                    .and_then(|id| reader_pending.lock().unwrap().remove(id));
+
+                match waiter {
+                    Some(waiter) => {
+                        let _ = waiter.send(value);
+                    }
+                    None => {
+                        let _ = reader_events.send(value);
+                    }
+                }
+            }
+
+            // Fail any requests still waiting for a response
+            reader_pending.lock().unwrap().clear();
+        });
+
+        Self {
+            outgoing,
+            pending,
+            events,
+            timeout: DEFAULT_TIMEOUT,
+            tasks: [writer, reader],
+        }
+    }
+
+    /// Sets how long to wait for each command response.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns a stream of events broadcast by the bridge.
+    ///
+    /// Only events received after this call are yielded. Events are dropped
+    /// for a subscriber that falls too far behind.
+    pub fn events(&self) -> impl Stream<Item = Value> + Send + 'static {
+        futures_util::stream::unfold(self.events.subscribe(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => return Some((event, rx)),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    /// Returns a stream of `window_event` broadcasts (window created, destroyed,
+    /// focused, moved, resized, ...).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn example(client: tauri_plugin_mcp_bridge::client::BridgeClient) {
+    /// use futures_util::StreamExt;
+    /// use tauri_plugin_mcp_bridge::window_events::WindowEventKind;
+    ///
+    /// let mut events = Box::pin(client.window_events());
+    /// while let Some(message) = events.next().await {
+    ///     if message.label == "settings" && message.event == WindowEventKind::Created {
+    ///         break;
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn window_events(&self) -> impl Stream<Item = WindowEventMessage> + Send + 'static {
+        self.events().filter_map(|event| async move {
+            if event.get("type").and_then(Value::as_str) != Some(WINDOW_EVENT_TYPE) {
+                return None;
+            }
+            serde_json::from_value(event).ok()
+        })
+    }
+
+    /// Returns a stream of `backend_log` broadcasts, sent while streaming is
+    /// on (see [`stream_backend_logs`](Self::stream_backend_logs)).
+    pub fn backend_logs(&self) -> impl Stream<Item = LogRecord> + Send + 'static {
+        self.events().filter_map(|event| async move {
+            if event.get("type").and_then(Value::as_str) != Some(BACKEND_LOG_TYPE) {
+                return None;
+            }
+            serde_json::from_value(event).ok()
+        })
+    }
+
+    /// Sends a command and waits for its raw response.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The dispatch command name (e.g., "list_windows")
+    /// * `args` - The command arguments, or `None`
+    pub async fn request(
+        &self,
+        command: &str,
+        args: Option<Value>,
+    ) -> Result<CommandResponse, ClientError> {
+        self.request_with_timeout(command, args, self.timeout).await
+    }
+
+    /// Sends a command and waits up to `timeout` for its raw response.
+    async fn request_with_timeout(
+        &self,
+        command: &str,
+        args: Option<Value>,
+        timeout: Duration,
+    ) -> Result<CommandResponse, ClientError> {
+        let id = Uuid::new_v4().to_string();
+        let mut message = serde_json::json!({ "id": id, "command": command });
+        if let Some(args) = args {
+            message["args"] = args;
+        }
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id.clone(), tx);
+
+        if self
+            .outgoing
+            .send(Message::Text(message.to_string().into()))
+            .is_err()
+        {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(ClientError::Closed);
+        }
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(response)) => Ok(serde_json::from_value(response)?),
+            Ok(Err(_)) => Err(ClientError::Closed),
+            Err(_) => {
+                self.pending.lock().unwrap().remove(&id);
+                Err(ClientError::Timeout(command.to_string()))
+            }
+        }
+    }
+
+    /// Sends a command and deserializes the data of a successful response.
+    async fn call<T: DeserializeOwned>(
+        &self,
+        command: &str,
+        args: Option<Value>,
+    ) -> Result<T, ClientError> {
+        let data = self.request(command, args).await?.into_result()?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Proxies a Tauri IPC command through `invoke_tauri`.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The Tauri command (e.g., "plugin:mcp-bridge|get_backend_state")
+    /// * `args` - The command's arguments
+    pub async fn invoke_tauri(&self, command: &str, args: Value) -> Result<Value, ClientError> {
+        self.call(
+            "invoke_tauri",
+            Some(serde_json::json!({ "command": command, "args": args })),
+        )
+        .await
+    }
+
+    /// Returns backend state: app metadata, environment and windows.
+    pub async fn get_backend_state(&self) -> Result<Value, ClientError> {
+        self.invoke_tauri("plugin:mcp-bridge|get_backend_state", Value::Null)
+            .await
+    }
+
+    /// Starts capturing IPC events from every window.
+    pub async fn start_ipc_monitor(&self) -> Result<Value, ClientError> {
+        self.invoke_tauri("plugin:mcp-bridge|start_ipc_monitor", Value::Null)
+            .await
+    }
+
+    /// Stops capturing IPC events.
+    pub async fn stop_ipc_monitor(&self) -> Result<Value, ClientError> {
+        self.invoke_tauri("plugin:mcp-bridge|stop_ipc_monitor", Value::Null)
+            .await
+    }
+
+    /// Returns the IPC events captured since the monitor was started, with
+    /// the faults each fault rule has injected.
+    pub async fn get_ipc_events(&self) -> Result<IPCEventsResult, ClientError> {
+        let data = self
+            .invoke_tauri("plugin:mcp-bridge|get_ipc_events", Value::Null)
+            .await?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Starts a monitoring session with its own event buffer, capturing the
+    /// given windows or, when `windows` is `None`, every window including ones
+    /// opened later. Reusing a session ID restarts that session.
+    pub async fn start_monitor_session(
+        &self,
+        session_id: Option<&str>,
+        windows: Option<&[&str]>,
+    ) -> Result<MonitorSessionInfo, ClientError> {
+        let data = self
+            .request(
+                "start_monitor_session",
+                Some(serde_json::json!({ "sessionId": session_id, "windows": windows })),
+            )
+            .await?
+            .into_result()?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Stops a monitoring session, keeping its events.
+    pub async fn stop_monitor_session(
+        &self,
+        session_id: &str,
+    ) -> Result<MonitorSessionInfo, ClientError> {
+        let data = self
+            .request(
+                "stop_monitor_session",
+                Some(serde_json::json!({ "sessionId": session_id })),
+            )
+            .await?
+            .into_result()?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Returns the events a monitoring session captured.
+    pub async fn get_monitor_session_events(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<IPCEvent>, ClientError> {
+        let data = self
+            .request(
+                "get_monitor_session_events",
+                Some(serde_json::json!({ "sessionId": session_id })),
+            )
+            .await?
+            .into_result()?;
+        Ok(serde_json::from_value(data["events"].clone())?)
+    }
+
+    /// Ends a monitoring session, returning its events.
+    pub async fn end_monitor_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<IPCEvent>, ClientError> {
+        let data = self
+            .request(
+                "end_monitor_session",
+                Some(serde_json::json!({ "sessionId": session_id })),
+            )
+            .await?
+            .into_result()?;
+        Ok(serde_json::from_value(data["events"].clone())?)
+    }
+
+    /// Returns a summary of every monitoring session.
+    pub async fn list_monitor_sessions(&self) -> Result<Vec<MonitorSessionInfo>, ClientError> {
+        let data = self
+            .request("list_monitor_sessions", None)
+            .await?
+            .into_result()?;
+        Ok(serde_json::from_value(data["sessions"].clone())?)
+    }
+
+    /// Emits a Tauri event to the app.
+    pub async fn emit_event(&self, event_name: &str, payload: Value) -> Result<Value, ClientError> {
+        self.invoke_tauri(
+            "plugin:mcp-bridge|emit_event",
+            serde_json::json!({ "eventName": event_name, "payload": payload }),
+        )
+        .await
+    }
+
+    /// Emits a Tauri event using the targets in `params` (`emit_to` for a single
+    /// target, `emit_filter` for a list).
+    pub async fn emit_event_to(&self, params: &EmitEventParams) -> Result<Value, ClientError> {
+        let mut args = serde_json::to_value(params)?;
+        args["waitForAck"] = Value::Bool(false);
+        self.invoke_tauri("plugin:mcp-bridge|emit_event", args)
+            .await
+    }
+
+    /// Emits a Tauri event and waits for a listener to emit the acknowledgement
+    /// event (`<eventName>:ack` unless `params.ack_event` is set).
+    pub async fn emit_event_with_ack(
+        &self,
+        params: &EmitEventParams,
+    ) -> Result<EmitAck, ClientError> {
+        let mut args = serde_json::to_value(params)?;
+        args["waitForAck"] = Value::Bool(true);
+        let data = self
+            .invoke_tauri("plugin:mcp-bridge|emit_event", args)
+            .await?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Waits for a Tauri event whose payload matches `params`.
+    ///
+    /// The bridge registers the listener when the request arrives and answers
+    /// when the event fires, so other commands (such as the one that triggers
+    /// the event) can be sent on this client while waiting.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Event name, optional JSON pointer and matcher, and timeout
+    pub async fn wait_for_event(
+        &self,
+        params: &WaitForEventParams,
+    ) -> Result<WaitForEventResult, ClientError> {
+        let wait = Duration::from_millis(
+            params
+                .timeout_ms
+                .unwrap_or(crate::commands::wait_for_event::DEFAULT_WAIT_TIMEOUT_MS),
+        );
+        let data = self
+            .request_with_timeout(
+                "wait_for_event",
+                Some(serde_json::to_value(params)?),
+                self.timeout.max(wait + Duration::from_secs(1)),
+            )
+            .await?
+            .into_result()?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Starts capturing the given Tauri events, replacing previous subscriptions.
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - Event names to capture
+    /// * `stream` - Also broadcast captured events as `tauri_event` messages
+    pub async fn start_event_tap(
+        &self,
+        events: &[&str],
+        stream: bool,
+    ) -> Result<EventTapStatus, ClientError> {
+        self.call(
+            "start_event_tap",
+            Some(serde_json::json!({ "events": events, "stream": stream })),
+        )
+        .await
+    }
+
+    /// Stops capturing Tauri events.
+    pub async fn stop_event_tap(&self) -> Result<EventTapStatus, ClientError> {
+        self.call("stop_event_tap", None).await
+    }
+
+    /// Returns captured Tauri events.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Optional glob pattern for event names (e.g. "download:*")
+    /// * `since` - Only return events with a greater sequence number
+    pub async fn get_tauri_events(
+        &self,
+        pattern: Option<&str>,
+        since: Option<u64>,
+    ) -> Result<Vec<TauriEventRecord>, ClientError> {
+        self.call(
+            "get_tauri_events",
+            Some(serde_json::json!({ "pattern": pattern, "since": since })),
+        )
+        .await
+    }
+
+    /// Removes all captured Tauri events.
+    pub async fn clear_tauri_events(&self) -> Result<(), ClientError> {
+        self.request("clear_tauri_events", None)
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    /// Returns captured backend log records matching `query`, oldest first.
+    pub async fn get_backend_logs(&self, query: &LogQuery) -> Result<Vec<LogRecord>, ClientError> {
+        self.call("get_backend_logs", Some(serde_json::to_value(query)?))
+            .await
+    }
+
+    /// Removes all captured backend log records. Returns how many were removed.
+    pub async fn clear_backend_logs(&self) -> Result<usize, ClientError> {
+        let data = self
+            .request("clear_backend_logs", None)
+            .await?
+            .into_result()?;
+        Ok(data["cleared"].as_u64().unwrap_or(0) as usize)
+    }
+
+    /// Turns broadcasting of backend log records as `backend_log` messages
+    /// on or off.
+    pub async fn stream_backend_logs(&self, enabled: bool) -> Result<(), ClientError> {
+        self.request(
+            "stream_backend_logs",
+            Some(serde_json::json!({ "enabled": enabled })),
+        )
+        .await?
+        .into_result()?;
+        Ok(())
+    }
+
+    /// Lists all open webview windows.
+    pub async fn list_windows(&self) -> Result<Vec<WindowInfo>, ClientError> {
+        self.call("list_windows", None).await
+    }
+
+    /// Lists all open webview windows with geometry, monitor and state flags
+    /// in each entry's `details`.
+    pub async fn list_windows_detailed(&self) -> Result<Vec<WindowInfo>, ClientError> {
+        self.call(
+            "list_windows",
+            Some(serde_json::json!({ "detail": WindowDetailLevel::Full })),
+        )
+        .await
+    }
+
+    /// Returns size, position, monitor, state flags, title, URL, focus and
+    /// visibility of a window.
+    ///
+    /// # Arguments
+    ///
+    /// * `window_id` - The window label, or `None` for "main"
+    pub async fn get_window_info(&self, window_id: Option<&str>) -> Result<Value, ClientError> {
+        self.call(
+            "get_window_info",
+            Some(serde_json::json!({ "windowId": window_id })),
+        )
+        .await
+    }
+
+    /// Lists the available monitors and the cursor position.
+    pub async fn list_monitors(&self) -> Result<MonitorsResult, ClientError> {
+        self.call("list_monitors", None).await
+    }
+
+    /// Navigates, reloads, goes back/forward or waits for the next page load.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The navigation operation
+    /// * `params` - Window, URL and wait options
+    pub async fn navigate(
+        &self,
+        action: NavigationAction,
+        params: &NavigationParams,
+    ) -> Result<NavigationResult, ClientError> {
+        self.call(action.command(), Some(serde_json::to_value(params)?))
+            .await
+    }
+
+    /// Executes JavaScript in a webview and returns its result.
+    ///
+    /// # Arguments
+    ///
+    /// * `script` - The JavaScript to run
+    /// * `window_label` - The window label, or `None` for "main"
+    pub async fn execute_js(
+        &self,
+        script: &str,
+        window_label: Option<&str>,
+    ) -> Result<Value, ClientError> {
+        self.call(
+            "execute_js",
+            Some(serde_json::json!({ "script": script, "windowLabel": window_label })),
+        )
+        .await
+    }
+
+    /// Captures a screenshot of a webview and returns it as a data URL.
+    pub async fn capture_native_screenshot(
+        &self,
+        options: &ScreenshotOptions,
+    ) -> Result<String, ClientError> {
+        self.call(
+            "capture_native_screenshot",
+            Some(serde_json::to_value(options)?),
+        )
+        .await
+    }
+
+    /// Resizes a window.
+    pub async fn resize_window(
+        &self,
+        params: &ResizeWindowParams,
+    ) -> Result<ResizeWindowResult, ClientError> {
+        self.call("resize_window", Some(serde_json::to_value(params)?))
+            .await
+    }
+
+    /// Moves, minimizes, maximizes, focuses, shows/hides, closes or retitles a window.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use tauri_plugin_mcp_bridge::commands::{ManageWindowParams, WindowAction};
+    ///
+    /// let params = ManageWindowParams {
+    ///     window_id: Some("settings".to_string()),
+    ///     action: WindowAction::SetPosition { x: 0, y: 0, logical: true },
+    /// };
+    /// client.manage_window(&params).await?;
+    /// ```
+    pub async fn manage_window(
+        &self,
+        params: &ManageWindowParams,
+    ) -> Result<ManageWindowResult, ClientError> {
+        self.call("manage_window", Some(serde_json::to_value(params)?))
+            .await
+    }
+
+    /// Opens a new webview window.
+    pub async fn create_window(
+        &self,
+        params: &CreateWindowParams,
+    ) -> Result<CreateWindowResult, ClientError> {
+        self.call("create_window", Some(serde_json::to_value(params)?))
+            .await
+    }
+
+    /// Adds a webview to an existing window. Requires the app to be built
+    /// with the plugin's `multiwebview` feature.
+    pub async fn create_webview(
+        &self,
+        params: &CreateWebviewParams,
+    ) -> Result<CreateWindowResult, ClientError> {
+        self.call("create_webview", Some(serde_json::to_value(params)?))
+            .await
+    }
+
+    /// Registers a script that is injected now and re-injected on every page load.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The script to register
+    /// * `window_label` - The window to inject into now, or `None` for "main"
+    pub async fn register_script(
+        &self,
+        entry: &ScriptEntry,
+        window_label: Option<&str>,
+    ) -> Result<(), ClientError> {
+        let script_type = match entry.script_type {
+            ScriptType::Inline => "inline",
+            ScriptType::Url => "url",
+        };
+
+        self.request(
+            "register_script",
+            Some(serde_json::json!({
+                "id": entry.id,
+                "type": script_type,
+                "content": entry.content,
+                "windowLabel": window_label,
+            })),
+        )
+        .await?
+        .into_result()
+        .map(|_| ())
+    }
+
+    /// Removes a registered script. Returns true if it was registered.
+    pub async fn remove_script(
+        &self,
+        id: &str,
+        window_label: Option<&str>,
+    ) -> Result<bool, ClientError> {
+        let data = self
+            .request(
+                "remove_script",
+                Some(serde_json::json!({ "id": id, "windowLabel": window_label })),
+            )
+            .await?
+            .into_result()?;
+        Ok(data["removed"].as_bool().unwrap_or(false))
+    }
+
+    /// Removes all registered scripts. Returns how many were removed.
+    pub async fn clear_scripts(&self, window_label: Option<&str>) -> Result<usize, ClientError> {
+        let data = self
+            .request(
+                "clear_scripts",
+                Some(serde_json::json!({ "windowLabel": window_label })),
+            )
+            .await?
+            .into_result()?;
+        Ok(data["cleared"].as_u64().unwrap_or(0) as usize)
+    }
+
+    /// Returns all registered scripts.
+    pub async fn get_scripts(&self) -> Result<Vec<ScriptEntry>, ClientError> {
+        let data = self.request("get_scripts", None).await?.into_result()?;
+        let scripts: Vec<ScriptListEntry> = serde_json::from_value(data["scripts"].clone())?;
+
+        Ok(scripts
+            .into_iter()
+            .map(|s| ScriptEntry {
+                id: s.id,
+                script_type: s.script_type,
+                content: s.content,
+            })
+            .collect())
+    }
+
+    /// Adds or replaces a mock rule for an app command. Returns whether rules
+    /// are known to be enforced, i.e. the app's invoke handler is wrapped with
+    /// [`ipc_capture::capture`](crate::ipc_capture::capture) and has handled a
+    /// call.
+    pub async fn add_ipc_mock(&self, rule: &MockRule) -> Result<bool, ClientError> {
+        let data = self
+            .request("add_ipc_mock", Some(serde_json::to_value(rule)?))
+            .await?
+            .into_result()?;
+        Ok(data["enforced"].as_bool().unwrap_or(false))
+    }
+
+    /// Removes a mock rule. Returns true if it existed.
+    pub async fn remove_ipc_mock(&self, id: &str) -> Result<bool, ClientError> {
+        let data = self
+            .request("remove_ipc_mock", Some(serde_json::json!({ "id": id })))
+            .await?
+            .into_result()?;
+        Ok(data["removed"].as_bool().unwrap_or(false))
+    }
+
+    /// Removes all mock rules. Returns how many were removed.
+    pub async fn clear_ipc_mocks(&self) -> Result<usize, ClientError> {
+        let data = self.request("clear_ipc_mocks", None).await?.into_result()?;
+        Ok(data["cleared"].as_u64().unwrap_or(0) as usize)
+    }
+
+    /// Returns all mock rules with their hit counts.
+    pub async fn get_ipc_mocks(&self) -> Result<Vec<MockRule>, ClientError> {
+        let data = self.request("get_ipc_mocks", None).await?.into_result()?;
+        Ok(serde_json::from_value(data["mocks"].clone())?)
+    }
+
+    /// Adds or replaces a fault injection rule for app commands. Returns
+    /// whether rules are enforced, as for [`add_ipc_mock`](Self::add_ipc_mock).
+    pub async fn add_ipc_fault(&self, rule: &FaultRule) -> Result<bool, ClientError> {
+        let data = self
+            .request("add_ipc_fault", Some(serde_json::to_value(rule)?))
+            .await?
+            .into_result()?;
+        Ok(data["enforced"].as_bool().unwrap_or(false))
+    }
+
+    /// Removes a fault injection rule. Returns true if it existed.
+    pub async fn remove_ipc_fault(&self, id: &str) -> Result<bool, ClientError> {
+        let data = self
+            .request("remove_ipc_fault", Some(serde_json::json!({ "id": id })))
+            .await?
+            .into_result()?;
+        Ok(data["removed"].as_bool().unwrap_or(false))
+    }
+
+    /// Removes all fault injection rules. Returns how many were removed.
+    pub async fn clear_ipc_faults(&self) -> Result<usize, ClientError> {
+        let data = self
+            .request("clear_ipc_faults", None)
+            .await?
+            .into_result()?;
+        Ok(data["cleared"].as_u64().unwrap_or(0) as usize)
+    }
+
+    /// Returns all fault injection rules with the faults each has injected.
+    pub async fn get_ipc_faults(&self) -> Result<Vec<FaultRule>, ClientError> {
+        let data = self.request("get_ipc_faults", None).await?.into_result()?;
+        Ok(serde_json::from_value(data["faults"].clone())?)
+    }
+
+    /// Exports captured IPC calls, Tauri events and console logs as a session
+    /// document.
+    pub async fn export_session(
+        &self,
+        params: &ExportSessionParams,
+    ) -> Result<ExportSessionResult, ClientError> {
+        let data = self
+            .request("export_session", Some(serde_json::to_value(params)?))
+            .await?
+            .into_result()?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Loads a session document's IPC calls into a new monitoring session,
+    /// returning its ID along with the document's events and console logs.
+    pub async fn import_session(
+        &self,
+        params: &ImportSessionParams,
+    ) -> Result<ImportSessionResult, ClientError> {
+        let data = self
+            .request("import_session", Some(serde_json::to_value(params)?))
+            .await?
+            .into_result()?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Returns per-command counts, errors and duration percentiles for the
+    /// calls captured since the IPC monitor started.
+    pub async fn get_ipc_stats(&self) -> Result<IPCStats, ClientError> {
+        let data = self.request("get_ipc_stats", None).await?.into_result()?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Sets the duration above which calls are reported as slow, or `None`
+    /// to stop reporting them.
+    pub async fn set_ipc_slow_threshold(
+        &self,
+        threshold_ms: Option<f64>,
+    ) -> Result<(), ClientError> {
+        self.request(
+            "set_ipc_slow_threshold",
+            Some(serde_json::json!({ "thresholdMs": threshold_ms })),
+        )
+        .await?
+        .into_result()?;
+        Ok(())
+    }
+
+    /// Adds or replaces a rule masking sensitive values in captured IPC calls
+    /// and console logs. The rule also applies to events already captured.
+    pub async fn add_redaction_rule(&self, rule: &RedactionRule) -> Result<(), ClientError> {
+        self.request("add_redaction_rule", Some(serde_json::to_value(rule)?))
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    /// Removes a redaction rule. Returns true if it existed.
+    pub async fn remove_redaction_rule(&self, id: &str) -> Result<bool, ClientError> {
+        let data = self
+            .request(
+                "remove_redaction_rule",
+                Some(serde_json::json!({ "id": id })),
+            )
+            .await?
+            .into_result()?;
+        Ok(data["removed"].as_bool().unwrap_or(false))
+    }
+
+    /// Removes all redaction rules. Returns how many were removed.
+    pub async fn clear_redaction_rules(&self) -> Result<usize, ClientError> {
+        let data = self
+            .request("clear_redaction_rules", None)
+            .await?
+            .into_result()?;
+        Ok(data["cleared"].as_u64().unwrap_or(0) as usize)
+    }
+
+    /// Returns all redaction rules.
+    pub async fn get_redaction_rules(&self) -> Result<Vec<RedactionRule>, ClientError> {
+        let data = self
+            .request("get_redaction_rules", None)
+            .await?
+            .into_result()?;
+        Ok(serde_json::from_value(data["rules"].clone())?)
+    }
+
+    /// Replays recorded IPC calls against the live backend and reports where
+    /// responses diverge from the recording.
+    ///
+    /// The whole replay must finish within the client's timeout (see
+    /// [`with_timeout`](Self::with_timeout)).
+    pub async fn replay_ipc(&self, params: &ReplayIpcParams) -> Result<ReplayReport, ClientError> {
+        let data = self
+            .request("replay_ipc", Some(serde_json::to_value(params)?))
+            .await?
+            .into_result()?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Closes the connection, waiting for the close frame to be sent.
+    pub async fn close(mut self) {
+        if self.outgoing.send(Message::Close(None)).is_ok() {
+            let _ = (&mut self.tasks[0]).await;
+        }
+    }
+}
+
+impl Drop for BridgeClient {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::ScriptExecutor;
+    use crate::monitor::IPCMonitor;
+    use crate::script_registry::create_shared_registry;
+    use crate::websocket::{Endpoint, WebSocketServer};
+    use tauri::Manager;
+
+    /// Starts a bridge for a mock app on an OS-assigned port.
+    async fn start_server() -> (
+        tauri::App<tauri::test::MockRuntime>,
+        String,
+        broadcast::Sender<String>,
+    ) {
+        let app = tauri::test::mock_app();
+        app.manage(ScriptExecutor::new());
+        app.manage(Arc::new(Mutex::new(IPCMonitor::new())));
+        app.manage(create_shared_registry());
+        app.manage(crate::ipc_mock::create_shared_mock_registry());
+        app.manage(crate::ipc_faults::create_shared_fault_injector());
+        app.manage::<crate::event_tap::EventTapState>(Arc::new(Mutex::new(
+            crate::event_tap::EventTap::new(),
+        )));
+
+        let (event_tx, _) = broadcast::channel::<String>(16);
+        let mut server =
+            WebSocketServer::new(0, "127.0.0.1", app.handle().clone(), event_tx.clone());
+        server.bind().unwrap();
+        let Endpoint::Tcp(addr) = server.endpoint().clone() else {
+            panic!("expected a TCP endpoint");
+        };
+        tokio::spawn(async move {
+            let _ = server.start().await;
+        });
+
+        (app, format!("ws://{addr}"), event_tx)
+    }
+
+    #[tokio::test]
+    async fn test_commands_round_trip() {
+        let (app, url, _event_tx) = start_server().await;
+        let client = BridgeClient::connect(&url).await.unwrap();
+
+        assert!(client.list_windows().await.unwrap().is_empty());
+        assert!(client.get_scripts().await.unwrap().is_empty());
+        let fault: FaultRule = serde_json::from_value(
+            serde_json::json!({ "id": "slow", "command": "save_*", "latencyMs": 100 }),
+        )
+        .unwrap();
+        client.add_ipc_fault(&fault).await.unwrap();
+        app.state::<crate::ipc_faults::SharedFaultInjector>()
+            .lock()
+            .unwrap()
+            .inject("save_note");
+        let captured = client.get_ipc_events().await.unwrap();
+        assert!(captured.events.is_empty());
+        assert_eq!(captured.faults.len(), 1);
+        assert_eq!(captured.faults[0].rule, "slow");
+        assert_eq!(
+            (
+                captured.faults[0].stats.calls,
+                captured.faults[0].stats.delayed
+            ),
+            (1, 1)
+        );
+        client.set_ipc_slow_threshold(Some(100.0)).await.unwrap();
+        assert_eq!(
+            client.get_ipc_stats().await.unwrap().slow_threshold_ms,
+            Some(100.0)
+        );
+        let rule = RedactionRule {
+            id: "secrets".to_string(),
+            keys: vec!["*token*".to_string()],
+            ..Default::default()
+        };
+        client.add_redaction_rule(&rule).await.unwrap();
+        assert_eq!(client.get_redaction_rules().await.unwrap(), vec![rule]);
+        assert!(client.remove_redaction_rule("secrets").await.unwrap());
+        assert_eq!(client.clear_redaction_rules().await.unwrap(), 0);
+
+        let session = client
+            .start_monitor_session(Some("agent-a"), Some(&["main"]))
+            .await
+            .unwrap();
+        assert_eq!(session.windows, Some(vec!["main".to_string()]));
+        let stopped = client.stop_monitor_session("agent-a").await.unwrap();
+        assert!(!stopped.active);
+        assert_eq!(client.list_monitor_sessions().await.unwrap().len(), 1);
+        assert!(client
+            .end_monitor_session("agent-a")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(client.get_monitor_session_events("agent-a").await.is_err());
+        client.stream_backend_logs(false).await.unwrap();
+        let tap = client
+            .start_event_tap(&["state-changed"], false)
+            .await
+            .unwrap();
+        assert_eq!(tap.events, vec!["state-changed"]);
+        client.clear_tauri_events().await.unwrap();
+        assert!(client
+            .get_tauri_events(None, None)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(!client.stop_event_tap().await.unwrap().enabled);
+        assert_eq!(client.clear_scripts(None).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_ipc_mocks_round_trip() {
+        let (_app, url, _event_tx) = start_server().await;
+        let client = BridgeClient::connect(&url).await.unwrap();
+
+        let rule: MockRule = serde_json::from_value(serde_json::json!({
+            "id": "save-fails",
+            "command": "save_settings",
+            "error": "disk full"
+        }))
+        .unwrap();
+        client.add_ipc_mock(&rule).await.unwrap();
+
+        let mocks = client.get_ipc_mocks().await.unwrap();
+        assert_eq!(mocks, vec![rule]);
+        assert!(client.remove_ipc_mock("save-fails").await.unwrap());
+        assert!(!client.remove_ipc_mock("save-fails").await.unwrap());
+        assert_eq!(client.clear_ipc_mocks().await.unwrap(), 0);
+
+        let invalid = MockRule {
+            error: None,
+            ..mocks[0].clone()
+        };
+        assert!(matches!(
+            client.add_ipc_mock(&invalid).await,
+            Err(ClientError::Command(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_command_error_is_reported() {
+        let (_app, url, _event_tx) = start_server().await;
+        let client = BridgeClient::connect(&url).await.unwrap();
+
+        let err = client.get_window_info(Some("missing")).await.unwrap_err();
+        assert!(matches!(err, ClientError::Command(msg) if msg.contains("missing")));
+    }
+
+    #[tokio::test]
+    async fn test_emit_event_with_ack() {
+        use tauri::{Emitter, Listener};
+
+        let (app, url, _event_tx) = start_server().await;
+        let handle = app.handle().clone();
+        app.listen_any("ping", move |_| {
+            handle.emit("ping:ack", "pong").unwrap();
+        });
+        let client = BridgeClient::connect(&url).await.unwrap();
+
+        let ack = client
+            .emit_event_with_ack(&EmitEventParams {
+                event_name: "ping".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(ack.ack_event, "ping:ack");
+        assert_eq!(ack.payload, "pong");
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_event_while_triggering() {
+        let (_app, url, _event_tx) = start_server().await;
+        let client = Arc::new(BridgeClient::connect(&url).await.unwrap());
+
+        let waiter = {
+            let client = client.clone();
+            tokio::spawn(async move {
+                client
+                    .wait_for_event(&WaitForEventParams {
+                        event: "job:update".into(),
+                        pointer: Some("/state".into()),
+                        matches: Some(serde_json::json!("done")),
+                        timeout_ms: Some(5_000),
+                    })
+                    .await
+            })
+        };
+
+        // Give the wait a head start so its listener is registered first
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        for state in ["running", "done"] {
+            client
+                .emit_event("job:update", serde_json::json!({ "state": state }))
+                .await
+                .unwrap();
+        }
+
+        let result = waiter.await.unwrap().unwrap();
+        assert_eq!(result.payload["state"], "done");
+    }
+
+    #[tokio::test]
+    async fn test_events_stream() {
+        let (_app, url, event_tx) = start_server().await;
+        let client = BridgeClient::connect(&url).await.unwrap();
+        let mut events = Box::pin(client.events());
+
+        // Round-trip a command so the server has subscribed this connection
+        client.get_scripts().await.unwrap();
+        event_tx.send(r#"{"type":"ping"}"#.to_string()).unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event["type"], "ping");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_connect_local() {
+        let app = tauri::test::mock_app();
+        app.manage(create_shared_registry());
+
+        let path =
+            std::env::temp_dir().join(format!("tauri-mcp-test-client-{}.sock", std::process::id()));
+        let (event_tx, _) = broadcast::channel::<String>(16);
+        let mut server = WebSocketServer::new_local(path.clone(), app.handle().clone(), event_tx);
+        server.bind().unwrap();
+        tokio::spawn(async move {
+            let _ = server.start().await;
+        });
+
+        let client = BridgeClient::connect_local(&path).await.unwrap();
+        assert!(client.get_scripts().await.unwrap().is_empty());
+
+        local_socket::cleanup(&path);
+    }
+}
@@ -0,0 +1,354 @@
+//! Creating windows and webviews.
+//!
+//! Windows created here get the bridge init script like any other webview,
+//! since plugin init scripts apply to every webview in the app. Registered
+//! scripts are injected from the `on_page_load` hook when each page finishes
+//! loading, so they also reach external pages that cannot call back into
+//! the plugin over IPC.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime, WebviewUrl};
+
+#[cfg(desktop)]
+use super::script_injection::registered_scripts_injection;
+#[cfg(desktop)]
+use tauri::{webview::PageLoadEvent, Manager, WebviewWindowBuilder};
+
+/// Parameters for creating a window.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWindowParams {
+    /// Unique label for the new window
+    pub label: String,
+    /// App route (e.g. "/settings" or "index.html") or external URL. Defaults to the app's index
+    pub url: Option<String>,
+    /// Window title
+    pub title: Option<String>,
+    /// Inner width in logical pixels
+    pub width: Option<f64>,
+    /// Inner height in logical pixels
+    pub height: Option<f64>,
+    /// X position in logical pixels
+    pub x: Option<f64>,
+    /// Y position in logical pixels
+    pub y: Option<f64>,
+    /// Whether the window has decorations (title bar, borders)
+    pub decorations: Option<bool>,
+    /// Whether the window is resizable
+    pub resizable: Option<bool>,
+    /// Whether the window is initially visible
+    pub visible: Option<bool>,
+    /// Whether the window is initially focused
+    pub focused: Option<bool>,
+    /// Label of the parent window
+    pub parent: Option<String>,
+}
+
+/// Parameters for adding a webview to an existing window.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebviewParams {
+    /// Unique label for the new webview
+    pub label: String,
+    /// Label of the window to attach to (defaults to "main")
+    pub window_id: Option<String>,
+    /// App route or external URL. Defaults to the app's index
+    pub url: Option<String>,
+    /// X position within the window in logical pixels (default 0)
+    pub x: Option<f64>,
+    /// Y position within the window in logical pixels (default 0)
+    pub y: Option<f64>,
+    /// Width in logical pixels (defaults to the window's width)
+    pub width: Option<f64>,
+    /// Height in logical pixels (defaults to the window's height)
+    pub height: Option<f64>,
+}
+
+/// Result of creating a window or webview.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWindowResult {
+    /// Whether the window or webview was created
+    pub success: bool,
+    /// The label of the new window or webview
+    pub label: String,
+    /// The window the webview belongs to
+    pub window_label: String,
+    /// The URL the webview was created with
+    pub url: String,
+    /// Error message if creation failed
+    pub error: Option<String>,
+}
+
+impl CreateWindowResult {
+    fn failed(label: &str, window_label: &str, url: &WebviewUrl, error: String) -> Self {
+        Self {
+            success: false,
+            label: label.to_string(),
+            window_label: window_label.to_string(),
+            url: url.to_string(),
+            error: Some(error),
+        }
+    }
+}
+
+/// Parses an app route or external URL.
+///
+/// Absolute `http(s)` URLs load externally; anything else is resolved against
+/// the app's frontend.
+pub fn parse_webview_url(url: Option<&str>) -> WebviewUrl {
+    match url {
+        None | Some("") => WebviewUrl::default(),
+        Some(url) => match tauri::Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {
+                WebviewUrl::External(parsed)
+            }
+            _ => WebviewUrl::App(url.trim_start_matches('/').into()),
+        },
+    }
+}
+
+/// Creates a new webview window.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri application handle
+/// * `params` - Label, URL, size, position, decorations and parent of the window
+///
+/// # Returns
+///
+/// * `Ok(CreateWindowResult)` - Result of the operation
+/// * `Err(String)` - Error message if the parameters are invalid
+///
+/// # Notes
+///
+/// - On mobile platforms (Android/iOS), only a single window is supported and this returns an error
+#[cfg(desktop)]
+pub async fn create_window<R: Runtime>(
+    app: AppHandle<R>,
+    params: CreateWindowParams,
+) -> Result<CreateWindowResult, String> {
+    let url = parse_webview_url(params.url.as_deref());
+    let label = params.label.as_str();
+
+    if app.get_webview_window(label).is_some() {
+        return Ok(CreateWindowResult::failed(
+            label,
+            label,
+            &url,
+            format!("Window '{label}' already exists"),
+        ));
+    }
+
+    let mut builder =
+        WebviewWindowBuilder::new(&app, label, url.clone()).on_page_load(|window, payload| {
+            if payload.event() == PageLoadEvent::Finished {
+                if let Some(script) = registered_scripts_injection(&window) {
+                    let _ = window.eval(&script);
+                }
+            }
+        });
+
+    if let Some(title) = &params.title {
+        builder = builder.title(title);
+    }
+    if let (Some(width), Some(height)) = (params.width, params.height) {
+        builder = builder.inner_size(width, height);
+    }
+    if let (Some(x), Some(y)) = (params.x, params.y) {
+        builder = builder.position(x, y);
+    }
+    if let Some(decorations) = params.decorations {
+        builder = builder.decorations(decorations);
+    }
+    if let Some(resizable) = params.resizable {
+        builder = builder.resizable(resizable);
+    }
+    if let Some(visible) = params.visible {
+        builder = builder.visible(visible);
+    }
+    if let Some(focused) = params.focused {
+        builder = builder.focused(focused);
+    }
+    if let Some(parent_label) = &params.parent {
+        let parent = app
+            .get_webview_window(parent_label)
+            .ok_or_else(|| format!("Parent window '{parent_label}' not found"))?;
+        builder = builder
+            .parent(&parent)
+            .map_err(|e| format!("Failed to set parent window: {e}"))?;
+    }
+
+    match builder.build() {
+        Ok(window) => Ok(CreateWindowResult {
+            success: true,
+            label: window.label().to_string(),
+            window_label: window.label().to_string(),
+            url: url.to_string(),
+            error: None,
+        }),
+        Err(e) => Ok(CreateWindowResult::failed(
+            label,
+            label,
+            &url,
+            format!("Failed to create window: {e}"),
+        )),
+    }
+}
+
+/// Mobile implementation - returns unsupported error with clear explanation for the agent.
+#[cfg(mobile)]
+pub async fn create_window<R: Runtime>(
+    _app: AppHandle<R>,
+    params: CreateWindowParams,
+) -> Result<CreateWindowResult, String> {
+    let url = parse_webview_url(params.url.as_deref());
+
+    Ok(CreateWindowResult::failed(
+        &params.label,
+        &params.label,
+        &url,
+        "Creating windows is not supported on mobile platforms (Android/iOS). \
+         Apps have a single window controlled by the operating system."
+            .to_string(),
+    ))
+}
+
+/// Adds a webview to an existing window.
+///
+/// Multiple webviews per window rely on Tauri's `unstable` feature, which this
+/// plugin enables through its `multiwebview` feature.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri application handle
+/// * `params` - Label, target window, URL, position and size of the webview
+///
+/// # Returns
+///
+/// * `Ok(CreateWindowResult)` - Result of the operation
+/// * `Err(String)` - Error message if the target window cannot be found
+#[cfg(all(desktop, feature = "multiwebview"))]
+pub async fn create_webview<R: Runtime>(
+    app: AppHandle<R>,
+    params: CreateWebviewParams,
+) -> Result<CreateWindowResult, String> {
+    use tauri::{LogicalPosition, LogicalSize, WebviewBuilder};
+
+    let url = parse_webview_url(params.url.as_deref());
+    let label = params.label.as_str();
+    let window_label = params.window_id.as_deref().unwrap_or("main");
+
+    let window = app
+        .get_window(window_label)
+        .ok_or_else(|| format!("Window '{window_label}' not found"))?;
+
+    if app.get_webview(label).is_some() {
+        return Ok(CreateWindowResult::failed(
+            label,
+            window_label,
+            &url,
+            format!("Webview '{label}' already exists"),
+        ));
+    }
+
+    let scale = window.scale_factor().unwrap_or(1.0);
+    let window_size = window
+        .inner_size()
+        .map_err(|e| format!("Failed to get window size: {e}"))?
+        .to_logical::<f64>(scale);
+
+    let builder = WebviewBuilder::new(label, url.clone()).on_page_load(|webview, payload| {
+        if payload.event() == PageLoadEvent::Finished {
+            if let Some(script) = registered_scripts_injection(&webview) {
+                let _ = webview.eval(&script);
+            }
+        }
+    });
+
+    let position = LogicalPosition::new(params.x.unwrap_or(0.0), params.y.unwrap_or(0.0));
+    let size = LogicalSize::new(
+        params.width.unwrap_or(window_size.width),
+        params.height.unwrap_or(window_size.height),
+    );
+
+    match window.add_child(builder, position, size) {
+        Ok(webview) => Ok(CreateWindowResult {
+            success: true,
+            label: webview.label().to_string(),
+            window_label: window_label.to_string(),
+            url: url.to_string(),
+            error: None,
+        }),
+        Err(e) => Ok(CreateWindowResult::failed(
+            label,
+            window_label,
+            &url,
+            format!("Failed to create webview: {e}"),
+        )),
+    }
+}
+
+/// Fallback when multiple webviews per window are unavailable.
+#[cfg(not(all(desktop, feature = "multiwebview")))]
+pub async fn create_webview<R: Runtime>(
+    _app: AppHandle<R>,
+    params: CreateWebviewParams,
+) -> Result<CreateWindowResult, String> {
+    let url = parse_webview_url(params.url.as_deref());
+    let error = if cfg!(mobile) {
+        "Creating webviews is not supported on mobile platforms (Android/iOS)."
+    } else {
+        "Adding webviews to a window requires the `multiwebview` feature of \
+         tauri-plugin-mcp-bridge, which enables Tauri's `unstable` feature."
+    };
+
+    Ok(CreateWindowResult::failed(
+        &params.label,
+        params.window_id.as_deref().unwrap_or("main"),
+        &url,
+        error.to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_webview_url() {
+        assert!(matches!(parse_webview_url(None), WebviewUrl::App(_)));
+        assert!(matches!(
+            parse_webview_url(Some("https://example.com/page")),
+            WebviewUrl::External(url) if url.as_str() == "https://example.com/page"
+        ));
+        assert!(matches!(
+            parse_webview_url(Some("/settings")),
+            WebviewUrl::App(path) if path.to_str() == Some("settings")
+        ));
+        assert!(matches!(
+            parse_webview_url(Some("index.html#/about")),
+            WebviewUrl::App(path) if path.to_str() == Some("index.html#/about")
+        ));
+    }
+
+    #[cfg(desktop)]
+    #[tokio::test]
+    async fn test_create_window_rejects_duplicate_label() {
+        let app = tauri::test::mock_app();
+        let params = CreateWindowParams {
+            label: "extra".to_string(),
+            ..Default::default()
+        };
+
+        let result = create_window(app.handle().clone(), params.clone())
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(app.get_webview_window("extra").is_some());
+
+        let result = create_window(app.handle().clone(), params).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("already exists"));
+    }
+}
@@ -4,7 +4,7 @@
 //! by the webview JavaScript and forwards them as JSON broadcasts over the
 //! WebSocket broadcast channel so the MCP server can receive them.
 
-use crate::logging::mcp_log_info;
+use crate::logging::{mcp_log_debug, mcp_log_info};
 use serde_json;
 use tauri::{AppHandle, Listener, Runtime};
 use tokio::sync::broadcast;
@@ -33,7 +33,7 @@ pub fn setup_element_picker_listeners<R: Runtime>(
             "payload": serde_json::from_str::<serde_json::Value>(payload_str).unwrap_or(serde_json::Value::Null)
         });
         let _ = tx_picked.send(broadcast_msg.to_string());
-        mcp_log_info("ELEMENT_PICKER", "Forwarded element_picked event");
+        mcp_log_debug("ELEMENT_PICKER", "Forwarded element_picked event");
     });
 
     // Forward __element_pointed events
@@ -45,7 +45,7 @@ pub fn setup_element_picker_listeners<R: Runtime>(
             "payload": serde_json::from_str::<serde_json::Value>(payload_str).unwrap_or(serde_json::Value::Null)
         });
         let _ = tx_pointed.send(broadcast_msg.to_string());
-        mcp_log_info("ELEMENT_PICKER", "Forwarded element_pointed event");
+        mcp_log_debug("ELEMENT_PICKER", "Forwarded element_pointed event");
     });
 
     mcp_log_info(
@@ -1,19 +1,143 @@
 //! Event emission.
 
+use super::wait_for_event::{EventWaiter, WaitForEventParams};
+use crate::event_tap::with_emit_context;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
-use tauri::{command, AppHandle, Emitter, Runtime};
+use tauri::{command, AppHandle, Emitter, EventTarget, Runtime};
+
+/// Default time to wait for a listener to acknowledge an event.
+pub const DEFAULT_ACK_TIMEOUT_MS: u64 = 5_000;
+
+/// Parameters for emitting an event over the WebSocket `invoke_tauri` path.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmitEventParams {
+    /// Name of the event to emit
+    pub event_name: String,
+    /// JSON payload to send with the event
+    #[serde(default)]
+    pub payload: Value,
+    /// Emit only to this target (a label string is shorthand for `AnyLabel`)
+    #[serde(default, deserialize_with = "deserialize_target")]
+    pub target: Option<EventTarget>,
+    /// Emit only to listeners matching any of these targets
+    #[serde(default, deserialize_with = "deserialize_targets")]
+    pub targets: Option<Vec<EventTarget>>,
+    /// Wait for a listener to emit `ack_event` before returning
+    #[serde(default)]
+    pub wait_for_ack: bool,
+    /// Event a listener emits to acknowledge (defaults to `<eventName>:ack`)
+    pub ack_event: Option<String>,
+    /// How long to wait for the acknowledgement, in milliseconds (default 5000)
+    pub ack_timeout_ms: Option<u64>,
+}
+
+/// Acknowledgement received for an emitted event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmitAck {
+    /// Name of the acknowledgement event
+    pub ack_event: String,
+    /// Payload the listener sent with the acknowledgement
+    pub payload: Value,
+}
+
+/// A target given either as a Tauri `EventTarget` or as a bare label.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TargetArg {
+    Label(String),
+    Target(EventTarget),
+}
+
+impl From<TargetArg> for EventTarget {
+    fn from(arg: TargetArg) -> Self {
+        match arg {
+            TargetArg::Label(label) => EventTarget::AnyLabel { label },
+            TargetArg::Target(target) => target,
+        }
+    }
+}
+
+fn deserialize_target<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<EventTarget>, D::Error> {
+    Ok(Option::<TargetArg>::deserialize(deserializer)?.map(Into::into))
+}
+
+fn deserialize_targets<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Vec<EventTarget>>, D::Error> {
+    Ok(Option::<Vec<TargetArg>>::deserialize(deserializer)?
+        .map(|targets| targets.into_iter().map(Into::into).collect()))
+}
+
+/// Returns whether a listener registered for `listener` receives an event
+/// sent to `target`, following Tauri's `emit_to` rules.
+pub fn target_matches(target: &EventTarget, listener: &EventTarget) -> bool {
+    match (target, listener) {
+        (EventTarget::Any, _) => true,
+        (EventTarget::AnyLabel { label }, listener) => match listener {
+            EventTarget::Window { label: l }
+            | EventTarget::Webview { label: l }
+            | EventTarget::WebviewWindow { label: l }
+            | EventTarget::AnyLabel { label: l } => l == label,
+            _ => false,
+        },
+        (target, listener) => target == listener,
+    }
+}
+
+/// Emits an event to every listener, a single target or a set of targets.
+fn emit_targeted<R: Runtime>(
+    app: &AppHandle<R>,
+    event_name: &str,
+    payload: Value,
+    target: Option<EventTarget>,
+    targets: Option<Vec<EventTarget>>,
+) -> Result<(), String> {
+    let result = match (target, targets) {
+        (Some(_), Some(_)) => return Err("Specify either target or targets, not both".to_string()),
+        (None, Some(targets)) if targets.is_empty() => {
+            return Err("targets must not be empty".to_string())
+        }
+        (None, None) => with_emit_context("bridge", Value::from("any"), || {
+            app.emit(event_name, payload)
+        }),
+        (Some(target), None) => {
+            let context = serde_json::to_value(&target).unwrap_or(Value::Null);
+            with_emit_context("bridge", context, || {
+                app.emit_to(target, event_name, payload)
+            })
+        }
+        (None, Some(targets)) => {
+            let context = serde_json::to_value(&targets).unwrap_or(Value::Null);
+            with_emit_context("bridge", context, || {
+                app.emit_filter(event_name, payload, |listener| {
+                    targets.iter().any(|t| target_matches(t, listener))
+                })
+            })
+        }
+    };
+    result.map_err(|e| format!("Failed to emit event: {e}"))
+}
 
 /// Emits a custom event to the application.
 ///
 /// Triggers a named event with a JSON payload that can be listened to by
 /// event handlers in the frontend or backend. Useful for testing event
-/// handling and triggering custom application behavior.
+/// handling and triggering custom application behavior. Without a target the
+/// event is broadcast to every listener; with `target` it is sent with
+/// `emit_to`, and with `targets` with `emit_filter`.
 ///
 /// # Arguments
 ///
 /// * `app` - The Tauri application handle
 /// * `event_name` - Name of the event to emit
 /// * `payload` - JSON payload to send with the event
+/// * `target` - Optional `EventTarget` (or label) to send the event to
+/// * `targets` - Optional list of `EventTarget`s (or labels) to send the event to
 ///
 /// # Returns
 ///
@@ -35,14 +159,226 @@ use tauri::{command, AppHandle, Emitter, Runtime};
 ///   eventName: 'custom-event',
 ///   payload: { data: 'test' }
 /// });
+///
+/// // Emit to the "settings" window only
+/// await invoke('plugin:mcp-bridge|emit_event', {
+///   eventName: 'custom-event',
+///   payload: { data: 'test' },
+///   target: { kind: 'WebviewWindow', label: 'settings' }
+/// });
 /// ```
 #[command]
 pub async fn emit_event<R: Runtime>(
     app: AppHandle<R>,
     event_name: String,
     payload: Value,
+    target: Option<Value>,
+    targets: Option<Value>,
 ) -> Result<String, String> {
-    app.emit(&event_name, payload)
-        .map_err(|e| format!("Failed to emit event: {e}"))?;
+    let target = target
+        .map(serde_json::from_value::<TargetArg>)
+        .transpose()
+        .map_err(|e| format!("Invalid target: {e}"))?
+        .map(Into::into);
+    let targets = targets
+        .map(serde_json::from_value::<Vec<TargetArg>>)
+        .transpose()
+        .map_err(|e| format!("Invalid targets: {e}"))?
+        .map(|targets| targets.into_iter().map(Into::into).collect());
+
+    emit_targeted(&app, &event_name, payload, target, targets)?;
     Ok(format!("Event '{event_name}' emitted successfully"))
 }
+
+/// Emits an event and waits for a listener to acknowledge it.
+///
+/// The acknowledgement listener is registered before emitting, so a listener
+/// that acknowledges synchronously is not missed.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri application handle
+/// * `params` - Event, payload, targets and acknowledgement options
+///
+/// # Returns
+///
+/// * `Ok(EmitAck)` - The acknowledgement event and its payload
+/// * `Err(String)` - Error message if emission fails or no ack arrives in time
+pub async fn emit_event_with_ack<R: Runtime>(
+    app: AppHandle<R>,
+    params: EmitEventParams,
+) -> Result<EmitAck, String> {
+    let ack_event = params
+        .ack_event
+        .clone()
+        .unwrap_or_else(|| format!("{}:ack", params.event_name));
+    let timeout_ms = params.ack_timeout_ms.unwrap_or(DEFAULT_ACK_TIMEOUT_MS);
+
+    let waiter = EventWaiter::register(
+        &app,
+        WaitForEventParams {
+            event: ack_event.clone(),
+            timeout_ms: Some(timeout_ms),
+            ..Default::default()
+        },
+    )
+    .map_err(|e| format!("Invalid ack event: {e}"))?;
+
+    emit_targeted(
+        &app,
+        &params.event_name,
+        params.payload,
+        params.target,
+        params.targets,
+    )?;
+
+    match waiter.wait().await {
+        Ok(ack) => Ok(EmitAck {
+            ack_event,
+            payload: ack.payload,
+        }),
+        Err(_) => Err(format!(
+            "Event '{}' was emitted but no '{ack_event}' acknowledgement arrived within {timeout_ms}ms",
+            params.event_name
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_tap::parse_payload;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use tauri::Listener;
+
+    #[test]
+    fn test_params_accept_labels_and_targets() {
+        let params: EmitEventParams = serde_json::from_value(json!({
+            "eventName": "refresh",
+            "target": "settings",
+            "targets": [{ "kind": "Window", "label": "main" }, "other"]
+        }))
+        .unwrap();
+        assert_eq!(
+            params.target,
+            Some(EventTarget::AnyLabel {
+                label: "settings".into()
+            })
+        );
+        assert_eq!(
+            params.targets.unwrap(),
+            vec![
+                EventTarget::Window {
+                    label: "main".into()
+                },
+                EventTarget::AnyLabel {
+                    label: "other".into()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_target_matches() {
+        let window = EventTarget::Window {
+            label: "main".into(),
+        };
+        assert!(target_matches(&EventTarget::Any, &window));
+        assert!(target_matches(&EventTarget::labeled("main"), &window));
+        assert!(!target_matches(&EventTarget::labeled("other"), &window));
+        assert!(!target_matches(&EventTarget::App, &window));
+        assert!(target_matches(&window.clone(), &window));
+    }
+
+    #[tokio::test]
+    async fn test_targeted_emit_reaches_only_matching_listeners() {
+        let app = tauri::test::mock_app();
+        let handle = app.handle().clone();
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        for label in ["main", "settings"] {
+            let window =
+                tauri::WebviewWindowBuilder::new(&app, label, tauri::WebviewUrl::default())
+                    .build()
+                    .unwrap();
+            let received = received.clone();
+            window.listen("refresh", move |_| {
+                received.lock().unwrap().push(label);
+            });
+        }
+
+        emit_event(
+            handle.clone(),
+            "refresh".into(),
+            json!(null),
+            Some(json!("settings")),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(*received.lock().unwrap(), vec!["settings"]);
+
+        emit_event(
+            handle.clone(),
+            "refresh".into(),
+            json!(null),
+            None,
+            Some(json!([{ "kind": "WebviewWindow", "label": "main" }])),
+        )
+        .await
+        .unwrap();
+        assert_eq!(*received.lock().unwrap(), vec!["settings", "main"]);
+
+        let err = emit_event(
+            handle,
+            "refresh".into(),
+            json!(null),
+            Some(json!("main")),
+            Some(json!(["settings"])),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("either target or targets"));
+    }
+
+    #[tokio::test]
+    async fn test_emit_with_ack() {
+        let app = tauri::test::mock_app();
+        let handle = app.handle().clone();
+
+        let responder = handle.clone();
+        handle.listen_any("ping", move |event| {
+            let payload = parse_payload(event.payload());
+            responder
+                .emit("ping:ack", json!({ "got": payload }))
+                .unwrap();
+        });
+
+        let ack = emit_event_with_ack(
+            handle.clone(),
+            EmitEventParams {
+                event_name: "ping".into(),
+                payload: json!(1),
+                wait_for_ack: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(ack.ack_event, "ping:ack");
+        assert_eq!(ack.payload, json!({ "got": 1 }));
+
+        let err = emit_event_with_ack(
+            handle,
+            EmitEventParams {
+                event_name: "unanswered".into(),
+                ack_timeout_ms: Some(10),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("no 'unanswered:ack' acknowledgement"));
+    }
+}
@@ -0,0 +1,244 @@
+//! Event tap commands: subscribe to Tauri events and query what was captured.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Listener, Manager, Runtime};
+
+use crate::event_tap::{
+    current_emit_context, is_valid_event_name, parse_payload, EventTapState, TauriEventRecord,
+    TAURI_EVENT_TYPE,
+};
+use crate::window_events::BroadcastSender;
+
+/// Parameters for starting the event tap.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartEventTapParams {
+    /// Event names to capture (e.g. "state-changed", "download:progress")
+    pub events: Vec<String>,
+    /// Also broadcast each captured event as a `tauri_event` message
+    #[serde(default)]
+    pub stream: bool,
+}
+
+/// Parameters for querying captured events.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTauriEventsParams {
+    /// Only return events whose name matches this glob pattern (e.g. "download:*")
+    pub pattern: Option<String>,
+    /// Only return events with a sequence number greater than this
+    pub since: Option<u64>,
+}
+
+/// Current state of the event tap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventTapStatus {
+    /// Whether events are being captured
+    pub enabled: bool,
+    /// Whether captured events are broadcast
+    pub stream: bool,
+    /// Event names the tap is subscribed to
+    pub events: Vec<String>,
+}
+
+fn tap_state<R: Runtime>(app: &AppHandle<R>) -> Result<EventTapState, String> {
+    app.try_state::<EventTapState>()
+        .map(|tap| tap.inner().clone())
+        .ok_or_else(|| "Event tap is not initialized".to_string())
+}
+
+/// Starts capturing the given Tauri events.
+///
+/// Replaces any previous subscriptions and clears previously captured events.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri application handle
+/// * `params` - Event names and whether to stream captured events
+///
+/// # Returns
+///
+/// * `Ok(EventTapStatus)` - The tap's state after starting
+/// * `Err(String)` - Error message if an event name is invalid
+pub async fn start_event_tap<R: Runtime>(
+    app: AppHandle<R>,
+    params: StartEventTapParams,
+) -> Result<EventTapStatus, String> {
+    if let Some(name) = params.events.iter().find(|n| !is_valid_event_name(n)) {
+        return Err(format!(
+            "Invalid event name '{name}': only alphanumeric, '-', '/', ':' and '_' are allowed \
+             (Tauri has no wildcard listeners; use patterns with get_tauri_events)"
+        ));
+    }
+
+    let tap = tap_state(&app)?;
+    let mut state = tap.lock().map_err(|e| format!("Lock error: {e}"))?;
+
+    for id in state.take_subscriptions() {
+        app.unlisten(id);
+    }
+
+    for name in &params.events {
+        if state.is_subscribed(name) {
+            continue;
+        }
+        let handle = app.clone();
+        let event_name = name.clone();
+        let id = app.listen_any(name.clone(), move |event| {
+            record_event(&handle, &event_name, event.payload());
+        });
+        state.add_subscription(name, id);
+    }
+
+    state.enabled = true;
+    state.stream = params.stream;
+    state.clear();
+
+    Ok(EventTapStatus {
+        enabled: state.enabled,
+        stream: state.stream,
+        events: state.subscriptions(),
+    })
+}
+
+/// Records a captured event and broadcasts it if streaming is on.
+fn record_event<R: Runtime>(app: &AppHandle<R>, name: &str, payload: &str) {
+    let Some(tap) = app.try_state::<EventTapState>() else {
+        return;
+    };
+    let (source, target) = current_emit_context();
+
+    let streamed = {
+        let Ok(mut tap) = tap.lock() else {
+            return;
+        };
+        let stream = tap.stream;
+        tap.record(name, parse_payload(payload), source, target)
+            .filter(|_| stream)
+    };
+
+    if let (Some(record), Some(sender)) = (streamed, app.try_state::<BroadcastSender>()) {
+        if let Ok(mut message) = serde_json::to_value(record) {
+            message["type"] = TAURI_EVENT_TYPE.into();
+            let _ = sender.0.send(message.to_string());
+        }
+    }
+}
+
+/// Stops capturing and removes the tap's listeners. Captured events remain
+/// available until the tap is started again or cleared.
+pub async fn stop_event_tap<R: Runtime>(app: AppHandle<R>) -> Result<EventTapStatus, String> {
+    let tap = tap_state(&app)?;
+    let mut state = tap.lock().map_err(|e| format!("Lock error: {e}"))?;
+
+    for id in state.take_subscriptions() {
+        app.unlisten(id);
+    }
+    state.enabled = false;
+    state.stream = false;
+
+    Ok(EventTapStatus {
+        enabled: false,
+        stream: false,
+        events: Vec::new(),
+    })
+}
+
+/// Returns captured events, optionally filtered by name pattern and sequence number.
+pub async fn get_tauri_events<R: Runtime>(
+    app: AppHandle<R>,
+    params: GetTauriEventsParams,
+) -> Result<Vec<TauriEventRecord>, String> {
+    let tap = tap_state(&app)?;
+    let state = tap.lock().map_err(|e| format!("Lock error: {e}"))?;
+    Ok(state.get_events(params.pattern.as_deref(), params.since))
+}
+
+/// Removes all captured events.
+pub async fn clear_tauri_events<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    let tap = tap_state(&app)?;
+    let mut state = tap.lock().map_err(|e| format!("Lock error: {e}"))?;
+    state.clear();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_tap::{with_emit_context, EventTap};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use tauri::Emitter;
+    use tokio::sync::broadcast;
+
+    #[tokio::test]
+    async fn test_tap_captures_emitted_events() {
+        let app = tauri::test::mock_app();
+        app.manage::<EventTapState>(Arc::new(Mutex::new(EventTap::new())));
+        let (tx, mut rx) = broadcast::channel(8);
+        app.manage(BroadcastSender(tx));
+        let handle = app.handle().clone();
+
+        let status = start_event_tap(
+            handle.clone(),
+            StartEventTapParams {
+                events: vec!["state-changed".into(), "download:progress".into()],
+                stream: true,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(status.events, vec!["download:progress", "state-changed"]);
+
+        handle.emit("state-changed", json!({ "count": 1 })).unwrap();
+        handle.emit("not-tapped", json!(null)).unwrap();
+        with_emit_context("bridge", json!("any"), || {
+            handle.emit("download:progress", 42).unwrap();
+        });
+
+        let events = get_tauri_events(handle.clone(), GetTauriEventsParams::default())
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].payload, json!({ "count": 1 }));
+        assert_eq!(events[0].source, None);
+        let backend_emit = serde_json::to_value(&events[0]).unwrap();
+        assert!(backend_emit.get("source").is_none() && backend_emit.get("target").is_none());
+        assert_eq!(events[1].source.as_deref(), Some("bridge"));
+
+        let streamed: serde_json::Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(streamed["type"], "tauri_event");
+        assert_eq!(streamed["event"], "state-changed");
+
+        stop_event_tap(handle.clone()).await.unwrap();
+        handle.emit("state-changed", json!({ "count": 2 })).unwrap();
+        let events = get_tauri_events(
+            handle.clone(),
+            GetTauriEventsParams {
+                pattern: Some("state-*".into()),
+                since: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(events.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_start_rejects_wildcard_names() {
+        let app = tauri::test::mock_app();
+        app.manage::<EventTapState>(Arc::new(Mutex::new(EventTap::new())));
+
+        let err = start_event_tap(
+            app.handle().clone(),
+            StartEventTapParams {
+                events: vec!["download:*".into()],
+                stream: false,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("Invalid event name"));
+    }
+}
@@ -1,13 +1,65 @@
 //! IPC monitoring commands.
 
+use crate::ipc_faults::{FaultSummary, SharedFaultInjector};
 use crate::monitor::{current_timestamp, IPCEvent, IPCMonitorState};
-use tauri::{command, Runtime, State, WebviewWindow};
+use serde::{Deserialize, Serialize};
+use tauri::{command, Manager, Runtime, State, WebviewWindow};
+
+/// Script that installs the bridge.js IPC hook.
+pub(crate) const START_IPC_HOOK: &str =
+    "window.__MCP_START_IPC_MONITOR__ && window.__MCP_START_IPC_MONITOR__();";
+
+/// Script that removes the bridge.js IPC hook.
+const STOP_IPC_HOOK: &str = "window.__MCP_STOP_IPC_MONITOR__ && window.__MCP_STOP_IPC_MONITOR__();";
+
+/// Result of [`get_ipc_events`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IPCEventsResult {
+    /// Captured IPC events
+    pub events: Vec<IPCEvent>,
+    /// Faults injected by each fault rule, counted whether or not the monitor
+    /// is running
+    pub faults: Vec<FaultSummary>,
+}
+
+/// Installs the bridge.js IPC hook in every window the monitor or one of its
+/// sessions captures, and removes it from the others.
+///
+/// Windows opened later get the hook when bridge.js reports their page load.
+pub(crate) fn sync_ipc_hooks<R: Runtime, M: Manager<R>>(manager: &M) {
+    let Some(monitor) = manager.try_state::<IPCMonitorState>() else {
+        return;
+    };
+    let windows: Vec<(WebviewWindow<R>, bool)> = {
+        let Ok(monitor) = monitor.lock() else {
+            return;
+        };
+        manager
+            .webview_windows()
+            .into_values()
+            .map(|window| {
+                let capturing = monitor.is_capturing(window.label());
+                (window, capturing)
+            })
+            .collect()
+    };
+
+    for (window, capturing) in windows {
+        let _ = window.eval(if capturing {
+            START_IPC_HOOK
+        } else {
+            STOP_IPC_HOOK
+        });
+    }
+}
 
 /// Starts IPC monitoring to capture Tauri command calls.
 ///
 /// Enables the IPC monitor which will begin capturing all subsequent Tauri
 /// command invocations with their arguments, results, and timing information.
-/// Previous events are cleared when monitoring starts.
+/// Previous events are cleared when monitoring starts. Calls from every
+/// window are captured, including windows opened later.
 ///
 /// # Arguments
 ///
@@ -36,11 +88,13 @@ pub async fn start_ipc_monitor<R: Runtime>(
     window: WebviewWindow<R>,
     monitor: State<'_, IPCMonitorState>,
 ) -> Result<String, String> {
-    let mut mon = monitor.lock().map_err(|e| format!("Lock error: {e}"))?;
-    mon.start();
+    monitor
+        .lock()
+        .map_err(|e| format!("Lock error: {e}"))?
+        .start();
 
     // Trigger JS-side IPC interception
-    let _ = window.eval("window.__MCP_START_IPC_MONITOR__ && window.__MCP_START_IPC_MONITOR__();");
+    sync_ipc_hooks(&window);
 
     Ok("IPC monitoring started".to_string())
 }
@@ -48,7 +102,8 @@ pub async fn start_ipc_monitor<R: Runtime>(
 /// Stops IPC monitoring.
 ///
 /// Disables the IPC monitor, stopping the capture of new events. Previously
-/// captured events remain available until monitoring is restarted.
+/// captured events remain available until monitoring is restarted. Monitoring
+/// sessions keep capturing.
 ///
 /// # Arguments
 ///
@@ -78,11 +133,13 @@ pub async fn stop_ipc_monitor<R: Runtime>(
     window: WebviewWindow<R>,
     monitor: State<'_, IPCMonitorState>,
 ) -> Result<String, String> {
-    // Trigger JS-side IPC interception stop first
-    let _ = window.eval("window.__MCP_STOP_IPC_MONITOR__ && window.__MCP_STOP_IPC_MONITOR__();");
+    monitor
+        .lock()
+        .map_err(|e| format!("Lock error: {e}"))?
+        .stop();
 
-    let mut mon = monitor.lock().map_err(|e| format!("Lock error: {e}"))?;
-    mon.stop();
+    // Stop JS-side IPC interception in windows no session captures
+    sync_ipc_hooks(&window);
     Ok("IPC monitoring stopped".to_string())
 }
 
@@ -95,11 +152,12 @@ pub async fn stop_ipc_monitor<R: Runtime>(
 /// # Arguments
 ///
 /// * `monitor` - Shared state for the IPC monitor
+/// * `faults` - Shared state for the fault injector
 ///
 /// # Returns
 ///
-/// * `Ok(Vec<IPCEvent>)` - List of captured IPC events
-/// * `Err(String)` - Error message if the monitor lock fails
+/// * `Ok(IPCEventsResult)` - Captured IPC events and a per-rule fault summary
+/// * `Err(String)` - Error message if a lock fails
 ///
 /// # Examples
 ///
@@ -108,11 +166,14 @@ pub async fn stop_ipc_monitor<R: Runtime>(
 ///
 /// await invoke('plugin:mcp-bridge|start_ipc_monitor');
 /// // ... perform some IPC calls ...
-/// const events = await invoke('plugin:mcp-bridge|get_ipc_events');
+/// const { events, faults } = await invoke('plugin:mcp-bridge|get_ipc_events');
 ///
 /// events.forEach(event => {
 ///   console.log(`${event.command} took ${event.duration_ms}ms`);
 /// });
+/// faults.forEach(fault => {
+///   console.log(`${fault.rule}: ${fault.failed} of ${fault.calls} calls failed`);
+/// });
 /// ```
 ///
 /// # See Also
@@ -121,18 +182,30 @@ pub async fn stop_ipc_monitor<R: Runtime>(
 /// * [`start_ipc_monitor`] - Start monitoring
 /// * [`stop_ipc_monitor`] - Stop monitoring
 #[command]
-pub async fn get_ipc_events(monitor: State<'_, IPCMonitorState>) -> Result<Vec<IPCEvent>, String> {
-    let mon = monitor.lock().map_err(|e| format!("Lock error: {e}"))?;
-    Ok(mon.get_events())
+pub async fn get_ipc_events(
+    monitor: State<'_, IPCMonitorState>,
+    faults: State<'_, SharedFaultInjector>,
+) -> Result<IPCEventsResult, String> {
+    let events = monitor
+        .lock()
+        .map_err(|e| format!("Lock error: {e}"))?
+        .get_events();
+    let faults = faults
+        .lock()
+        .map_err(|e| format!("Lock error: {e}"))?
+        .summary();
+    Ok(IPCEventsResult { events, faults })
 }
 
 /// Reports an IPC event from JavaScript.
 ///
 /// This command is called by the bridge.js IPC interceptor to report captured
-/// IPC calls. It adds the event to the monitor if monitoring is enabled.
+/// IPC calls. It adds the event to the monitor if monitoring is enabled, and to
+/// the monitoring sessions that capture the calling window.
 ///
 /// # Arguments
 ///
+/// * `window` - The window that made the call
 /// * `monitor` - Shared state for the IPC monitor
 /// * `params` - The IPC event details from JavaScript
 ///
@@ -141,7 +214,8 @@ pub async fn get_ipc_events(monitor: State<'_, IPCMonitorState>) -> Result<Vec<I
 /// * `Ok(())` - Event recorded (or ignored if monitoring is disabled)
 /// * `Err(String)` - Error message if the monitor lock fails
 #[command]
-pub async fn report_ipc_event(
+pub async fn report_ipc_event<R: Runtime>(
+    window: WebviewWindow<R>,
     monitor: State<'_, IPCMonitorState>,
     command: String,
     args: serde_json::Value,
@@ -158,6 +232,11 @@ pub async fn report_ipc_event(
         return Ok(());
     }
 
+    // App commands are recorded by the backend when its capture is installed
+    if crate::ipc_capture::is_installed(&window) && !command.starts_with("plugin:") {
+        return Ok(());
+    }
+
     let mut mon = monitor.lock().map_err(|e| format!("Lock error: {e}"))?;
 
     let event = IPCEvent {
@@ -167,6 +246,9 @@ pub async fn report_ipc_event(
         result,
         error,
         duration_ms,
+        source: Some("frontend".to_string()),
+        fault: None,
+        window: Some(window.label().to_string()),
     };
 
     mon.add_event(event);
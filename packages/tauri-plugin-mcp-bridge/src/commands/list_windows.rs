@@ -1,11 +1,24 @@
 //! Window listing and discovery.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tauri::{command, AppHandle, Manager, Runtime};
 
+use super::window_info::{window_details, WindowDetails};
+
+/// How much information `list_windows` returns per window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WindowDetailLevel {
+    /// Label, title, URL, focus and visibility
+    #[default]
+    Basic,
+    /// Also includes geometry, monitor and state flags (see `WindowDetails`)
+    Full,
+}
+
 /// Information about a webview window.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WindowInfo {
     /// The unique label/identifier for this window
@@ -20,16 +33,22 @@ pub struct WindowInfo {
     pub visible: bool,
     /// Whether this is the main window (label == "main")
     pub is_main: bool,
+    /// Geometry, monitor and state flags (only with `WindowDetailLevel::Full`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<WindowDetails>,
 }
 
 /// Lists all open webview windows in the application.
 ///
 /// Returns detailed information about each window including its label, title,
-/// URL, focus state, and visibility.
+/// URL, focus state, and visibility. With `detail: "full"`, each entry also
+/// carries a `details` object with the same geometry and state fields as
+/// `get_window_info`.
 ///
 /// # Arguments
 ///
 /// * `app` - The Tauri application handle
+/// * `detail` - Optional detail level (defaults to `basic`)
 ///
 /// # Returns
 ///
@@ -43,9 +62,15 @@ pub struct WindowInfo {
 ///
 /// const windows = await invoke('plugin:mcp-bridge|list_windows');
 /// console.log(`Found ${windows.length} windows`);
+///
+/// const detailed = await invoke('plugin:mcp-bridge|list_windows', { detail: 'full' });
 /// ```
 #[command]
-pub async fn list_windows<R: Runtime>(app: AppHandle<R>) -> Result<Value, String> {
+pub async fn list_windows<R: Runtime>(
+    app: AppHandle<R>,
+    detail: Option<WindowDetailLevel>,
+) -> Result<Value, String> {
+    let detail = detail.unwrap_or_default();
     let windows = app.webview_windows();
     let mut window_list: Vec<WindowInfo> = Vec::new();
 
@@ -55,6 +80,10 @@ pub async fn list_windows<R: Runtime>(app: AppHandle<R>) -> Result<Value, String
         let focused = window.is_focused().unwrap_or(false);
         let visible = window.is_visible().unwrap_or(false);
         let is_main = label == "main";
+        let details = match detail {
+            WindowDetailLevel::Basic => None,
+            WindowDetailLevel::Full => window_details(window).ok(),
+        };
 
         window_list.push(WindowInfo {
             label: label.clone(),
@@ -63,6 +92,7 @@ pub async fn list_windows<R: Runtime>(app: AppHandle<R>) -> Result<Value, String
             focused,
             visible,
             is_main,
+            details,
         });
     }
 
@@ -81,7 +111,7 @@ pub async fn list_windows<R: Runtime>(app: AppHandle<R>) -> Result<Value, String
 }
 
 /// Context about which window was used for an operation.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WindowContext {
     /// The label of the window that was used
@@ -164,3 +194,26 @@ pub fn resolve_window<R: Runtime>(
     app.get_webview_window(&label)
         .ok_or_else(|| format!("Window '{label}' not found"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_list_windows_detail_level() {
+        let app = tauri::test::mock_app();
+        tauri::WebviewWindowBuilder::new(&app, "main", tauri::WebviewUrl::default())
+            .build()
+            .unwrap();
+
+        let basic = list_windows(app.handle().clone(), None).await.unwrap();
+        assert_eq!(basic[0]["label"], "main");
+        assert!(basic[0].get("details").is_none());
+
+        let full = list_windows(app.handle().clone(), Some(WindowDetailLevel::Full))
+            .await
+            .unwrap();
+        assert!(full[0]["details"]["scaleFactor"].is_number());
+        assert!(full[0]["details"]["fullscreen"].is_boolean());
+    }
+}
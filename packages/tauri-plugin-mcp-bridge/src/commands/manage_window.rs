@@ -0,0 +1,260 @@
+//! Window management operations: position, state, focus, visibility and title.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+
+#[cfg(desktop)]
+use tauri::{LogicalPosition, PhysicalPosition};
+
+#[cfg(desktop)]
+use super::list_windows::resolve_window;
+
+/// A window management operation.
+///
+/// Serialized with an `action` tag, e.g.
+/// `{"action": "setPosition", "x": 100, "y": 50}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(
+    tag = "action",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum WindowAction {
+    /// Moves the window's top-left corner to `x`, `y`
+    SetPosition {
+        x: i32,
+        y: i32,
+        /// Whether to use logical (true) or physical (false) pixels. Defaults to logical.
+        #[serde(default = "default_logical")]
+        logical: bool,
+    },
+    /// Minimizes the window
+    Minimize,
+    /// Restores a minimized window
+    Unminimize,
+    /// Maximizes the window
+    Maximize,
+    /// Restores a maximized window
+    Unmaximize,
+    /// Enters or leaves fullscreen; toggles when `fullscreen` is omitted
+    SetFullscreen { fullscreen: Option<bool> },
+    /// Brings the window to the front and focuses it
+    Focus,
+    /// Shows a hidden window
+    Show,
+    /// Hides the window
+    Hide,
+    /// Closes the window
+    Close,
+    /// Keeps the window above all other windows, or stops doing so
+    SetAlwaysOnTop { always_on_top: bool },
+    /// Sets the window title
+    SetTitle { title: String },
+    /// Centers the window on its current monitor
+    Center,
+}
+
+impl WindowAction {
+    /// Returns the action's name as used in the `action` tag.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SetPosition { .. } => "setPosition",
+            Self::Minimize => "minimize",
+            Self::Unminimize => "unminimize",
+            Self::Maximize => "maximize",
+            Self::Unmaximize => "unmaximize",
+            Self::SetFullscreen { .. } => "setFullscreen",
+            Self::Focus => "focus",
+            Self::Show => "show",
+            Self::Hide => "hide",
+            Self::Close => "close",
+            Self::SetAlwaysOnTop { .. } => "setAlwaysOnTop",
+            Self::SetTitle { .. } => "setTitle",
+            Self::Center => "center",
+        }
+    }
+}
+
+fn default_logical() -> bool {
+    true
+}
+
+/// Parameters for a window management operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManageWindowParams {
+    /// Optional window label (defaults to "main")
+    pub window_id: Option<String>,
+    /// The operation to perform
+    #[serde(flatten)]
+    pub action: WindowAction,
+}
+
+/// Result of a window management operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManageWindowResult {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// The window that was operated on
+    pub window_label: String,
+    /// The operation that was performed
+    pub action: String,
+    /// Error message if the operation failed
+    pub error: Option<String>,
+}
+
+/// Performs a window management operation.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri application handle
+/// * `params` - The operation and optional window label
+///
+/// # Returns
+///
+/// * `Ok(ManageWindowResult)` - Result of the operation
+/// * `Err(String)` - Error message if the window cannot be found
+///
+/// # Notes
+///
+/// - Window managers may ignore some requests (e.g. always-on-top on some
+///   Linux desktops) without reporting an error
+/// - On mobile platforms (Android/iOS), these operations are not supported and return an error
+#[cfg(desktop)]
+pub async fn manage_window<R: Runtime>(
+    app: AppHandle<R>,
+    params: ManageWindowParams,
+) -> Result<ManageWindowResult, String> {
+    let window_label = params
+        .window_id
+        .clone()
+        .unwrap_or_else(|| "main".to_string());
+    let window = resolve_window(&app, params.window_id)?;
+    let action = params.action;
+
+    let result = match &action {
+        WindowAction::SetPosition { x, y, logical } => {
+            if *logical {
+                window.set_position(LogicalPosition::new(*x, *y))
+            } else {
+                window.set_position(PhysicalPosition::new(*x, *y))
+            }
+        }
+        WindowAction::Minimize => window.minimize(),
+        WindowAction::Unminimize => window.unminimize(),
+        WindowAction::Maximize => window.maximize(),
+        WindowAction::Unmaximize => window.unmaximize(),
+        WindowAction::SetFullscreen { fullscreen } => match fullscreen {
+            Some(fullscreen) => window.set_fullscreen(*fullscreen),
+            None => window
+                .is_fullscreen()
+                .and_then(|current| window.set_fullscreen(!current)),
+        },
+        WindowAction::Focus => window.set_focus(),
+        WindowAction::Show => window.show(),
+        WindowAction::Hide => window.hide(),
+        WindowAction::Close => window.close(),
+        WindowAction::SetAlwaysOnTop { always_on_top } => window.set_always_on_top(*always_on_top),
+        WindowAction::SetTitle { title } => window.set_title(title),
+        WindowAction::Center => window.center(),
+    };
+
+    Ok(ManageWindowResult {
+        success: result.is_ok(),
+        window_label,
+        action: action.name().to_string(),
+        error: result
+            .err()
+            .map(|e| format!("Failed to {} window: {e}", action.name())),
+    })
+}
+
+/// Mobile implementation - returns unsupported error with clear explanation for the agent.
+#[cfg(mobile)]
+pub async fn manage_window<R: Runtime>(
+    _app: AppHandle<R>,
+    params: ManageWindowParams,
+) -> Result<ManageWindowResult, String> {
+    let window_label = params
+        .window_id
+        .clone()
+        .unwrap_or_else(|| "main".to_string());
+
+    Ok(ManageWindowResult {
+        success: false,
+        window_label,
+        action: params.action.name().to_string(),
+        error: Some(
+            "Window management is not supported on mobile platforms (Android/iOS). \
+             Windows are controlled by the operating system."
+                .to_string(),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_params_deserialize() {
+        let params: ManageWindowParams = serde_json::from_value(json!({
+            "windowId": "settings",
+            "action": "setPosition",
+            "x": 10,
+            "y": -20
+        }))
+        .unwrap();
+        assert_eq!(params.window_id.as_deref(), Some("settings"));
+        assert_eq!(
+            params.action,
+            WindowAction::SetPosition {
+                x: 10,
+                y: -20,
+                logical: true
+            }
+        );
+
+        let params: ManageWindowParams =
+            serde_json::from_value(json!({ "action": "setAlwaysOnTop", "alwaysOnTop": true }))
+                .unwrap();
+        assert_eq!(
+            params.action,
+            WindowAction::SetAlwaysOnTop {
+                always_on_top: true
+            }
+        );
+
+        let params: ManageWindowParams =
+            serde_json::from_value(json!({ "action": "setFullscreen" })).unwrap();
+        assert_eq!(
+            params.action,
+            WindowAction::SetFullscreen { fullscreen: None }
+        );
+    }
+
+    #[test]
+    fn test_action_name_matches_tag() {
+        let actions = [
+            WindowAction::Minimize,
+            WindowAction::SetTitle {
+                title: "x".to_string(),
+            },
+            WindowAction::SetFullscreen {
+                fullscreen: Some(true),
+            },
+        ];
+        for action in actions {
+            let value = serde_json::to_value(&action).unwrap();
+            assert_eq!(value["action"], action.name());
+        }
+    }
+
+    #[test]
+    fn test_unknown_action_is_rejected() {
+        let result = serde_json::from_value::<ManageWindowParams>(json!({ "action": "explode" }));
+        assert!(result.is_err());
+    }
+}
@@ -6,16 +6,24 @@
 
 // Individual command modules
 pub mod backend_state;
+pub mod create_window;
 pub mod element_picker;
 pub mod emit_event;
+pub mod event_tap;
 pub mod execute_command;
 pub mod execute_js;
 pub mod ipc_monitor;
 pub mod list_windows;
+pub mod manage_window;
+pub mod monitors;
+pub mod navigation;
+pub mod replay;
 pub mod resize_window;
 pub mod screenshot;
 pub mod script_executor;
 pub mod script_injection;
+pub mod session;
+pub mod wait_for_event;
 pub mod window_info;
 
 // Re-export types and commands for convenience
@@ -23,16 +31,34 @@ pub use script_executor::ScriptExecutor;
 
 // Re-export command functions (needed for generate_handler! macro)
 pub use backend_state::get_backend_state;
-pub use emit_event::emit_event;
+pub use create_window::{
+    create_webview, create_window, CreateWebviewParams, CreateWindowParams, CreateWindowResult,
+};
+pub use emit_event::{emit_event, emit_event_with_ack, EmitAck, EmitEventParams};
+pub use event_tap::{
+    clear_tauri_events, get_tauri_events, start_event_tap, stop_event_tap, EventTapStatus,
+    GetTauriEventsParams, StartEventTapParams,
+};
 pub use execute_command::execute_command;
 pub use execute_js::execute_js;
-pub use ipc_monitor::{get_ipc_events, report_ipc_event, start_ipc_monitor, stop_ipc_monitor};
+pub use ipc_monitor::{
+    get_ipc_events, report_ipc_event, start_ipc_monitor, stop_ipc_monitor, IPCEventsResult,
+};
 pub use list_windows::{
     list_windows, resolve_window, resolve_window_with_context, ResolvedWindow, WindowContext,
-    WindowInfo,
+    WindowDetailLevel, WindowInfo,
 };
+pub use manage_window::{manage_window, ManageWindowParams, ManageWindowResult, WindowAction};
+pub use monitors::{list_monitors, MonitorsResult, Point};
+pub use navigation::{navigate, NavigationAction, NavigationParams, NavigationResult};
+pub use replay::{replay_ipc, ReplayIpcParams, ReplayReport};
 pub use resize_window::{resize_window, ResizeWindowParams, ResizeWindowResult};
 pub use screenshot::capture_native_screenshot;
 pub use script_executor::script_result;
 pub use script_injection::request_script_injection;
-pub use window_info::get_window_info;
+pub use session::{
+    export_session, import_session, ExportSessionParams, ExportSessionResult, ImportSessionParams,
+    ImportSessionResult, SessionDocument,
+};
+pub use wait_for_event::{wait_for_event, EventWaiter, WaitForEventParams, WaitForEventResult};
+pub use window_info::{get_window_info, window_details, MonitorInfo, Rect, WindowDetails};
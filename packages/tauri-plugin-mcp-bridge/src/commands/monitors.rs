@@ -0,0 +1,144 @@
+//! Monitor listing and cursor position.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+
+use super::window_info::MonitorInfo;
+
+/// A point in physical desktop coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Result of listing monitors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorsResult {
+    /// All available monitors, primary first
+    pub monitors: Vec<MonitorInfo>,
+    /// Cursor position relative to the top-left corner of the desktop, in
+    /// physical pixels (desktop only)
+    pub cursor: Option<Point>,
+}
+
+/// Lists the available monitors and the cursor position.
+///
+/// Monitors are queried through an open window when there is one, falling
+/// back to the application handle.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri application handle
+///
+/// # Returns
+///
+/// * `Ok(MonitorsResult)` - Monitors with name, physical and logical size,
+///   position, scale factor, work area and primary flag, plus the cursor position
+/// * `Err(String)` - Error message if the monitors cannot be queried
+///
+/// # Notes
+///
+/// - On mobile platforms (Android/iOS), the cursor position is always `None`
+pub async fn list_monitors<R: Runtime>(app: AppHandle<R>) -> Result<MonitorsResult, String> {
+    use tauri::Manager;
+
+    let window = app.webview_windows().into_values().next();
+
+    let (available, primary) = match &window {
+        Some(window) => (
+            window.available_monitors(),
+            window.primary_monitor().ok().flatten(),
+        ),
+        None => (
+            app.available_monitors(),
+            app.primary_monitor().ok().flatten(),
+        ),
+    };
+    let available = available.map_err(|e| format!("Failed to list monitors: {e}"))?;
+
+    let mut monitors: Vec<MonitorInfo> = available
+        .iter()
+        .map(|monitor| MonitorInfo::new(monitor, primary.as_ref()))
+        .collect();
+    monitors.sort_by_key(|monitor| !monitor.primary);
+
+    Ok(MonitorsResult {
+        monitors,
+        cursor: cursor_position(&app, window.as_ref()),
+    })
+}
+
+#[cfg(desktop)]
+fn cursor_position<R: Runtime>(
+    app: &AppHandle<R>,
+    window: Option<&tauri::WebviewWindow<R>>,
+) -> Option<Point> {
+    let position = match window {
+        Some(window) => window.cursor_position(),
+        None => app.cursor_position(),
+    };
+    position.ok().map(|p| Point { x: p.x, y: p.y })
+}
+
+#[cfg(mobile)]
+fn cursor_position<R: Runtime>(
+    _app: &AppHandle<R>,
+    _window: Option<&tauri::WebviewWindow<R>>,
+) -> Option<Point> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(name: &str, x: i32, scale_factor: f64) -> tauri::Monitor {
+        tauri_runtime::monitor::Monitor {
+            name: Some(name.to_string()),
+            size: tauri::PhysicalSize::new(3840, 2160),
+            position: tauri::PhysicalPosition::new(x, 0),
+            work_area: tauri::PhysicalRect {
+                position: tauri::PhysicalPosition::new(x, 25),
+                size: tauri::PhysicalSize::new(3840, 2110),
+            },
+            scale_factor,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_monitor_info() {
+        let primary = monitor("DP-1", 0, 2.0);
+        let secondary = monitor("DP-2", 3840, 1.0);
+
+        let info = MonitorInfo::new(&primary, Some(&primary));
+        assert_eq!((info.width, info.height), (3840, 2160));
+        assert_eq!((info.logical_width, info.logical_height), (1920.0, 1080.0));
+        assert_eq!((info.work_area.y, info.work_area.height), (25, 2110));
+        assert!(info.primary);
+
+        let info = MonitorInfo::new(&secondary, Some(&primary));
+        assert_eq!((info.x, info.logical_width), (3840, 3840.0));
+        assert!(!info.primary);
+        assert!(!MonitorInfo::new(&primary, None).primary);
+    }
+
+    #[tokio::test]
+    async fn test_list_monitors() {
+        let app = tauri::test::mock_app();
+        tauri::WebviewWindowBuilder::new(&app, "main", tauri::WebviewUrl::default())
+            .build()
+            .unwrap();
+
+        // The mock runtime reports no monitors, so this only checks the shape
+        let result = list_monitors(app.handle().clone()).await.unwrap();
+        assert!(result.monitors.is_empty());
+
+        let value = serde_json::to_value(&result).unwrap();
+        assert!(value["monitors"].is_array());
+        #[cfg(desktop)]
+        assert!(value["cursor"]["x"].is_number());
+    }
+}
@@ -0,0 +1,338 @@
+//! Webview navigation: navigate, reload, back/forward and waiting for page loads.
+//!
+//! Page loads are detected through bridge.js, which reports page-ready to the
+//! plugin once its IPC is available (see `page_load`). Pages without the bridge
+//! script or without IPC access never report ready, so waiting on them times out.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime, Url};
+
+use super::list_windows::resolve_window;
+use crate::page_load::PageLoadTracker;
+
+/// Default time to wait for a page to report ready.
+pub const DEFAULT_NAVIGATION_TIMEOUT_MS: u64 = 10_000;
+
+/// A navigation operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NavigationAction {
+    /// Loads `url` (an app route or absolute URL)
+    Navigate,
+    /// Reloads the current page
+    Reload,
+    /// Goes back in the webview's history
+    GoBack,
+    /// Goes forward in the webview's history
+    GoForward,
+    /// Waits for the next page load without navigating
+    WaitForNavigation,
+}
+
+impl NavigationAction {
+    /// Returns the action's name as used in results.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Navigate => "navigate",
+            Self::Reload => "reload",
+            Self::GoBack => "goBack",
+            Self::GoForward => "goForward",
+            Self::WaitForNavigation => "waitForNavigation",
+        }
+    }
+
+    /// Returns the WebSocket command for this action.
+    pub fn command(&self) -> &'static str {
+        match self {
+            Self::Navigate => "navigate",
+            Self::Reload => "reload",
+            Self::GoBack => "go_back",
+            Self::GoForward => "go_forward",
+            Self::WaitForNavigation => "wait_for_navigation",
+        }
+    }
+
+    /// Looks up the action for a WebSocket command.
+    pub fn from_command(command: &str) -> Option<Self> {
+        [
+            Self::Navigate,
+            Self::Reload,
+            Self::GoBack,
+            Self::GoForward,
+            Self::WaitForNavigation,
+        ]
+        .into_iter()
+        .find(|action| action.command() == command)
+    }
+}
+
+/// Parameters for a navigation operation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationParams {
+    /// Optional window label (defaults to "main")
+    pub window_id: Option<String>,
+    /// For `navigate`: the target URL or app route (e.g. "/settings").
+    /// For `waitForNavigation`: only resolve once the loaded URL contains this string
+    pub url: Option<String>,
+    /// Wait for the new page to report ready before returning (always true
+    /// for `waitForNavigation`)
+    #[serde(default)]
+    pub wait: bool,
+    /// How long to wait for the page, in milliseconds (default 10000)
+    pub timeout_ms: Option<u64>,
+}
+
+/// Result of a navigation operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationResult {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// The window that was operated on
+    pub window_label: String,
+    /// The operation that was performed
+    pub action: String,
+    /// The webview's URL when the operation completed
+    pub url: Option<String>,
+    /// Whether a page load was observed
+    pub loaded: bool,
+    /// Error message if the operation failed
+    pub error: Option<String>,
+}
+
+/// Resolves an app route or relative URL against the current page.
+pub fn resolve_navigation_url(current: Option<&Url>, target: &str) -> Result<Url, String> {
+    match Url::parse(target) {
+        Ok(url) => Ok(url),
+        Err(_) => current
+            .ok_or_else(|| format!("Cannot resolve relative URL '{target}'"))?
+            .join(target)
+            .map_err(|e| format!("Invalid URL '{target}': {e}")),
+    }
+}
+
+/// Performs a navigation operation and optionally waits for the page to load.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri application handle
+/// * `action` - The navigation operation
+/// * `params` - Window, URL and wait options
+///
+/// # Returns
+///
+/// * `Ok(NavigationResult)` - Result of the operation, including the final URL
+/// * `Err(String)` - Error message if the window cannot be found
+pub async fn navigate<R: Runtime>(
+    app: AppHandle<R>,
+    action: NavigationAction,
+    params: NavigationParams,
+) -> Result<NavigationResult, String> {
+    let window_label = params
+        .window_id
+        .clone()
+        .unwrap_or_else(|| "main".to_string());
+    let window = resolve_window(&app, params.window_id.clone())?;
+    let tracker = app
+        .try_state::<Arc<PageLoadTracker>>()
+        .map(|t| t.inner().clone())
+        .ok_or_else(|| "Page load tracking is not initialized".to_string())?;
+
+    let mut result = NavigationResult {
+        success: true,
+        window_label: window_label.clone(),
+        action: action.name().to_string(),
+        url: None,
+        loaded: false,
+        error: None,
+    };
+
+    // Subscribe before acting so a fast page load is not missed
+    let mut loads = tracker.subscribe(&window_label);
+
+    let outcome = match action {
+        NavigationAction::Navigate => match params.url.as_deref() {
+            Some(target) => {
+                resolve_navigation_url(window.url().ok().as_ref(), target).and_then(|url| {
+                    window
+                        .navigate(url)
+                        .map_err(|e| format!("Failed to navigate: {e}"))
+                })
+            }
+            None => Err("Missing url for navigate".to_string()),
+        },
+        NavigationAction::Reload => window
+            .reload()
+            .map_err(|e| format!("Failed to reload: {e}")),
+        NavigationAction::GoBack => window
+            .eval("window.history.back()")
+            .map_err(|e| format!("Failed to go back: {e}")),
+        NavigationAction::GoForward => window
+            .eval("window.history.forward()")
+            .map_err(|e| format!("Failed to go forward: {e}")),
+        NavigationAction::WaitForNavigation => {
+            let current = loads.borrow().clone();
+            let already_there = match (&params.url, &current.url) {
+                (Some(wanted), Some(url)) => current.count > 0 && url.contains(wanted.as_str()),
+                _ => false,
+            };
+            if already_there {
+                result.loaded = true;
+                result.url = current.url;
+                return Ok(result);
+            }
+            Ok(())
+        }
+    };
+
+    if let Err(e) = outcome {
+        result.success = false;
+        result.error = Some(e);
+        result.url = window.url().ok().map(|u| u.to_string());
+        return Ok(result);
+    }
+
+    if params.wait || action == NavigationAction::WaitForNavigation {
+        // Only `waitForNavigation` filters by URL; for `navigate` the url is the target
+        let wanted = match action {
+            NavigationAction::WaitForNavigation => params.url.as_deref(),
+            _ => None,
+        };
+        let timeout_ms = params.timeout_ms.unwrap_or(DEFAULT_NAVIGATION_TIMEOUT_MS);
+        let wait = async {
+            loop {
+                loads.changed().await.map_err(|_| "Webview was closed")?;
+                let load = loads.borrow_and_update().clone();
+                let matches = match (wanted, &load.url) {
+                    (Some(wanted), Some(url)) => url.contains(wanted),
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                };
+                if matches {
+                    return Ok::<_, &str>(load);
+                }
+            }
+        };
+
+        match tokio::time::timeout(Duration::from_millis(timeout_ms), wait).await {
+            Ok(Ok(load)) => {
+                result.loaded = true;
+                result.url = load.url;
+            }
+            Ok(Err(e)) => {
+                result.success = false;
+                result.error = Some(e.to_string());
+            }
+            Err(_) => {
+                result.success = false;
+                result.error = Some(format!(
+                    "Timed out after {timeout_ms}ms waiting for the page to load"
+                ));
+            }
+        }
+    }
+
+    if result.url.is_none() {
+        result.url = window.url().ok().map(|u| u.to_string());
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_with_window() -> tauri::App<tauri::test::MockRuntime> {
+        let app = tauri::test::mock_app();
+        app.manage(Arc::new(PageLoadTracker::new()));
+        tauri::WebviewWindowBuilder::new(&app, "main", tauri::WebviewUrl::default())
+            .build()
+            .unwrap();
+        app
+    }
+
+    #[test]
+    fn test_resolve_navigation_url() {
+        let current = Url::parse("tauri://localhost/index.html").unwrap();
+        assert_eq!(
+            resolve_navigation_url(Some(&current), "/settings")
+                .unwrap()
+                .as_str(),
+            "tauri://localhost/settings"
+        );
+        assert_eq!(
+            resolve_navigation_url(Some(&current), "https://example.com/")
+                .unwrap()
+                .as_str(),
+            "https://example.com/"
+        );
+        assert!(resolve_navigation_url(None, "/settings").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_navigation_resolves_on_page_load() {
+        let app = app_with_window();
+        let tracker = app.state::<Arc<PageLoadTracker>>().inner().clone();
+
+        let handle = app.handle().clone();
+        let waiter = tokio::spawn(async move {
+            navigate(
+                handle,
+                NavigationAction::WaitForNavigation,
+                NavigationParams {
+                    url: Some("/settings".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+        });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        tracker.page_loaded("main", Some("tauri://localhost/other".to_string()));
+        tracker.page_loaded("main", Some("tauri://localhost/settings".to_string()));
+
+        let result = waiter.await.unwrap().unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.loaded);
+        assert_eq!(result.url.as_deref(), Some("tauri://localhost/settings"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_navigation_times_out() {
+        let app = app_with_window();
+        let result = navigate(
+            app.handle().clone(),
+            NavigationAction::WaitForNavigation,
+            NavigationParams {
+                timeout_ms: Some(10),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(!result.success);
+        assert!(!result.loaded);
+        assert!(result.error.unwrap().contains("Timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_navigate_requires_url() {
+        let app = app_with_window();
+        let result = navigate(
+            app.handle().clone(),
+            NavigationAction::Navigate,
+            NavigationParams::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(!result.success);
+        assert_eq!(result.action, "navigate");
+    }
+}
@@ -0,0 +1,502 @@
+//! Replay of recorded IPC calls against the live backend.
+//!
+//! Recorded calls are re-issued in order from a webview, as the frontend
+//! would issue them, and each response is compared with the recorded one.
+//! Replayed calls bypass mock and fault rules and are not recorded by the
+//! IPC monitor.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, Runtime};
+
+use super::list_windows::resolve_window;
+use super::session::SessionDocument;
+use crate::event_tap::matches_pattern;
+use crate::ipc_capture::{error_message, invoke_command, FAULT_SOURCE, MOCK_SOURCE};
+use crate::monitor::{IPCEvent, IPCMonitorState};
+use crate::redaction::{contains_redacted, REDACTED};
+
+/// Default time to wait for each replayed call.
+pub const DEFAULT_REPLAY_TIMEOUT_MS: u64 = 10_000;
+
+/// How the time between recorded calls is reproduced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReplayTiming {
+    /// Wait as long between calls as the recording did
+    Original,
+    /// Wait at most `maxGapMs` between calls
+    #[default]
+    Compressed,
+}
+
+/// Parameters for replaying IPC calls.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayIpcParams {
+    /// Session document to replay (defaults to the IPC monitor's events)
+    pub session: Option<Value>,
+    /// Monitoring session whose events to replay, such as one created by
+    /// `import_session`
+    pub session_id: Option<String>,
+    /// Window to issue the calls from (defaults to "main")
+    pub window_label: Option<String>,
+    /// Only replay commands matching this glob pattern (e.g. "settings_*")
+    pub commands: Option<String>,
+    /// Recorded or compressed timing (default compressed)
+    #[serde(default)]
+    pub timing: ReplayTiming,
+    /// Longest wait between calls with compressed timing (default 0)
+    #[serde(default)]
+    pub max_gap_ms: u64,
+    /// How long to wait for each call, in milliseconds (default 10000)
+    pub timeout_ms: Option<u64>,
+    /// JSON pointers into results or errors to leave out of the comparison
+    /// (e.g. "/updatedAt")
+    #[serde(default)]
+    pub ignore: Vec<String>,
+}
+
+/// Outcome of one replayed call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReplayStatus {
+    /// The response matched the recording
+    Matched,
+    /// The response differed from the recording
+    Diverged,
+    /// The call was not replayed
+    Skipped,
+}
+
+/// A value that differs between the recording and the replay.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Divergence {
+    /// JSON pointer to the value, under `/result` or `/error`
+    pub path: String,
+    /// Recorded value, if it existed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Value>,
+    /// Replayed value, if it exists
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<Value>,
+}
+
+/// A replayed call and how its response compared.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayedCall {
+    /// Position of the call in the recording
+    pub index: usize,
+    /// Command name
+    pub command: String,
+    /// Arguments the call was replayed with
+    pub args: Value,
+    /// Whether the response matched
+    pub status: ReplayStatus,
+    /// Why the call was skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Differences from the recorded response
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub divergences: Vec<Divergence>,
+    /// Recorded duration, in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recorded_duration_ms: Option<f64>,
+    /// Replayed duration, in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<f64>,
+}
+
+/// Result of a replay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayReport {
+    /// Calls whose response matched the recording
+    pub matched: usize,
+    /// Calls whose response differed
+    pub diverged: usize,
+    /// Calls that were not replayed
+    pub skipped: usize,
+    /// Every call in the recording, in order
+    pub calls: Vec<ReplayedCall>,
+}
+
+/// Appends the differences between `expected` and `actual` under `path`,
+/// leaving out values at the `ignore` pointers (full paths, including `path`)
+/// and values that are redacted in `expected`.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json::json;
+/// use tauri_plugin_mcp_bridge::commands::replay::diff_values;
+///
+/// let mut divergences = Vec::new();
+/// diff_values(
+///     &json!({ "name": "a", "tags": [1, 2] }),
+///     &json!({ "name": "b", "tags": [1, 2] }),
+///     "/result",
+///     &[],
+///     &mut divergences,
+/// );
+/// assert_eq!(divergences[0].path, "/result/name");
+/// ```
+pub fn diff_values(
+    expected: &Value,
+    actual: &Value,
+    path: &str,
+    ignore: &[String],
+    out: &mut Vec<Divergence>,
+) {
+    if ignore.iter().any(|pointer| pointer == path) || expected.as_str() == Some(REDACTED) {
+        return;
+    }
+    let child = |key: &str| format!("{path}/{}", key.replace('~', "~0").replace('/', "~1"));
+    let push = |out: &mut Vec<Divergence>,
+                path: String,
+                expected: Option<&Value>,
+                actual: Option<&Value>| {
+        if !ignore.contains(&path) {
+            out.push(Divergence {
+                path,
+                expected: expected.cloned(),
+                actual: actual.cloned(),
+            });
+        }
+    };
+
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, value) in expected {
+                match actual.get(key) {
+                    Some(other) => diff_values(value, other, &child(key), ignore, out),
+                    None => push(out, child(key), Some(value), None),
+                }
+            }
+            for (key, value) in actual {
+                if !expected.contains_key(key) {
+                    push(out, child(key), None, Some(value));
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            for (index, value) in expected.iter().enumerate() {
+                let path = child(&index.to_string());
+                match actual.get(index) {
+                    Some(other) => diff_values(value, other, &path, ignore, out),
+                    None => push(out, path, Some(value), None),
+                }
+            }
+            for (index, value) in actual.iter().enumerate().skip(expected.len()) {
+                push(out, child(&index.to_string()), None, Some(value));
+            }
+        }
+        (expected, actual) if expected != actual => {
+            push(out, path.to_string(), Some(expected), Some(actual));
+        }
+        _ => {}
+    }
+}
+
+/// Returns why a recorded call is not replayed, if it is not.
+fn skip_reason(event: &IPCEvent, commands: Option<&str>) -> Option<String> {
+    if event.command.starts_with("plugin:mcp-bridge|") {
+        return Some("Bridge commands are not replayed".to_string());
+    }
+    if commands.is_some_and(|pattern| !matches_pattern(pattern, &event.command)) {
+        return Some("Command does not match the filter".to_string());
+    }
+    if contains_redacted(&event.args) {
+        return Some("Arguments were redacted".to_string());
+    }
+    match event.source.as_deref() {
+        Some(MOCK_SOURCE) => Some("Answered by a mock rule when recorded".to_string()),
+        Some(FAULT_SOURCE) => Some("Failed by a fault rule when recorded".to_string()),
+        _ => None,
+    }
+}
+
+/// Loads the calls to replay from a session document, a monitoring session or
+/// the IPC monitor.
+fn recorded_calls<R: Runtime>(
+    app: &AppHandle<R>,
+    params: &ReplayIpcParams,
+) -> Result<Vec<IPCEvent>, String> {
+    if let Some(session) = &params.session {
+        if params.session_id.is_some() {
+            return Err("Specify either session or sessionId, not both".to_string());
+        }
+        return Ok(SessionDocument::from_json(&session.to_string())?.ipc_events());
+    }
+
+    let monitor = app
+        .try_state::<IPCMonitorState>()
+        .ok_or("IPC monitor is not initialized")?;
+    let monitor = monitor.lock().map_err(|e| format!("Lock error: {e}"))?;
+    match &params.session_id {
+        Some(id) => monitor.session_events(id),
+        None => Ok(monitor.get_events()),
+    }
+}
+
+/// Replays recorded IPC calls in order and compares each response with the
+/// recorded one.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri application handle
+/// * `params` - Where to read the calls from, which to replay and how fast
+///
+/// # Returns
+///
+/// * `Ok(ReplayReport)` - Per-call outcomes and totals
+/// * `Err(String)` - Error message if the recording or window cannot be found
+pub async fn replay_ipc<R: Runtime>(
+    app: AppHandle<R>,
+    params: ReplayIpcParams,
+) -> Result<ReplayReport, String> {
+    let mut events = recorded_calls(&app, &params)?;
+    events.sort_by_key(|event| event.timestamp);
+    let window = resolve_window(&app, params.window_label.clone())?;
+    let timeout = Duration::from_millis(params.timeout_ms.unwrap_or(DEFAULT_REPLAY_TIMEOUT_MS));
+
+    let ignore: Vec<String> = params
+        .ignore
+        .iter()
+        .flat_map(|pointer| [format!("/result{pointer}"), format!("/error{pointer}")])
+        .collect();
+
+    let mut calls = Vec::with_capacity(events.len());
+    let mut previous: Option<u64> = None;
+    for (index, event) in events.into_iter().enumerate() {
+        let mut call = ReplayedCall {
+            index,
+            command: event.command.clone(),
+            args: event.args.clone(),
+            status: ReplayStatus::Skipped,
+            reason: skip_reason(&event, params.commands.as_deref()),
+            divergences: Vec::new(),
+            recorded_duration_ms: event.duration_ms,
+            duration_ms: None,
+        };
+        if call.reason.is_some() {
+            calls.push(call);
+            continue;
+        }
+
+        if let Some(previous) = previous {
+            let gap = event.timestamp.saturating_sub(previous);
+            let gap = match params.timing {
+                ReplayTiming::Original => gap,
+                ReplayTiming::Compressed => gap.min(params.max_gap_ms),
+            };
+            tokio::time::sleep(Duration::from_millis(gap)).await;
+        }
+        previous = Some(event.timestamp);
+
+        let started = Instant::now();
+        let response = tokio::time::timeout(
+            timeout,
+            invoke_command(window.as_ref(), &event.command, event.args),
+        )
+        .await
+        .unwrap_or_else(|_| {
+            Err(Value::from(format!(
+                "Timed out after {}ms",
+                timeout.as_millis()
+            )))
+        });
+        call.duration_ms = Some(started.elapsed().as_secs_f64() * 1000.0);
+
+        let expected = match (event.result, event.error) {
+            (_, Some(error)) => serde_json::json!({ "error": error }),
+            (result, None) => serde_json::json!({ "result": result.unwrap_or(Value::Null) }),
+        };
+        let actual = match response {
+            Ok(result) => serde_json::json!({ "result": result }),
+            Err(error) => serde_json::json!({ "error": error_message(&error) }),
+        };
+        diff_values(&expected, &actual, "", &ignore, &mut call.divergences);
+        call.status = if call.divergences.is_empty() {
+            ReplayStatus::Matched
+        } else {
+            ReplayStatus::Diverged
+        };
+        calls.push(call);
+    }
+
+    let count = |status| calls.iter().filter(|c| c.status == status).count();
+    Ok(ReplayReport {
+        matched: count(ReplayStatus::Matched),
+        diverged: count(ReplayStatus::Diverged),
+        skipped: count(ReplayStatus::Skipped),
+        calls,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc_capture::capture;
+    use crate::monitor::IPCMonitor;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use tauri::test::{mock_builder, mock_context, noop_assets};
+
+    #[tauri::command]
+    fn greet(name: String) -> String {
+        format!("Hello, {name}!")
+    }
+
+    fn recorded(
+        command: &str,
+        args: Value,
+        result: Option<Value>,
+        error: Option<&str>,
+    ) -> IPCEvent {
+        IPCEvent {
+            timestamp: 1_000,
+            command: command.into(),
+            args,
+            result,
+            error: error.map(str::to_string),
+            duration_ms: Some(1.0),
+            source: Some("backend".into()),
+            fault: None,
+            window: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_values() {
+        let mut divergences = Vec::new();
+        diff_values(
+            &json!({ "result": { "items": [1, 2], "at": 1, "same": true } }),
+            &json!({ "result": { "items": [1], "at": 2, "same": true, "new": 0 } }),
+            "",
+            &["/result/at".to_string()],
+            &mut divergences,
+        );
+        let paths: Vec<&str> = divergences.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, vec!["/result/items/1", "/result/new"]);
+        assert_eq!(divergences[0].actual, None);
+
+        divergences.clear();
+        diff_values(
+            &json!({ "result": { "token": REDACTED, "user": "a" } }),
+            &json!({ "result": { "token": "t0k3n", "user": "a" } }),
+            "",
+            &[],
+            &mut divergences,
+        );
+        assert!(divergences.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_replay_reports_divergences() {
+        let app = mock_builder()
+            .invoke_handler(capture(tauri::generate_handler![greet]))
+            .build(mock_context(noop_assets()))
+            .unwrap();
+        let monitor: IPCMonitorState = Arc::new(Mutex::new(IPCMonitor::new()));
+        app.manage(monitor.clone());
+        tauri::WebviewWindowBuilder::new(&app, "main", tauri::WebviewUrl::default())
+            .build()
+            .unwrap();
+
+        monitor.lock().unwrap().start();
+        for event in [
+            recorded(
+                "greet",
+                json!({ "name": "a" }),
+                Some(json!("Hello, a!")),
+                None,
+            ),
+            recorded("greet", json!({ "name": "b" }), Some(json!("Hi, b!")), None),
+            recorded("missing", json!({}), Some(json!(1)), None),
+            recorded("plugin:mcp-bridge|get_ipc_events", json!({}), None, None),
+        ] {
+            monitor.lock().unwrap().add_event(event);
+        }
+
+        let report = replay_ipc(app.handle().clone(), ReplayIpcParams::default())
+            .await
+            .unwrap();
+        assert_eq!((report.matched, report.diverged, report.skipped), (1, 2, 1));
+        assert_eq!(report.calls[1].divergences[0].path, "/result");
+        assert_eq!(
+            report.calls[1].divergences[0].actual,
+            Some(json!("Hello, b!"))
+        );
+        // The unknown command now fails instead of returning a result
+        let paths: Vec<&str> = report.calls[2]
+            .divergences
+            .iter()
+            .map(|d| d.path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["/result", "/error"]);
+        assert_eq!(report.calls[3].status, ReplayStatus::Skipped);
+
+        // Replayed calls are not recorded
+        assert_eq!(monitor.lock().unwrap().get_events().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_replay_skips_redacted_values() {
+        let app = mock_builder()
+            .invoke_handler(capture(tauri::generate_handler![greet]))
+            .build(mock_context(noop_assets()))
+            .unwrap();
+        let monitor: IPCMonitorState = Arc::new(Mutex::new(IPCMonitor::new()));
+        app.manage(monitor.clone());
+        tauri::WebviewWindowBuilder::new(&app, "main", tauri::WebviewUrl::default())
+            .build()
+            .unwrap();
+
+        {
+            let mut monitor = monitor.lock().unwrap();
+            monitor
+                .add_redaction_rule(crate::redaction::RedactionRule {
+                    id: "tokens".into(),
+                    keys: vec!["token".into()],
+                    ..Default::default()
+                })
+                .unwrap();
+            monitor
+                .add_redaction_rule(crate::redaction::RedactionRule {
+                    id: "results".into(),
+                    paths: vec!["$.result".into()],
+                    ..Default::default()
+                })
+                .unwrap();
+            monitor.start();
+            monitor.add_event(recorded(
+                "greet",
+                json!({ "name": "a", "token": "t0k3n" }),
+                Some(json!("Hello, a!")),
+                None,
+            ));
+            monitor.add_event(recorded(
+                "greet",
+                json!({ "name": "b" }),
+                Some(json!("Hi, b!")),
+                None,
+            ));
+        }
+
+        let report = replay_ipc(app.handle().clone(), ReplayIpcParams::default())
+            .await
+            .unwrap();
+        assert_eq!((report.matched, report.diverged, report.skipped), (1, 0, 1));
+        assert_eq!(
+            report.calls[0].reason.as_deref(),
+            Some("Arguments were redacted")
+        );
+        // The redacted result is not compared with the live one
+        assert_eq!(report.calls[1].status, ReplayStatus::Matched);
+    }
+}
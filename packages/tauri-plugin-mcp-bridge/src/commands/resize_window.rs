@@ -10,7 +10,7 @@ use tauri::{LogicalSize, PhysicalSize};
 use super::list_windows::resolve_window;
 
 /// Parameters for resizing a window.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ResizeWindowParams {
     /// Width in pixels
@@ -29,7 +29,7 @@ fn default_logical() -> bool {
 }
 
 /// Result of a window resize operation.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ResizeWindowResult {
     /// Whether the resize was successful
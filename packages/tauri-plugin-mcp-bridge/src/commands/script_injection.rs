@@ -1,15 +1,42 @@
 //! Script injection command for re-injecting registered scripts on page load.
 
+use crate::commands::ipc_monitor::START_IPC_HOOK;
+use crate::monitor::IPCMonitorState;
+use crate::page_load::PageLoadTracker;
+use crate::redaction::console_patterns_script;
 use crate::script_registry::{ScriptEntry, ScriptType, SharedScriptRegistry};
-use tauri::{command, Runtime, State, WebviewWindow};
+use std::sync::Arc;
+use tauri::{command, Manager, Runtime, State, WebviewWindow};
 
 /// Request script injection - called by bridge.js when a page loads.
 /// This command retrieves all registered scripts and injects them into the webview.
+/// It also records the page load so navigation commands can wait for it,
+/// refreshes the console redaction patterns, which may have changed at runtime,
+/// and installs the IPC hook if a monitor captures this window.
 #[command]
 pub async fn request_script_injection<R: Runtime>(
     window: WebviewWindow<R>,
     registry: State<'_, SharedScriptRegistry>,
 ) -> Result<serde_json::Value, String> {
+    if let Some(tracker) = window.try_state::<Arc<PageLoadTracker>>() {
+        tracker.page_loaded(window.label(), window.url().ok().map(|u| u.to_string()));
+    }
+
+    if let Some(monitor) = window.try_state::<IPCMonitorState>() {
+        let (keys, capturing) = monitor
+            .lock()
+            .map(|m| (m.console_redaction_keys(), m.is_capturing(window.label())))
+            .unwrap_or_default();
+        window
+            .eval(console_patterns_script(&keys))
+            .map_err(|e| format!("Failed to set redaction patterns: {e}"))?;
+        if capturing {
+            window
+                .eval(START_IPC_HOOK)
+                .map_err(|e| format!("Failed to start IPC monitoring: {e}"))?;
+        }
+    }
+
     let scripts: Vec<ScriptEntry> = {
         let reg = registry
             .lock()
@@ -24,7 +51,21 @@ pub async fn request_script_injection<R: Runtime>(
         }));
     }
 
-    // Build the injection script
+    let inject_script = injection_script(&scripts);
+
+    window
+        .eval(&inject_script)
+        .map_err(|e| format!("Failed to inject scripts: {e}"))?;
+
+    Ok(serde_json::json!({
+        "injected": scripts.len(),
+        "scriptIds": scripts.iter().map(|s| s.id.clone()).collect::<Vec<_>>()
+    }))
+}
+
+/// Builds the eval script that hands registered scripts to `__MCP_INJECT_SCRIPTS__`
+/// in bridge.js.
+pub(crate) fn injection_script(scripts: &[ScriptEntry]) -> String {
     let scripts_json: Vec<serde_json::Value> = scripts
         .iter()
         .map(|entry| {
@@ -39,17 +80,22 @@ pub async fn request_script_injection<R: Runtime>(
         })
         .collect();
 
-    let inject_script = format!(
+    format!(
         "if (window.__MCP_INJECT_SCRIPTS__) {{ window.__MCP_INJECT_SCRIPTS__({}); }}",
         serde_json::to_string(&scripts_json).unwrap_or_else(|_| "[]".to_string())
-    );
+    )
+}
 
-    window
-        .eval(&inject_script)
-        .map_err(|e| format!("Failed to inject scripts: {e}"))?;
+/// Returns the injection script for all registered scripts, or `None` when
+/// nothing is registered.
+pub(crate) fn registered_scripts_injection<R: Runtime, M: Manager<R>>(
+    manager: &M,
+) -> Option<String> {
+    let registry = manager.try_state::<SharedScriptRegistry>()?;
+    let scripts: Vec<ScriptEntry> = {
+        let reg = registry.lock().ok()?;
+        reg.get_all().iter().map(|e| (*e).clone()).collect()
+    };
 
-    Ok(serde_json::json!({
-        "injected": scripts.len(),
-        "scriptIds": scripts.iter().map(|s| s.id.clone()).collect::<Vec<_>>()
-    }))
+    (!scripts.is_empty()).then(|| injection_script(&scripts))
 }
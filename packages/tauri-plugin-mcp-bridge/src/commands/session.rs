@@ -0,0 +1,546 @@
+//! Session export and import.
+//!
+//! A session document bundles captured IPC calls, Tauri events and console
+//! logs into one HAR-like JSON file that can be attached to a bug report:
+//!
+//! ```json
+//! {
+//!   "log": {
+//!     "version": "1.0",
+//!     "creator": { "name": "tauri-plugin-mcp-bridge", "version": "0.11.2" },
+//!     "startedDateTime": "2026-01-05T09:30:00.120Z",
+//!     "exportedDateTime": "2026-01-05T09:31:12.004Z",
+//!     "entries": [
+//!       { "startedDateTime": "2026-01-05T09:30:00.120Z", "type": "ipc", "timestamp": 1767605400120, "command": "greet", ... },
+//!       { "startedDateTime": "2026-01-05T09:30:00.250Z", "type": "event", "timestamp": 1767605400250, "event": "greeted", ... },
+//!       { "startedDateTime": "2026-01-05T09:30:00.251Z", "type": "console", "timestamp": 1767605400251, "window": "main", "level": "log", "message": "hi" }
+//!     ],
+//!     "warnings": []
+//!   }
+//! }
+//! ```
+//!
+//! Entries are sorted by time. Besides `startedDateTime` and `type`, each
+//! entry has the fields of an [`IPCEvent`], a [`TauriEventRecord`] or a
+//! [`ConsoleLogEntry`].
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, Runtime};
+
+use super::{execute_js, ScriptExecutor};
+use crate::event_tap::{EventTapState, TauriEventRecord};
+use crate::monitor::{current_timestamp, IPCEvent, IPCMonitorState};
+
+/// Version of the session document format.
+pub const SESSION_FORMAT_VERSION: &str = "1.0";
+
+/// Reads the console logs bridge.js captured in a webview.
+const CONSOLE_LOGS_SCRIPT: &str = "return window.__MCP_CONSOLE_LOGS__ || [];";
+
+/// A console message captured by bridge.js.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsoleLogEntry {
+    /// Label of the window the message was logged in
+    #[serde(default)]
+    pub window: String,
+    /// Console method: "log", "debug", "info", "warn" or "error"
+    pub level: String,
+    /// Logged values joined into one string
+    pub message: String,
+    /// Unix timestamp in milliseconds when the message was logged
+    pub timestamp: u64,
+}
+
+/// The captured record in a session entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SessionRecord {
+    /// An IPC call
+    Ipc(IPCEvent),
+    /// A Tauri event
+    Event(TauriEventRecord),
+    /// A console message
+    Console(ConsoleLogEntry),
+}
+
+impl SessionRecord {
+    /// Unix timestamp in milliseconds of the record.
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            Self::Ipc(event) => event.timestamp,
+            Self::Event(event) => event.timestamp,
+            Self::Console(entry) => entry.timestamp,
+        }
+    }
+}
+
+/// One entry of a session document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionEntry {
+    /// Time of the record as an RFC 3339 UTC date
+    pub started_date_time: String,
+    /// The captured record
+    #[serde(flatten)]
+    pub record: SessionRecord,
+}
+
+/// The tool that wrote a session document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionCreator {
+    /// Tool name
+    pub name: String,
+    /// Tool version
+    pub version: String,
+}
+
+/// Contents of a session document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLog {
+    /// Format version, currently "1.0"
+    pub version: String,
+    /// The tool that wrote the document
+    pub creator: SessionCreator,
+    /// Time of the first entry (or of the export if there are none)
+    pub started_date_time: String,
+    /// Time of the export
+    pub exported_date_time: String,
+    /// Captured records, sorted by time
+    pub entries: Vec<SessionEntry>,
+    /// Problems met while collecting, e.g. a window whose logs could not be read
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// A HAR-like session document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionDocument {
+    /// Document contents, under `log` as in HAR
+    pub log: SessionLog,
+}
+
+impl SessionDocument {
+    /// Builds a document from records, sorting them by time.
+    pub fn new(mut records: Vec<SessionRecord>, warnings: Vec<String>) -> Self {
+        records.sort_by_key(SessionRecord::timestamp);
+        let exported = format_timestamp(current_timestamp());
+        let started = records
+            .first()
+            .map_or_else(|| exported.clone(), |r| format_timestamp(r.timestamp()));
+
+        Self {
+            log: SessionLog {
+                version: SESSION_FORMAT_VERSION.to_string(),
+                creator: SessionCreator {
+                    name: env!("CARGO_PKG_NAME").to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                },
+                started_date_time: started,
+                exported_date_time: exported,
+                entries: records
+                    .into_iter()
+                    .map(|record| SessionEntry {
+                        started_date_time: format_timestamp(record.timestamp()),
+                        record,
+                    })
+                    .collect(),
+                warnings,
+            },
+        }
+    }
+
+    /// Parses a document, checking its format version.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let document: Self =
+            serde_json::from_str(json).map_err(|e| format!("Invalid session document: {e}"))?;
+        let major = |version: &str| version.split('.').next().map(str::to_string);
+        if major(&document.log.version) != major(SESSION_FORMAT_VERSION) {
+            return Err(format!(
+                "Unsupported session document version {} (expected {SESSION_FORMAT_VERSION})",
+                document.log.version
+            ));
+        }
+        Ok(document)
+    }
+
+    /// Returns the IPC calls in the document.
+    pub fn ipc_events(&self) -> Vec<IPCEvent> {
+        self.records(|record| match record {
+            SessionRecord::Ipc(event) => Some(event.clone()),
+            _ => None,
+        })
+    }
+
+    /// Returns the Tauri events in the document.
+    pub fn tauri_events(&self) -> Vec<TauriEventRecord> {
+        self.records(|record| match record {
+            SessionRecord::Event(event) => Some(event.clone()),
+            _ => None,
+        })
+    }
+
+    /// Returns the console messages in the document.
+    pub fn console_logs(&self) -> Vec<ConsoleLogEntry> {
+        self.records(|record| match record {
+            SessionRecord::Console(entry) => Some(entry.clone()),
+            _ => None,
+        })
+    }
+
+    fn records<T>(&self, select: impl Fn(&SessionRecord) -> Option<T>) -> Vec<T> {
+        self.log
+            .entries
+            .iter()
+            .filter_map(|entry| select(&entry.record))
+            .collect()
+    }
+}
+
+/// Formats a Unix timestamp in milliseconds as an RFC 3339 UTC date.
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri_plugin_mcp_bridge::commands::session::format_timestamp;
+///
+/// assert_eq!(format_timestamp(0), "1970-01-01T00:00:00.000Z");
+/// assert_eq!(format_timestamp(1_767_605_400_120), "2026-01-05T09:30:00.120Z");
+/// ```
+pub fn format_timestamp(ms: u64) -> String {
+    let secs = ms / 1000;
+    let (days, rem) = ((secs / 86_400) as i64, secs % 86_400);
+
+    // Civil date from days since the epoch (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        ms % 1000
+    )
+}
+
+/// Parameters for exporting a session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSessionParams {
+    /// Only read console logs from this window (defaults to every window)
+    pub window_label: Option<String>,
+    /// Include console logs (default true)
+    #[serde(default = "default_true")]
+    pub console: bool,
+}
+
+impl Default for ExportSessionParams {
+    fn default() -> Self {
+        Self {
+            window_label: None,
+            console: true,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Result of a session export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSessionResult {
+    /// Number of entries in the document
+    pub entries: usize,
+    /// The document
+    pub document: SessionDocument,
+}
+
+/// Parameters for importing a session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSessionParams {
+    /// The session document
+    pub document: Value,
+}
+
+/// Result of a session import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSessionResult {
+    /// When the document was exported
+    pub exported_date_time: String,
+    /// Monitoring session holding the document's IPC calls
+    pub session_id: String,
+    /// Number of IPC calls loaded into the monitoring session
+    pub ipc_events: usize,
+    /// Tauri events from the document
+    pub tauri_events: Vec<TauriEventRecord>,
+    /// Console messages from the document
+    pub console_logs: Vec<ConsoleLogEntry>,
+    /// Warnings recorded at export time
+    pub warnings: Vec<String>,
+}
+
+/// Exports captured IPC calls, Tauri events and console logs as a session
+/// document.
+///
+/// IPC calls come from the [`IPCMonitor`](crate::monitor::IPCMonitor) and
+/// events from the event tap; console logs are read from the webviews, and a
+/// window whose logs cannot be read adds a warning instead of failing the
+/// export.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri application handle
+/// * `params` - Window to read console logs from
+///
+/// # Returns
+///
+/// * `Ok(ExportSessionResult)` - The document
+/// * `Err(String)` - Error message if the window is unknown
+pub async fn export_session<R: Runtime>(
+    app: AppHandle<R>,
+    params: ExportSessionParams,
+) -> Result<ExportSessionResult, String> {
+    let mut records = Vec::new();
+    let mut warnings = Vec::new();
+
+    if let Some(monitor) = app.try_state::<IPCMonitorState>() {
+        let monitor = monitor.lock().map_err(|e| format!("Lock error: {e}"))?;
+        records.extend(monitor.get_events().into_iter().map(SessionRecord::Ipc));
+    }
+    if let Some(tap) = app.try_state::<EventTapState>() {
+        let tap = tap.lock().map_err(|e| format!("Lock error: {e}"))?;
+        records.extend(
+            tap.get_events(None, None)
+                .into_iter()
+                .map(SessionRecord::Event),
+        );
+    }
+    if params.console {
+        for (label, logs) in read_console_logs(&app, params.window_label.as_deref()).await? {
+            match logs {
+                Ok(logs) => records.extend(logs.into_iter().map(|mut entry| {
+                    entry.window = label.clone();
+                    SessionRecord::Console(entry)
+                })),
+                Err(e) => warnings.push(format!("Console logs of window '{label}': {e}")),
+            }
+        }
+    }
+
+    let document = SessionDocument::new(records, warnings);
+    Ok(ExportSessionResult {
+        entries: document.log.entries.len(),
+        document,
+    })
+}
+
+/// Reads the console logs of one window, or of every window, by label.
+async fn read_console_logs<R: Runtime>(
+    app: &AppHandle<R>,
+    window_label: Option<&str>,
+) -> Result<Vec<(String, Result<Vec<ConsoleLogEntry>, String>)>, String> {
+    let windows = match window_label {
+        Some(label) => vec![app
+            .get_webview_window(label)
+            .ok_or_else(|| format!("Window '{label}' not found"))?],
+        None => app.webview_windows().into_values().collect(),
+    };
+    let Some(executor) = app.try_state::<ScriptExecutor>() else {
+        return Ok(Vec::new());
+    };
+
+    let mut logs = Vec::new();
+    for window in windows {
+        let label = window.label().to_string();
+        let result = execute_js(window, CONSOLE_LOGS_SCRIPT.to_string(), executor.clone())
+            .await
+            .and_then(|result| {
+                if result.get("success").and_then(Value::as_bool) == Some(false) {
+                    let error = result.get("error").and_then(Value::as_str);
+                    return Err(error.unwrap_or("Script failed").to_string());
+                }
+                let data = result.get("data").cloned().unwrap_or(Value::Null);
+                serde_json::from_value(data).map_err(|e| format!("Unexpected console logs: {e}"))
+            });
+        logs.push((label, result));
+    }
+    Ok(logs)
+}
+
+/// Imports a session document for inspection.
+///
+/// The document's IPC calls are loaded into a new, stopped monitoring
+/// session, so `get_monitor_session_events` (or `replay_ipc` with its
+/// `sessionId`) reads the recording without touching the default monitor or
+/// other clients' sessions. The calls are redacted by the current rules. Tauri
+/// events and console messages are returned as they are.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri application handle
+/// * `params` - The document
+///
+/// # Returns
+///
+/// * `Ok(ImportSessionResult)` - The new session's ID, plus the events and
+///   console messages
+/// * `Err(String)` - Error message if the document is invalid
+pub async fn import_session<R: Runtime>(
+    app: AppHandle<R>,
+    params: ImportSessionParams,
+) -> Result<ImportSessionResult, String> {
+    let document = SessionDocument::from_json(&params.document.to_string())?;
+    let ipc_events = document.ipc_events();
+    let count = ipc_events.len();
+
+    let monitor = app
+        .try_state::<IPCMonitorState>()
+        .ok_or("IPC monitor is not initialized")?;
+    let session = monitor
+        .lock()
+        .map_err(|e| format!("Lock error: {e}"))?
+        .load_session(ipc_events);
+
+    Ok(ImportSessionResult {
+        exported_date_time: document.log.exported_date_time.clone(),
+        session_id: session.session_id,
+        ipc_events: count,
+        tauri_events: document.tauri_events(),
+        console_logs: document.console_logs(),
+        warnings: document.log.warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_tap::EventTap;
+    use crate::monitor::IPCMonitor;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_entries_are_flat_and_sorted() {
+        let document = SessionDocument::new(
+            vec![
+                SessionRecord::Console(ConsoleLogEntry {
+                    window: "main".into(),
+                    level: "warn".into(),
+                    message: "slow".into(),
+                    timestamp: 2_000,
+                }),
+                SessionRecord::Ipc(
+                    serde_json::from_value(json!({
+                        "timestamp": 1_000,
+                        "command": "greet",
+                        "args": { "name": "a" },
+                        "result": "Hello, a!",
+                        "error": null,
+                        "duration_ms": 1.5
+                    }))
+                    .unwrap(),
+                ),
+            ],
+            Vec::new(),
+        );
+
+        let value = serde_json::to_value(&document).unwrap();
+        let entries = &value["log"]["entries"];
+        assert_eq!(entries[0]["type"], "ipc");
+        assert_eq!(entries[0]["command"], "greet");
+        assert_eq!(entries[0]["startedDateTime"], "1970-01-01T00:00:01.000Z");
+        assert_eq!(entries[1]["type"], "console");
+        assert_eq!(
+            value["log"]["startedDateTime"],
+            entries[0]["startedDateTime"]
+        );
+
+        let parsed = SessionDocument::from_json(&value.to_string()).unwrap();
+        assert_eq!(parsed.ipc_events()[0].command, "greet");
+        assert_eq!(parsed.console_logs()[0].message, "slow");
+
+        let mut future = value;
+        future["log"]["version"] = json!("2.0");
+        assert!(SessionDocument::from_json(&future.to_string())
+            .unwrap_err()
+            .contains("Unsupported"));
+    }
+
+    #[tokio::test]
+    async fn test_export_then_import_round_trip() {
+        let app = tauri::test::mock_app();
+        let monitor: IPCMonitorState = Arc::new(Mutex::new(IPCMonitor::new()));
+        let tap: EventTapState = Arc::new(Mutex::new(EventTap::new()));
+        app.manage(monitor.clone());
+        app.manage(tap.clone());
+
+        monitor.lock().unwrap().start();
+        monitor.lock().unwrap().add_event(
+            serde_json::from_value(json!({
+                "timestamp": current_timestamp(),
+                "command": "save",
+                "args": { "token": "abc" },
+                "result": null,
+                "error": "disk full",
+                "duration_ms": null
+            }))
+            .unwrap(),
+        );
+        tap.lock().unwrap().enabled = true;
+        tap.lock()
+            .unwrap()
+            .record("saved", json!({ "ok": false }), None, None);
+
+        let exported = export_session(app.handle().clone(), ExportSessionParams::default())
+            .await
+            .unwrap();
+        assert_eq!(exported.entries, 2);
+
+        // Another client's capture and the event tap are left alone
+        monitor.lock().unwrap().start();
+        monitor
+            .lock()
+            .unwrap()
+            .add_event(IPCEvent::new(current_timestamp(), "live", json!({})));
+        monitor
+            .lock()
+            .unwrap()
+            .add_redaction_rule(crate::redaction::RedactionRule {
+                id: "tokens".into(),
+                keys: vec!["token".into()],
+                ..Default::default()
+            })
+            .unwrap();
+        let imported = import_session(
+            app.handle().clone(),
+            ImportSessionParams {
+                document: serde_json::to_value(&exported.document).unwrap(),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(imported.ipc_events, 1);
+        assert_eq!(imported.tauri_events[0].event, "saved");
+        let monitor = monitor.lock().unwrap();
+        assert_eq!(monitor.get_events()[0].command, "live");
+        let events = monitor.session_events(&imported.session_id).unwrap();
+        assert_eq!(events[0].error.as_deref(), Some("disk full"));
+        assert_eq!(events[0].args["token"], crate::redaction::REDACTED);
+        assert_eq!(tap.lock().unwrap().get_events(None, None).len(), 1);
+    }
+}
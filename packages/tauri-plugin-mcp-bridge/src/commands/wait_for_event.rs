@@ -0,0 +1,270 @@
+//! Waiting for a Tauri event with an optional payload matcher.
+//!
+//! The listener is registered as soon as the request arrives, so an event
+//! fired by a later command on the same connection is not missed.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, EventId, Listener, Runtime};
+use tokio::sync::oneshot;
+
+use crate::event_tap::{is_valid_event_name, parse_payload};
+
+/// Default time to wait for an event.
+pub const DEFAULT_WAIT_TIMEOUT_MS: u64 = 10_000;
+
+/// Parameters for waiting on an event.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WaitForEventParams {
+    /// Name of the event to wait for
+    pub event: String,
+    /// JSON pointer into the payload (e.g. "/status"); the value there is
+    /// matched instead of the whole payload, and must exist
+    pub pointer: Option<String>,
+    /// Expected value; objects match if every key they list matches
+    pub matches: Option<Value>,
+    /// How long to wait, in milliseconds (default 10000)
+    pub timeout_ms: Option<u64>,
+}
+
+/// The event that satisfied a wait.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WaitForEventResult {
+    /// Event name
+    pub event: String,
+    /// Full payload of the matching event
+    pub payload: Value,
+    /// Time between registering the listener and the match, in milliseconds
+    pub elapsed_ms: u64,
+}
+
+/// Returns whether `expected` is contained in `actual`: objects match if each
+/// of their keys matches, arrays element by element, anything else by equality.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json::json;
+/// use tauri_plugin_mcp_bridge::commands::wait_for_event::is_subset;
+///
+/// assert!(is_subset(&json!({ "state": "done" }), &json!({ "state": "done", "id": 7 })));
+/// assert!(!is_subset(&json!({ "state": "done" }), &json!({ "state": "running" })));
+/// ```
+pub fn is_subset(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => expected
+            .iter()
+            .all(|(key, value)| actual.get(key).is_some_and(|a| is_subset(value, a))),
+        (Value::Array(expected), Value::Array(actual)) => {
+            expected.len() == actual.len()
+                && expected.iter().zip(actual).all(|(e, a)| is_subset(e, a))
+        }
+        (expected, actual) => expected == actual,
+    }
+}
+
+/// Returns whether a payload satisfies the pointer and matcher of a wait.
+pub fn payload_matches(payload: &Value, pointer: Option<&str>, expected: Option<&Value>) -> bool {
+    let value = match pointer {
+        Some(pointer) => match payload.pointer(pointer) {
+            Some(value) => value,
+            None => return false,
+        },
+        None => payload,
+    };
+    expected.map_or(true, |expected| is_subset(expected, value))
+}
+
+/// A registered listener waiting for the first matching event.
+///
+/// The listener is removed when the waiter completes or is dropped.
+pub struct EventWaiter<R: Runtime> {
+    app: AppHandle<R>,
+    event: String,
+    listener: EventId,
+    rx: oneshot::Receiver<Value>,
+    timeout_ms: u64,
+    started: Instant,
+}
+
+impl<R: Runtime> EventWaiter<R> {
+    /// Registers a listener for the event described by `params`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(EventWaiter)` - The waiter, already listening
+    /// * `Err(String)` - Error message if the event name or pointer is invalid
+    pub fn register(app: &AppHandle<R>, params: WaitForEventParams) -> Result<Self, String> {
+        if !is_valid_event_name(&params.event) {
+            return Err(format!(
+                "Invalid event name '{}': only alphanumeric, '-', '/', ':' and '_' are allowed",
+                params.event
+            ));
+        }
+        if let Some(pointer) = params.pointer.as_deref() {
+            if !pointer.is_empty() && !pointer.starts_with('/') {
+                return Err(format!(
+                    "Invalid JSON pointer '{pointer}': must be empty or start with '/'"
+                ));
+            }
+        }
+
+        let (tx, rx) = oneshot::channel();
+        let tx = Mutex::new(Some(tx));
+        let WaitForEventParams {
+            event,
+            pointer,
+            matches,
+            timeout_ms,
+        } = params;
+        let listener = app.listen_any(event.clone(), move |e| {
+            let payload = parse_payload(e.payload());
+            if payload_matches(&payload, pointer.as_deref(), matches.as_ref()) {
+                if let Some(tx) = tx.lock().ok().and_then(|mut tx| tx.take()) {
+                    let _ = tx.send(payload);
+                }
+            }
+        });
+
+        Ok(Self {
+            app: app.clone(),
+            event,
+            listener,
+            rx,
+            timeout_ms: timeout_ms.unwrap_or(DEFAULT_WAIT_TIMEOUT_MS),
+            started: Instant::now(),
+        })
+    }
+
+    /// Waits for the first matching event or the timeout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(WaitForEventResult)` - The matching event's payload
+    /// * `Err(String)` - Timeout error message
+    pub async fn wait(mut self) -> Result<WaitForEventResult, String> {
+        let timeout = Duration::from_millis(self.timeout_ms);
+        match tokio::time::timeout(timeout, &mut self.rx).await {
+            Ok(Ok(payload)) => Ok(WaitForEventResult {
+                event: self.event.clone(),
+                payload,
+                elapsed_ms: self.started.elapsed().as_millis() as u64,
+            }),
+            _ => Err(format!(
+                "Timed out after {}ms waiting for event '{}'",
+                self.timeout_ms, self.event
+            )),
+        }
+    }
+}
+
+impl<R: Runtime> Drop for EventWaiter<R> {
+    fn drop(&mut self) {
+        self.app.unlisten(self.listener);
+    }
+}
+
+/// Waits for an event whose payload matches, without polling.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri application handle
+/// * `params` - Event name, optional pointer and matcher, and timeout
+///
+/// # Returns
+///
+/// * `Ok(WaitForEventResult)` - The matching event's payload
+/// * `Err(String)` - Error message if the parameters are invalid or the wait times out
+pub async fn wait_for_event<R: Runtime>(
+    app: AppHandle<R>,
+    params: WaitForEventParams,
+) -> Result<WaitForEventResult, String> {
+    EventWaiter::register(&app, params)?.wait().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tauri::Emitter;
+
+    #[test]
+    fn test_payload_matches() {
+        let payload = json!({ "status": "done", "items": [1, 2], "meta": { "id": 7 } });
+        assert!(payload_matches(&payload, None, None));
+        assert!(payload_matches(&payload, Some("/meta/id"), Some(&json!(7))));
+        assert!(payload_matches(&payload, Some("/items"), None));
+        assert!(!payload_matches(&payload, Some("/missing"), None));
+        assert!(payload_matches(
+            &payload,
+            None,
+            Some(&json!({ "meta": { "id": 7 } }))
+        ));
+        assert!(!payload_matches(
+            &payload,
+            None,
+            Some(&json!({ "items": [1] }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wait_returns_first_matching_payload() {
+        let app = tauri::test::mock_app();
+        let handle = app.handle().clone();
+
+        let waiter = EventWaiter::register(
+            &handle,
+            WaitForEventParams {
+                event: "job:update".into(),
+                matches: Some(json!({ "state": "done" })),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        handle
+            .emit("job:update", json!({ "state": "running" }))
+            .unwrap();
+        handle
+            .emit("job:update", json!({ "state": "done", "id": 1 }))
+            .unwrap();
+        handle
+            .emit("job:update", json!({ "state": "done", "id": 2 }))
+            .unwrap();
+
+        let result = waiter.wait().await.unwrap();
+        assert_eq!(result.payload, json!({ "state": "done", "id": 1 }));
+    }
+
+    #[tokio::test]
+    async fn test_wait_times_out() {
+        let app = tauri::test::mock_app();
+        let err = wait_for_event(
+            app.handle().clone(),
+            WaitForEventParams {
+                event: "never".into(),
+                timeout_ms: Some(10),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("Timed out after 10ms"));
+
+        let err = wait_for_event(
+            app.handle().clone(),
+            WaitForEventParams {
+                event: "job:*".into(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("Invalid event name"));
+    }
+}
@@ -1,12 +1,166 @@
 //! Window information retrieval.
 
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tauri::{command, Runtime, WebviewWindow};
+use tauri::{command, Monitor, Runtime, WebviewWindow};
+
+/// A rectangle in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Information about a monitor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorInfo {
+    /// Human-readable monitor name (if available)
+    pub name: Option<String>,
+    /// Width in physical pixels
+    pub width: u32,
+    /// Height in physical pixels
+    pub height: u32,
+    /// Width in logical pixels
+    pub logical_width: f64,
+    /// Height in logical pixels
+    pub logical_height: f64,
+    /// X position of the top-left corner in physical desktop coordinates
+    pub x: i32,
+    /// Y position of the top-left corner in physical desktop coordinates
+    pub y: i32,
+    /// Ratio of physical to logical pixels
+    pub scale_factor: f64,
+    /// Area not covered by taskbars, docks and menu bars, in physical pixels
+    pub work_area: Rect,
+    /// Whether this is the primary monitor
+    pub primary: bool,
+}
+
+impl MonitorInfo {
+    /// Builds monitor information, marking it primary if it matches `primary`.
+    pub fn new(monitor: &Monitor, primary: Option<&Monitor>) -> Self {
+        let size = monitor.size();
+        let position = monitor.position();
+        let logical = size.to_logical::<f64>(monitor.scale_factor());
+        let work_area = monitor.work_area();
+
+        Self {
+            name: monitor.name().cloned(),
+            width: size.width,
+            height: size.height,
+            logical_width: logical.width,
+            logical_height: logical.height,
+            x: position.x,
+            y: position.y,
+            scale_factor: monitor.scale_factor(),
+            work_area: Rect {
+                x: work_area.position.x,
+                y: work_area.position.y,
+                width: work_area.size.width,
+                height: work_area.size.height,
+            },
+            primary: primary.is_some_and(|p| {
+                p.name() == monitor.name() && p.position() == position && p.size() == size
+            }),
+        }
+    }
+}
+
+/// Geometry and state of a window, beyond the basics in `WindowInfo`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowDetails {
+    /// Outer width in physical pixels
+    pub width: u32,
+    /// Outer height in physical pixels
+    pub height: u32,
+    /// Outer x-coordinate in physical pixels
+    pub x: i32,
+    /// Outer y-coordinate in physical pixels
+    pub y: i32,
+    /// Inner (content) width in physical pixels
+    pub inner_width: u32,
+    /// Inner (content) height in physical pixels
+    pub inner_height: u32,
+    /// Inner (content) width in logical pixels
+    pub logical_inner_width: f64,
+    /// Inner (content) height in logical pixels
+    pub logical_inner_height: f64,
+    /// Ratio of physical to logical pixels
+    pub scale_factor: f64,
+    /// The monitor the window is currently on (if known)
+    pub monitor: Option<MonitorInfo>,
+    /// Current theme ("light" or "dark", if known)
+    pub theme: Option<String>,
+    pub minimized: bool,
+    pub maximized: bool,
+    pub fullscreen: bool,
+    pub decorated: bool,
+    pub resizable: bool,
+    pub always_on_top: bool,
+}
+
+/// Collects the geometry and state of a window.
+///
+/// # Arguments
+///
+/// * `window` - The Tauri window handle
+///
+/// # Returns
+///
+/// * `Ok(WindowDetails)` - Window geometry and state flags
+/// * `Err(String)` - Error message if the size or position cannot be read
+pub fn window_details<R: Runtime>(window: &WebviewWindow<R>) -> Result<WindowDetails, String> {
+    let size = window
+        .outer_size()
+        .map_err(|e| format!("Failed to get size: {e}"))?;
+    let position = window
+        .outer_position()
+        .map_err(|e| format!("Failed to get position: {e}"))?;
+    let inner_size = window
+        .inner_size()
+        .map_err(|e| format!("Failed to get inner size: {e}"))?;
+    let scale_factor = window
+        .scale_factor()
+        .map_err(|e| format!("Failed to get scale factor: {e}"))?;
+    let logical_inner_size = inner_size.to_logical::<f64>(scale_factor);
+
+    let primary = window.primary_monitor().ok().flatten();
+    let monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .map(|monitor| MonitorInfo::new(&monitor, primary.as_ref()));
+
+    Ok(WindowDetails {
+        width: size.width,
+        height: size.height,
+        x: position.x,
+        y: position.y,
+        inner_width: inner_size.width,
+        inner_height: inner_size.height,
+        logical_inner_width: logical_inner_size.width,
+        logical_inner_height: logical_inner_size.height,
+        scale_factor,
+        monitor,
+        theme: window.theme().ok().map(|theme| theme.to_string()),
+        minimized: window.is_minimized().unwrap_or(false),
+        maximized: window.is_maximized().unwrap_or(false),
+        fullscreen: window.is_fullscreen().unwrap_or(false),
+        decorated: window.is_decorated().unwrap_or(false),
+        resizable: window.is_resizable().unwrap_or(false),
+        always_on_top: window.is_always_on_top().unwrap_or(false),
+    })
+}
 
 /// Retrieves detailed information about the current window.
 ///
 /// Returns comprehensive window state including dimensions, position, title,
-/// focus state, and visibility.
+/// focus state, visibility, monitor and window state flags.
 ///
 /// # Arguments
 ///
@@ -15,11 +169,16 @@ use tauri::{command, Runtime, WebviewWindow};
 /// # Returns
 ///
 /// * `Ok(Value)` - JSON object containing:
-///   - `width`: Window width in pixels
-///   - `height`: Window height in pixels
-///   - `x`: Window x-coordinate
-///   - `y`: Window y-coordinate
+///   - `width`, `height`: Outer window size in physical pixels
+///   - `x`, `y`: Outer window position in physical pixels
+///   - `innerWidth`, `innerHeight`: Content size in physical pixels
+///   - `logicalInnerWidth`, `logicalInnerHeight`: Content size in logical pixels
+///   - `scaleFactor`: Ratio of physical to logical pixels
+///   - `monitor`: The current monitor (name, size, position, work area), or null
+///   - `theme`: "light" or "dark", or null
+///   - `minimized`, `maximized`, `fullscreen`, `decorated`, `resizable`, `alwaysOnTop`: State flags
 ///   - `title`: Window title string
+///   - `url`: The URL currently loaded in the webview
 ///   - `focused`: Whether the window has focus
 ///   - `visible`: Whether the window is visible
 /// * `Err(String)` - Error message if retrieval fails
@@ -34,12 +193,7 @@ use tauri::{command, Runtime, WebviewWindow};
 /// ```
 #[command]
 pub async fn get_window_info<R: Runtime>(window: WebviewWindow<R>) -> Result<Value, String> {
-    let size = window
-        .outer_size()
-        .map_err(|e| format!("Failed to get size: {e}"))?;
-    let position = window
-        .outer_position()
-        .map_err(|e| format!("Failed to get position: {e}"))?;
+    let details = window_details(&window)?;
     let title = window
         .title()
         .map_err(|e| format!("Failed to get title: {e}"))?;
@@ -49,14 +203,57 @@ pub async fn get_window_info<R: Runtime>(window: WebviewWindow<R>) -> Result<Val
     let is_visible = window
         .is_visible()
         .map_err(|e| format!("Failed to get visibility: {e}"))?;
+    let url = window.url().ok().map(|u| u.to_string());
+
+    let mut info = serde_json::to_value(details)
+        .map_err(|e| format!("Failed to serialize window info: {e}"))?;
+    if let Some(map) = info.as_object_mut() {
+        map.insert("title".to_string(), title.into());
+        map.insert("url".to_string(), url.into());
+        map.insert("focused".to_string(), is_focused.into());
+        map.insert("visible".to_string(), is_visible.into());
+    }
+
+    Ok(info)
+}
+
+#[cfg(all(test, desktop))]
+mod tests {
+    use super::*;
+    use tauri::{WebviewUrl, WebviewWindowBuilder};
+
+    #[tokio::test]
+    async fn test_get_window_info_fields() {
+        let app = tauri::test::mock_app();
+        let window = WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
+            .build()
+            .unwrap();
 
-    Ok(serde_json::json!({
-        "width": size.width,
-        "height": size.height,
-        "x": position.x,
-        "y": position.y,
-        "title": title,
-        "focused": is_focused,
-        "visible": is_visible,
-    }))
+        let info = get_window_info(window).await.unwrap();
+        for key in [
+            "width",
+            "height",
+            "x",
+            "y",
+            "innerWidth",
+            "innerHeight",
+            "logicalInnerWidth",
+            "logicalInnerHeight",
+            "scaleFactor",
+            "monitor",
+            "theme",
+            "minimized",
+            "maximized",
+            "fullscreen",
+            "decorated",
+            "resizable",
+            "alwaysOnTop",
+            "title",
+            "url",
+            "focused",
+            "visible",
+        ] {
+            assert!(info.get(key).is_some(), "missing {key}");
+        }
+    }
 }
@@ -1,18 +1,97 @@
 //! Configuration for the MCP Bridge plugin.
 //!
 //! This module provides configuration options for customizing the plugin behavior,
-//! including the WebSocket server bind address.
+//! including the WebSocket server bind address and transport.
+
+use std::path::PathBuf;
+
+use crate::redaction::RedactionRule;
+
+/// Transport(s) the WebSocket server listens on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Transport {
+    /// TCP on `bind_address`, scanning ports from `base_port`.
+    #[default]
+    Tcp,
+    /// A local socket only: a Unix domain socket on Linux/macOS, or a named
+    /// pipe on Windows. No TCP port is opened.
+    LocalSocket,
+    /// Both TCP and a local socket.
+    Both,
+}
+
+impl Transport {
+    /// Returns true if a TCP listener should be started.
+    pub fn uses_tcp(self) -> bool {
+        matches!(self, Self::Tcp | Self::Both)
+    }
+
+    /// Returns true if a local socket listener should be started.
+    pub fn uses_local_socket(self) -> bool {
+        matches!(self, Self::LocalSocket | Self::Both)
+    }
+}
+
+/// How the built-in MCP endpoint is exposed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum McpEndpoint {
+    /// No MCP endpoint; clients go through the Node MCP server.
+    #[default]
+    Disabled,
+    /// Streamable HTTP at `/mcp` on the bridge's existing listener(s).
+    Http,
+    /// Newline-delimited JSON-RPC on stdin/stdout, for sidecar mode.
+    Stdio,
+}
 
 /// Configuration for the MCP Bridge plugin.
+///
+/// New options may be added in minor releases, so create one with
+/// [`Config::default`], [`Config::new`] or [`Builder`] and set fields on it.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct Config {
     /// The address to bind the WebSocket server to.
     /// Default: "0.0.0.0" (all interfaces, for remote device support)
     /// Use "127.0.0.1" for localhost-only access.
     pub bind_address: String,
     /// The base port for the WebSocket server.
-    /// Default: 9223. The plugin will scan up to 100 ports from this base.
+    /// Default: 9223. The plugin will scan up to `port_count` ports from this
+    /// base. Use 0 to let the OS assign a free port.
     pub base_port: u16,
+    /// How many consecutive ports to try, starting at `base_port`.
+    /// Default: 100.
+    pub port_count: u16,
+    /// The transport(s) the WebSocket server listens on.
+    /// Default: [`Transport::Tcp`].
+    pub transport: Transport,
+    /// Overrides the local socket path.
+    /// Default: `None`, which derives the path from the bundle identifier and
+    /// PID (see [`crate::local_socket::socket_path`]).
+    pub socket_path: Option<PathBuf>,
+    /// Whether to advertise the TCP endpoint via mDNS/DNS-SD.
+    /// Default: false.
+    pub advertise_mdns: bool,
+    /// Exposes a built-in MCP endpoint.
+    /// Default: [`McpEndpoint::Disabled`].
+    pub mcp_endpoint: McpEndpoint,
+    /// Duration in milliseconds above which IPC calls are reported as slow.
+    /// Default: `None` (no slow-call alerts). Can be changed at runtime with
+    /// the `set_ipc_slow_threshold` command.
+    pub slow_call_threshold_ms: Option<u64>,
+    /// Rules masking sensitive values in captured IPC calls and console logs.
+    /// Default: none. Rules can also be added at runtime with the
+    /// `add_redaction_rule` command.
+    pub redaction_rules: Vec<RedactionRule>,
+    /// Installs a `tracing` subscriber capturing backend log records at this
+    /// level and above for the `get_backend_logs` command. Default: `None`
+    /// (apps can add [`backend_logs::layer`](crate::backend_logs::layer) to
+    /// their own subscriber instead).
+    pub backend_log_level: Option<tracing::Level>,
+    /// Most verbose level the plugin's own diagnostics are logged at, through
+    /// the `log` facade with `tauri_plugin_mcp_bridge::*` targets. Default:
+    /// `Info` (`Off` silences the plugin).
+    pub log_level: log::LevelFilter,
 }
 
 impl Default for Config {
@@ -20,6 +99,15 @@ impl Default for Config {
         Self {
             bind_address: "0.0.0.0".to_string(),
             base_port: 9223,
+            port_count: crate::discovery::DEFAULT_PORT_COUNT,
+            transport: Transport::default(),
+            socket_path: None,
+            advertise_mdns: false,
+            mcp_endpoint: McpEndpoint::default(),
+            slow_call_threshold_ms: None,
+            redaction_rules: Vec::new(),
+            backend_log_level: None,
+            log_level: log::LevelFilter::Info,
         }
     }
 }
@@ -29,7 +117,7 @@ impl Config {
     pub fn new(bind_address: &str) -> Self {
         Self {
             bind_address: bind_address.to_string(),
-            base_port: 9223,
+            ..Self::default()
         }
     }
 
@@ -37,7 +125,7 @@ impl Config {
     pub fn localhost_only() -> Self {
         Self {
             bind_address: "127.0.0.1".to_string(),
-            base_port: 9223,
+            ..Self::default()
         }
     }
 }
@@ -95,11 +183,13 @@ impl Builder {
 
     /// Sets the base port for the WebSocket server.
     ///
-    /// The plugin will scan up to 100 ports starting from this base port.
+    /// The plugin will scan up to 100 ports (see [`Builder::port_count`])
+    /// starting from this base port. Port 0 lets the OS assign a free port;
+    /// read the actual port from [`BridgeStatus`](crate::BridgeStatus).
     ///
     /// # Arguments
     ///
-    /// * `port` - The base port number (e.g., 9223)
+    /// * `port` - The base port number (e.g., 9223), or 0 for any free port
     ///
     /// # Examples
     ///
@@ -113,6 +203,185 @@ impl Builder {
         self
     }
 
+    /// Sets how many consecutive ports are tried, starting at the base port.
+    ///
+    /// If every port in the range is taken, the TCP listener is not started
+    /// and the error is recorded in [`BridgeStatus`](crate::BridgeStatus).
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of ports to try (at least 1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tauri_plugin_mcp_bridge::Builder;
+    ///
+    /// // Only ever use ports 9300-9309
+    /// let builder = Builder::new().base_port(9300).port_count(10);
+    /// ```
+    pub fn port_count(mut self, count: u16) -> Self {
+        self.config.port_count = count;
+        self
+    }
+
+    /// Sets the transport(s) the WebSocket server listens on.
+    ///
+    /// # Arguments
+    ///
+    /// * `transport` - TCP, a local socket, or both
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tauri_plugin_mcp_bridge::{Builder, Transport};
+    ///
+    /// // Only reachable through a Unix domain socket / named pipe
+    /// let builder = Builder::new().transport(Transport::LocalSocket);
+    /// ```
+    pub fn transport(mut self, transport: Transport) -> Self {
+        self.config.transport = transport;
+        self
+    }
+
+    /// Overrides the local socket path.
+    ///
+    /// Only used when the transport includes a local socket. On Windows this
+    /// must be a named pipe name such as `\\.\pipe\my-app`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tauri_plugin_mcp_bridge::{Builder, Transport};
+    ///
+    /// let builder = Builder::new()
+    ///     .transport(Transport::Both)
+    ///     .socket_path("/tmp/my-app-mcp.sock");
+    /// ```
+    pub fn socket_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.socket_path = Some(path.into());
+        self
+    }
+
+    /// Enables or disables mDNS/DNS-SD advertisement of the bridge.
+    ///
+    /// When enabled, the TCP endpoint is advertised as a `_tauri-mcp._tcp`
+    /// service with TXT records for the app identifier, version and protocol
+    /// version, so devices on the LAN can discover it without knowing the IP
+    /// and port. Has no effect when the transport is local socket only.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tauri_plugin_mcp_bridge::Builder;
+    ///
+    /// let builder = Builder::new().advertise_mdns(true);
+    /// ```
+    pub fn advertise_mdns(mut self, enabled: bool) -> Self {
+        self.config.advertise_mdns = enabled;
+        self
+    }
+
+    /// Exposes a built-in MCP server so MCP clients can attach to the app
+    /// without the Node MCP server.
+    ///
+    /// * [`McpEndpoint::Http`] - Streamable HTTP at `/mcp` on the same
+    ///   listener(s) as the WebSocket server
+    /// * [`McpEndpoint::Stdio`] - JSON-RPC on stdin/stdout; plugin log output
+    ///   moves to stderr
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tauri_plugin_mcp_bridge::{Builder, McpEndpoint};
+    ///
+    /// // MCP clients connect to http://127.0.0.1:9223/mcp
+    /// let builder = Builder::new()
+    ///     .bind_address("127.0.0.1")
+    ///     .mcp_endpoint(McpEndpoint::Http);
+    /// ```
+    pub fn mcp_endpoint(mut self, endpoint: McpEndpoint) -> Self {
+        self.config.mcp_endpoint = endpoint;
+        self
+    }
+
+    /// Reports IPC calls slower than `ms` milliseconds.
+    ///
+    /// While the IPC monitor runs, slow calls are counted by `get_ipc_stats`
+    /// and broadcast to WebSocket clients as `ipc_slow_call` messages.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tauri_plugin_mcp_bridge::Builder;
+    ///
+    /// let builder = Builder::new().slow_call_threshold_ms(250);
+    /// ```
+    pub fn slow_call_threshold_ms(mut self, ms: u64) -> Self {
+        self.config.slow_call_threshold_ms = Some(ms);
+        self
+    }
+
+    /// Adds a rule masking sensitive values in captured IPC calls.
+    ///
+    /// Key patterns of rules without a `command` filter also mask console
+    /// logs captured from the webview.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tauri_plugin_mcp_bridge::redaction::RedactionRule;
+    /// use tauri_plugin_mcp_bridge::Builder;
+    ///
+    /// let builder = Builder::new().redaction_rule(RedactionRule {
+    ///     id: "secrets".to_string(),
+    ///     keys: vec!["*token*".to_string(), "password".to_string()],
+    ///     ..Default::default()
+    /// });
+    /// ```
+    pub fn redaction_rule(mut self, rule: RedactionRule) -> Self {
+        self.config.redaction_rules.push(rule);
+        self
+    }
+
+    /// Captures backend log records at `level` and above, from `tracing` and
+    /// the `log` crate, so clients can query them with `get_backend_logs`.
+    ///
+    /// This installs a global `tracing` subscriber when the plugin is
+    /// created. Apps that set up their own subscriber should add
+    /// [`backend_logs::layer`](crate::backend_logs::layer) to it instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tauri_plugin_mcp_bridge::Builder;
+    ///
+    /// let builder = Builder::new().capture_backend_logs(tracing::Level::DEBUG);
+    /// ```
+    pub fn capture_backend_logs(mut self, level: tracing::Level) -> Self {
+        self.config.backend_log_level = Some(level);
+        self
+    }
+
+    /// Sets the most verbose level the plugin logs its own diagnostics at.
+    ///
+    /// Diagnostics go through the `log` facade with targets such as
+    /// `tauri_plugin_mcp_bridge::ws_server`, so nothing is printed unless the
+    /// app installs a logger. Use `LevelFilter::Debug` to include
+    /// per-connection messages or `LevelFilter::Off` to silence the plugin.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tauri_plugin_mcp_bridge::Builder;
+    ///
+    /// let builder = Builder::new().log_level(log::LevelFilter::Warn);
+    /// ```
+    pub fn log_level(mut self, level: log::LevelFilter) -> Self {
+        self.config.log_level = level;
+        self
+    }
+
     /// Builds the plugin with the configured options.
     pub fn build<R: tauri::Runtime>(self) -> tauri::plugin::TauriPlugin<R> {
         crate::init_with_config(self.config)
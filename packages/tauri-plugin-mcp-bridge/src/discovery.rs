@@ -1,7 +1,90 @@
-//! Simple port discovery for multiple Tauri instances.
+//! Port and instance discovery for multiple Tauri instances.
 //!
 //! This module provides a lightweight mechanism for multiple Tauri apps
 //! to coexist on the same machine by finding available ports dynamically.
+//!
+//! Each running instance also writes an [`InstanceRecord`] to a well-known
+//! per-user directory (see [`instances_dir`]) so clients can enumerate live
+//! apps with [`list_instances`] instead of probing the whole port range.
+
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that overrides the instance registry directory.
+pub const ENV_INSTANCES_DIR: &str = "TAURI_MCP_INSTANCES_DIR";
+
+/// Default number of ports scanned starting from the base port.
+pub const DEFAULT_PORT_COUNT: u16 = 100;
+
+/// Error returned when the WebSocket server port cannot be bound.
+#[derive(Debug, thiserror::Error)]
+pub enum BindError {
+    #[error("Invalid bind address '{0}'")]
+    InvalidAddress(String),
+
+    #[error("No free port in range {start}-{end} on {bind_address}")]
+    NoFreePort {
+        bind_address: String,
+        start: u16,
+        end: u16,
+    },
+
+    #[error("Failed to bind {bind_address}:{port}: {source}")]
+    Io {
+        bind_address: String,
+        port: u16,
+        source: io::Error,
+    },
+}
+
+/// Binds a TCP listener on the first free port in a range.
+///
+/// Binding (rather than probing and releasing the port) means the returned
+/// listener owns the port, so no other process can take it between the check
+/// and the server starting.
+///
+/// # Arguments
+///
+/// * `bind_address` - The address to bind to (e.g., "0.0.0.0" or "127.0.0.1")
+/// * `base_port` - The first port to try, or 0 to let the OS assign a port
+/// * `port_count` - How many consecutive ports to try (ignored for port 0)
+///
+/// # Returns
+///
+/// * `Ok(TcpListener)` - A listener bound to the first free port
+/// * `Err(BindError)` - If the address is invalid or every port is taken
+pub fn bind_available_port(
+    bind_address: &str,
+    base_port: u16,
+    port_count: u16,
+) -> Result<std::net::TcpListener, BindError> {
+    let ip: IpAddr = bind_address
+        .parse()
+        .map_err(|_| BindError::InvalidAddress(bind_address.to_string()))?;
+
+    if base_port == 0 {
+        return std::net::TcpListener::bind((ip, 0)).map_err(|source| BindError::Io {
+            bind_address: bind_address.to_string(),
+            port: 0,
+            source,
+        });
+    }
+
+    let ports = port_range(base_port, port_count);
+    for port in ports.clone() {
+        if let Ok(listener) = std::net::TcpListener::bind((ip, port)) {
+            return Ok(listener);
+        }
+    }
+
+    Err(BindError::NoFreePort {
+        bind_address: bind_address.to_string(),
+        start: *ports.start(),
+        end: *ports.end(),
+    })
+}
 
 /// Finds an available port for the WebSocket server.
 ///
@@ -13,19 +96,36 @@
 /// # Returns
 ///
 /// An available port number in the range base_port to base_port+99, or base_port if none are available.
+#[deprecated(
+    since = "0.12.0",
+    note = "returns an in-use port when the range is exhausted; use `bind_available_port` or `try_find_available_port`"
+)]
 pub fn find_available_port(bind_address: &str, base_port: u16) -> u16 {
-    let max_attempts = 100;
+    try_find_available_port(bind_address, base_port).unwrap_or(base_port)
+}
 
-    for offset in 0..max_attempts {
-        let port = base_port + offset;
-        if is_port_available(bind_address, port) {
-            return port;
-        }
-    }
+/// Finds an available port for the WebSocket server.
+///
+/// Prefer [`bind_available_port`], which keeps the port bound. A port
+/// returned here may be taken by another process before it is used.
+///
+/// # Arguments
+///
+/// * `bind_address` - The address to bind to (e.g., "0.0.0.0" or "127.0.0.1")
+/// * `base_port` - The starting port number to scan from
+///
+/// # Returns
+///
+/// An available port number in the range base_port to base_port+99, or `None`
+/// if all of them are taken.
+pub fn try_find_available_port(bind_address: &str, base_port: u16) -> Option<u16> {
+    port_range(base_port, DEFAULT_PORT_COUNT).find(|port| is_port_available(bind_address, *port))
+}
 
-    // If no ports in the range are available, use default
-    // (The app will need to handle port conflicts)
-    base_port
+/// Returns the inclusive range of ports to scan, clamped to `u16::MAX`.
+fn port_range(base_port: u16, port_count: u16) -> std::ops::RangeInclusive<u16> {
+    let end = base_port.saturating_add(port_count.max(1) - 1);
+    base_port..=end
 }
 
 /// Checks if a port is available on the specified bind address.
@@ -35,28 +135,366 @@ fn is_port_available(bind_address: &str, port: u16) -> bool {
     TcpListener::bind(format!("{bind_address}:{port}")).is_ok()
 }
 
+/// A discovery record describing a running bridge instance.
+///
+/// Records are written as JSON to `{instances_dir}/{identifier}-{pid}.json`
+/// when the plugin starts and removed when the app exits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceRecord {
+    /// The app's bundle identifier
+    pub identifier: String,
+    /// The app's product name
+    pub product_name: String,
+    /// Process ID of the running instance
+    pub pid: u32,
+    /// Current working directory of the host process (if available)
+    pub cwd: Option<String>,
+    /// TCP port of the WebSocket server (if listening on TCP)
+    pub port: Option<u16>,
+    /// Local socket path of the WebSocket server (if listening on a local socket)
+    pub socket_path: Option<PathBuf>,
+    /// Version of the bridge WebSocket protocol
+    pub protocol_version: u32,
+    /// Unix timestamp in milliseconds when the instance started
+    pub started_at: u64,
+}
+
+impl InstanceRecord {
+    /// Writes this record to `dir`, creating the directory if needed.
+    ///
+    /// The file is written to a temporary name and renamed into place so
+    /// readers never observe a partially written record.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PathBuf)` - Path of the written record file
+    /// * `Err(io::Error)` - If the directory or file could not be written
+    pub fn write(&self, dir: &Path) -> io::Result<PathBuf> {
+        create_private_dir(dir)?;
+
+        let path = instance_record_path(dir, &self.identifier, self.pid);
+        let tmp_path = path.with_extension("json.tmp");
+        let json =
+            serde_json::to_vec_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        std::fs::write(&tmp_path, json)?;
+        std::fs::rename(&tmp_path, &path)?;
+        Ok(path)
+    }
+}
+
+/// Returns the per-user directory where instance records are stored.
+///
+/// Uses `TAURI_MCP_INSTANCES_DIR` if set. Otherwise:
+///
+/// * Unix: `$XDG_RUNTIME_DIR/tauri-mcp/instances`, falling back to
+///   `{temp_dir}/tauri-mcp-{user}/instances`
+/// * Windows: `%LOCALAPPDATA%\tauri-mcp\instances`
+pub fn instances_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(ENV_INSTANCES_DIR) {
+        return PathBuf::from(dir);
+    }
+
+    #[cfg(windows)]
+    {
+        std::env::var_os("LOCALAPPDATA")
+            .map(PathBuf::from)
+            .unwrap_or_else(std::env::temp_dir)
+            .join("tauri-mcp")
+            .join("instances")
+    }
+
+    #[cfg(not(windows))]
+    {
+        match std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from) {
+            Some(dir) if dir.is_dir() => dir.join("tauri-mcp").join("instances"),
+            _ => {
+                let user = std::env::var("USER").unwrap_or_else(|_| "default".to_string());
+                std::env::temp_dir()
+                    .join(format!("tauri-mcp-{user}"))
+                    .join("instances")
+            }
+        }
+    }
+}
+
+/// Returns the record file path for an instance.
+pub fn instance_record_path(dir: &Path, identifier: &str, pid: u32) -> PathBuf {
+    let identifier: String = identifier
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    dir.join(format!("{identifier}-{pid}.json"))
+}
+
+/// Removes the record file for an instance, ignoring missing files.
+pub fn remove_instance_record(dir: &Path, identifier: &str, pid: u32) {
+    let _ = std::fs::remove_file(instance_record_path(dir, identifier, pid));
+}
+
+/// Lists the instances recorded in `dir` whose process is still alive.
+///
+/// Records left behind by crashed processes are deleted as a side effect, and
+/// unreadable files are skipped. Results are sorted by start time.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri_plugin_mcp_bridge::discovery::{instances_dir, list_instances};
+///
+/// for instance in list_instances(&instances_dir()) {
+///     println!("{} (pid {}) on port {:?}", instance.identifier, instance.pid, instance.port);
+/// }
+/// ```
+pub fn list_instances(dir: &Path) -> Vec<InstanceRecord> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut instances: Vec<InstanceRecord> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let record = std::fs::read(&path)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<InstanceRecord>(&bytes).ok())?;
+
+            if is_process_alive(record.pid) {
+                Some(record)
+            } else {
+                let _ = std::fs::remove_file(&path);
+                None
+            }
+        })
+        .collect();
+
+    instances.sort_by_key(|record| record.started_at);
+    instances
+}
+
+/// Removes records in `dir` whose process is no longer running.
+pub fn prune_stale_instances(dir: &Path) {
+    let _ = list_instances(dir);
+}
+
+/// Creates `dir` (and parents) if it does not exist.
+///
+/// On Unix, directories created here are restricted to the current user. An
+/// existing directory (e.g. one named by `TAURI_MCP_INSTANCES_DIR`) keeps its
+/// permissions.
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+
+    builder.create(dir)
+}
+
+/// Checks whether a process with the given PID is running.
+#[cfg(unix)]
+fn is_process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    if pid <= 0 {
+        return false;
+    }
+
+    // Signal 0 performs error checking only. EPERM means the process exists
+    // but belongs to another user.
+    let result = unsafe { libc::kill(pid, 0) };
+    result == 0 || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Checks whether a process with the given PID is running.
+#[cfg(windows)]
+fn is_process_alive(pid: u32) -> bool {
+    use windows::Win32::Foundation::{CloseHandle, STILL_ACTIVE};
+    use windows::Win32::System::Threading::{
+        GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    unsafe {
+        let Ok(handle) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+            return false;
+        };
+        let mut exit_code = 0u32;
+        let alive = GetExitCodeProcess(handle, &mut exit_code).is_ok()
+            && exit_code == STILL_ACTIVE.0 as u32;
+        let _ = CloseHandle(handle);
+        alive
+    }
+}
+
+/// Checks whether a process with the given PID is running.
+#[cfg(not(any(unix, windows)))]
+fn is_process_alive(_pid: u32) -> bool {
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "tauri-mcp-test-instances-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn record(pid: u32) -> InstanceRecord {
+        InstanceRecord {
+            identifier: "com.example.app".to_string(),
+            product_name: "Example".to_string(),
+            pid,
+            cwd: Some("/tmp".to_string()),
+            port: Some(9223),
+            socket_path: None,
+            protocol_version: 1,
+            started_at: 1234567890,
+        }
+    }
+
     #[test]
     fn test_port_availability_all_interfaces() {
-        let port = find_available_port("0.0.0.0", 9223);
+        let port = try_find_available_port("0.0.0.0", 9223).unwrap();
         assert!(port >= 9223);
         assert!(port < 9323);
     }
 
     #[test]
     fn test_port_availability_localhost() {
-        let port = find_available_port("127.0.0.1", 9223);
+        let port = try_find_available_port("127.0.0.1", 9223).unwrap();
         assert!(port >= 9223);
         assert!(port < 9323);
     }
 
     #[test]
     fn test_port_availability_custom_base() {
-        let port = find_available_port("127.0.0.1", 9400);
+        let port = try_find_available_port("127.0.0.1", 9400).unwrap();
         assert!(port >= 9400);
         assert!(port < 9500);
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_find_available_port_falls_back_to_base() {
+        let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let taken_port = taken.local_addr().unwrap().port();
+
+        let port = find_available_port("127.0.0.1", taken_port);
+        assert_ne!(port, 0);
+        assert!(port >= taken_port);
+    }
+
+    #[test]
+    fn test_bind_available_port_skips_taken_port() {
+        let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let taken_port = taken.local_addr().unwrap().port();
+
+        match bind_available_port("127.0.0.1", taken_port, 2) {
+            Ok(listener) => assert_ne!(listener.local_addr().unwrap().port(), taken_port),
+            // The only other candidate may legitimately be in use
+            Err(BindError::NoFreePort { start, .. }) => assert_eq!(start, taken_port),
+            Err(e) => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_bind_available_port_reports_exhausted_range() {
+        let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let taken_port = taken.local_addr().unwrap().port();
+
+        let err = bind_available_port("127.0.0.1", taken_port, 1).unwrap_err();
+        assert!(matches!(
+            err,
+            BindError::NoFreePort { start, end, .. } if start == taken_port && end == taken_port
+        ));
+    }
+
+    #[test]
+    fn test_bind_available_port_os_assigned() {
+        let listener = bind_available_port("127.0.0.1", 0, 1).unwrap();
+        assert_ne!(listener.local_addr().unwrap().port(), 0);
+    }
+
+    #[test]
+    fn test_bind_available_port_invalid_address() {
+        assert!(matches!(
+            bind_available_port("not-an-ip", 9223, 1),
+            Err(BindError::InvalidAddress(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_and_list_live_instance() {
+        let dir = test_dir("live");
+        let path = record(std::process::id()).write(&dir).unwrap();
+        assert!(path.exists());
+
+        let instances = list_instances(&dir);
+        assert_eq!(instances, vec![record(std::process::id())]);
+
+        remove_instance_record(&dir, "com.example.app", std::process::id());
+        assert!(list_instances(&dir).is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_keeps_existing_dir_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let created = test_dir("created");
+        record(std::process::id()).write(&created).unwrap();
+        let mode = std::fs::metadata(&created).unwrap().permissions().mode();
+        assert_eq!(mode & 0o077, 0);
+
+        let shared = test_dir("shared");
+        std::fs::create_dir(&shared).unwrap();
+        std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o755)).unwrap();
+        record(std::process::id()).write(&shared).unwrap();
+        let mode = std::fs::metadata(&shared).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+
+        let _ = std::fs::remove_dir_all(&created);
+        let _ = std::fs::remove_dir_all(&shared);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_list_removes_stale_instances() {
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let dead_pid = child.id();
+        child.wait().unwrap();
+
+        let dir = test_dir("stale");
+        let path = record(dead_pid).write(&dir).unwrap();
+
+        assert!(list_instances(&dir).is_empty());
+        assert!(!path.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_list_missing_dir_is_empty() {
+        assert!(list_instances(&test_dir("missing")).is_empty());
+    }
 }
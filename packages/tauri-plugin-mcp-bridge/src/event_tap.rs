@@ -0,0 +1,276 @@
+//! Capture of Tauri events.
+//!
+//! The event tap records events emitted with `emit`, `emit_to` or
+//! `emit_filter`, from Rust or from the frontend, and keeps them for querying
+//! like [`IPCMonitor`](crate::monitor::IPCMonitor). Captured events can also be
+//! streamed over the WebSocket broadcast channel as `tauri_event` messages.
+//!
+//! Limitations, all due to what Tauri exposes to listeners:
+//!
+//! * Tauri has no wildcard listener, so the tap only captures the exact event
+//!   names it is given. Glob patterns select among those when querying.
+//! * Listeners are not told who emitted an event or where it was sent, so
+//!   [`TauriEventRecord::source`] and [`TauriEventRecord::target`] are only
+//!   set for events emitted through the bridge's `emit_event` command.
+//! * Capturing deliveries in the frontend instead is not possible: Tauri
+//!   defines its event dispatch function and listener table in each webview
+//!   as non-writable properties, so bridge.js cannot hook them.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tauri::EventId;
+
+/// Maximum number of events kept by the tap; older events are dropped first.
+pub const MAX_TAPPED_EVENTS: usize = 1000;
+
+/// Message type of event tap broadcasts.
+pub const TAURI_EVENT_TYPE: &str = "tauri_event";
+
+/// A captured Tauri event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TauriEventRecord {
+    /// Sequence number, increasing for each captured event
+    pub seq: u64,
+    /// Unix timestamp in milliseconds when the event was received
+    pub timestamp: u64,
+    /// Event name
+    pub event: String,
+    /// Event payload (a string if the payload is not valid JSON)
+    pub payload: Value,
+    /// Who emitted the event ("bridge"); only set for events emitted through
+    /// the bridge's `emit_event` command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Event target (e.g. "any" or `{"kind": "WebviewWindow", "label": "main"}`);
+    /// only set for events emitted through the bridge's `emit_event` command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<Value>,
+}
+
+/// Event tap state.
+///
+/// Typically wrapped in [`EventTapState`] and kept in managed state.
+#[derive(Debug, Default)]
+pub struct EventTap {
+    /// Whether events are being captured
+    pub enabled: bool,
+    /// Whether captured events are broadcast as `tauri_event` messages
+    pub stream: bool,
+    subscriptions: HashMap<String, EventId>,
+    events: VecDeque<TauriEventRecord>,
+    next_seq: u64,
+}
+
+impl EventTap {
+    /// Creates a disabled tap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the event names the tap is subscribed to, sorted.
+    pub fn subscriptions(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.subscriptions.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Returns whether the tap has a listener for `name`.
+    pub fn is_subscribed(&self, name: &str) -> bool {
+        self.subscriptions.contains_key(name)
+    }
+
+    /// Records the listener registered for `name`.
+    pub fn add_subscription(&mut self, name: &str, id: EventId) {
+        self.subscriptions.insert(name.to_string(), id);
+    }
+
+    /// Removes and returns all listener IDs.
+    pub fn take_subscriptions(&mut self) -> Vec<EventId> {
+        self.subscriptions.drain().map(|(_, id)| id).collect()
+    }
+
+    /// Records an event if the tap is enabled, returning the stored record.
+    pub fn record(
+        &mut self,
+        event: &str,
+        payload: Value,
+        source: Option<String>,
+        target: Option<Value>,
+    ) -> Option<TauriEventRecord> {
+        if !self.enabled {
+            return None;
+        }
+
+        self.next_seq += 1;
+        let record = TauriEventRecord {
+            seq: self.next_seq,
+            timestamp: crate::monitor::current_timestamp(),
+            event: event.to_string(),
+            payload,
+            source,
+            target,
+        };
+
+        if self.events.len() >= MAX_TAPPED_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(record.clone());
+        Some(record)
+    }
+
+    /// Returns captured events, optionally filtered by a name pattern and a
+    /// minimum sequence number.
+    pub fn get_events(&self, pattern: Option<&str>, since: Option<u64>) -> Vec<TauriEventRecord> {
+        self.events
+            .iter()
+            .filter(|record| since.map_or(true, |since| record.seq > since))
+            .filter(|record| pattern.map_or(true, |p| matches_pattern(p, &record.event)))
+            .cloned()
+            .collect()
+    }
+
+    /// Removes all captured events.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+thread_local! {
+    static EMIT_CONTEXT: RefCell<Option<(String, Value)>> = const { RefCell::new(None) };
+}
+
+/// Runs `emit` with a known source and target.
+///
+/// Tauri calls listeners synchronously on the emitting thread, so events
+/// captured while `emit` runs are attributed to `source` and `target`.
+pub fn with_emit_context<T>(source: &str, target: Value, emit: impl FnOnce() -> T) -> T {
+    EMIT_CONTEXT.with(|context| *context.borrow_mut() = Some((source.to_string(), target)));
+    let result = emit();
+    EMIT_CONTEXT.with(|context| *context.borrow_mut() = None);
+    result
+}
+
+/// Returns the source and target set by [`with_emit_context`] on this thread.
+pub fn current_emit_context() -> (Option<String>, Option<Value>) {
+    EMIT_CONTEXT.with(|context| match &*context.borrow() {
+        Some((source, target)) => (Some(source.clone()), Some(target.clone())),
+        None => (None, None),
+    })
+}
+
+/// Type alias for thread-safe event tap state.
+pub type EventTapState = Arc<Mutex<EventTap>>;
+
+/// Returns whether `name` is a valid Tauri event name (alphanumeric, `-`,
+/// `/`, `:` and `_`).
+pub fn is_valid_event_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '/' | ':' | '_'))
+}
+
+/// Matches an event name against a glob pattern where `*` matches any
+/// sequence of characters.
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri_plugin_mcp_bridge::event_tap::matches_pattern;
+///
+/// assert!(matches_pattern("download:*", "download:progress"));
+/// assert!(matches_pattern("*", "anything"));
+/// assert!(!matches_pattern("download:*", "upload:progress"));
+/// ```
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No '*' in the pattern
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.ends_with(last)
+}
+
+/// Parses an event payload, falling back to a JSON string.
+pub fn parse_payload(payload: &str) -> Value {
+    serde_json::from_str(payload).unwrap_or_else(|_| Value::String(payload.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("state-changed", "state-changed"));
+        assert!(!matches_pattern("state-changed", "state-changed-2"));
+        assert!(matches_pattern("*:progress", "download:progress"));
+        assert!(matches_pattern("a*b*c", "a-x-b-y-c"));
+        assert!(!matches_pattern("a*b*c", "a-x-c"));
+        assert!(matches_pattern("ab*b", "abb"));
+        assert!(!matches_pattern("ab*b", "ab"));
+    }
+
+    #[test]
+    fn test_record_and_query() {
+        let mut tap = EventTap::new();
+        assert!(tap.record("ignored", json!(null), None, None).is_none());
+
+        tap.enabled = true;
+        tap.record("download:progress", json!({ "pct": 10 }), None, None);
+        tap.record(
+            "state-changed",
+            json!(1),
+            Some("bridge".into()),
+            Some(json!("any")),
+        );
+        tap.record("download:done", json!(null), None, None);
+
+        assert_eq!(tap.get_events(None, None).len(), 3);
+        assert_eq!(tap.get_events(Some("download:*"), None).len(), 2);
+        let since = tap.get_events(None, Some(2));
+        assert_eq!(since.len(), 1);
+        assert_eq!(since[0].event, "download:done");
+
+        tap.clear();
+        assert!(tap.get_events(None, None).is_empty());
+    }
+
+    #[test]
+    fn test_buffer_is_bounded() {
+        let mut tap = EventTap::new();
+        tap.enabled = true;
+        for _ in 0..MAX_TAPPED_EVENTS + 5 {
+            tap.record("tick", Value::Null, None, None);
+        }
+        let events = tap.get_events(None, None);
+        assert_eq!(events.len(), MAX_TAPPED_EVENTS);
+        assert_eq!(events[0].seq, 6);
+    }
+
+    #[test]
+    fn test_is_valid_event_name() {
+        assert!(is_valid_event_name("app:state-changed/v2_x"));
+        assert!(!is_valid_event_name("download:*"));
+        assert!(!is_valid_event_name(""));
+    }
+}
@@ -0,0 +1,587 @@
+//! Backend-side IPC capture and mocking.
+//!
+//! The bridge.js shim only sees calls made through `window.__TAURI__.core.invoke`
+//! after it has loaded, and measures durations in the webview. Wrapping the
+//! app's invoke handler with [`capture`] records every app command at the
+//! invoke boundary instead, with the backend execution time, and feeds the
+//! same [`IPCMonitor`](crate::monitor::IPCMonitor):
+//!
+//! ```rust,ignore
+//! tauri::Builder::default()
+//!     .plugin(tauri_plugin_mcp_bridge::init())
+//!     .invoke_handler(tauri_plugin_mcp_bridge::ipc_capture::capture(
+//!         tauri::generate_handler![greet, save_settings],
+//!     ))
+//! ```
+//!
+//! Tauri does not let a handler observe the response of an invoke, so while
+//! the monitor is running each call is dispatched again through
+//! [`Webview::on_message`] with a responder that records the response before
+//! passing it on. Plugin commands do not go through the app's invoke handler
+//! and are still reported by bridge.js.
+//!
+//! The wrapper also enforces [mock rules](crate::ipc_mock), answering
+//! matching calls without running the real command, and
+//! [fault rules](crate::ipc_faults) for calls no mock rule matched.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use tauri::http::{HeaderName, HeaderValue};
+use tauri::ipc::{CallbackFn, Invoke, InvokeBody, InvokeError, InvokeResponse, InvokeResponseBody};
+use tauri::webview::InvokeRequest;
+use tauri::{Manager, Runtime, Webview};
+
+use crate::ipc_faults::{InjectedFault, SharedFaultInjector};
+use crate::ipc_mock::SharedMockRegistry;
+use crate::monitor::{current_timestamp, IPCEvent, IPCMonitorState};
+
+/// Header marking a call that is being re-dispatched for capture.
+const CAPTURED_HEADER: HeaderName = HeaderName::from_static("x-mcp-bridge-captured");
+
+/// Source recorded for events captured by the backend.
+pub const BACKEND_SOURCE: &str = "backend";
+
+/// Source recorded for calls answered by a mock rule.
+pub const MOCK_SOURCE: &str = "mock";
+
+/// Source recorded for calls failed or left unanswered by a fault rule.
+pub const FAULT_SOURCE: &str = "fault";
+
+/// Managed state marking an app whose invoke handler is wrapped with [`capture`].
+struct CaptureInstalled;
+
+/// Returns whether the app's invoke handler is wrapped with [`capture`].
+///
+/// The wrapper marks the app when it handles its first call, so this is false
+/// until then. While it is true, bridge.js reports for app commands are
+/// ignored so calls are not recorded twice.
+pub fn is_installed<R: Runtime, M: Manager<R>>(manager: &M) -> bool {
+    manager.try_state::<CaptureInstalled>().is_some()
+}
+
+/// Wraps an invoke handler so the IPC monitor records app commands from the
+/// backend and [mock](crate::ipc_mock) and [fault](crate::ipc_faults) rules
+/// are enforced.
+///
+/// When the monitor is stopped and no rule matches, calls go straight to
+/// `handler`.
+pub fn capture<R, F>(handler: F) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static
+where
+    R: Runtime,
+    F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+{
+    let handler = Arc::new(handler);
+
+    move |invoke: Invoke<R>| {
+        let webview = invoke.message.webview_ref();
+        if !is_installed(webview) {
+            webview.manage(CaptureInstalled);
+        }
+        if invoke.message.headers().contains_key(CAPTURED_HEADER) {
+            return handler(invoke);
+        }
+
+        let webview = invoke.message.webview();
+        let rule = webview.try_state::<SharedMockRegistry>().and_then(|mocks| {
+            let args = body_to_json(invoke.message.payload());
+            mocks
+                .lock()
+                .ok()?
+                .take_match(invoke.message.command(), &args)
+        });
+        if let Some(rule) = rule {
+            let delay = Duration::from_millis(rule.delay_ms.unwrap_or(0));
+            match rule.response() {
+                Some(response) if delay.is_zero() => {
+                    respond_with(invoke, response, Instant::now(), MOCK_SOURCE, None);
+                }
+                response => {
+                    answer_later(handler.clone(), invoke, delay, response, MOCK_SOURCE, None)
+                }
+            }
+            return true;
+        }
+
+        let fault = webview
+            .try_state::<SharedFaultInjector>()
+            .and_then(|faults| faults.lock().ok()?.inject(invoke.message.command()));
+        let Some(fault) = fault else {
+            return dispatch(&*handler, invoke, None);
+        };
+
+        if fault.hang {
+            // Dropping the call without answering leaves its promise pending
+            record_fault(invoke, fault);
+            return true;
+        }
+        let delay = Duration::from_millis(fault.latency_ms.unwrap_or(0));
+        match fault.error.clone() {
+            None if delay.is_zero() => dispatch(&*handler, invoke, Some(fault)),
+            error => {
+                let response = error.map(Err);
+                answer_later(
+                    handler.clone(),
+                    invoke,
+                    delay,
+                    response,
+                    FAULT_SOURCE,
+                    Some(fault),
+                );
+                true
+            }
+        }
+    }
+}
+
+/// Answers a call after `delay`, with `response` or by running the command.
+fn answer_later<R, F>(
+    handler: Arc<F>,
+    invoke: Invoke<R>,
+    delay: Duration,
+    response: Option<Result<Value, Value>>,
+    source: &'static str,
+    fault: Option<InjectedFault>,
+) where
+    R: Runtime,
+    F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+{
+    let started = Instant::now();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(delay).await;
+        match response {
+            Some(response) => respond_with(invoke, response, started, source, fault),
+            None => {
+                let resolver = invoke.resolver.clone();
+                let command = invoke.message.command().to_string();
+                if !dispatch(&*handler, invoke, fault) {
+                    resolver.reject(format!("Command {command} not found"));
+                }
+            }
+        }
+    });
+}
+
+/// Runs a call, recording it in the IPC monitor if it is running.
+fn dispatch<R: Runtime, F: Fn(Invoke<R>) -> bool>(
+    handler: &F,
+    invoke: Invoke<R>,
+    fault: Option<InjectedFault>,
+) -> bool {
+    let webview = invoke.message.webview();
+    let monitoring = webview
+        .try_state::<IPCMonitorState>()
+        .and_then(|monitor| monitor.lock().ok().map(|m| m.is_capturing(webview.label())))
+        .unwrap_or(false);
+    if !monitoring {
+        return handler(invoke);
+    }
+
+    let Ok(url) = webview.url() else {
+        return handler(invoke);
+    };
+    let command = invoke.message.command().to_string();
+    let args = body_to_json(invoke.message.payload());
+    let mut headers = invoke.message.headers().clone();
+    headers.insert(CAPTURED_HEADER, HeaderValue::from_static("1"));
+
+    let request = InvokeRequest {
+        cmd: command.clone(),
+        // The re-dispatched call answers through the responder below,
+        // which ignores these callbacks
+        callback: CallbackFn(0),
+        error: CallbackFn(0),
+        url,
+        body: invoke.message.payload().clone(),
+        headers,
+        invoke_key: webview.app_handle().invoke_key().to_string(),
+    };
+
+    let resolver = invoke.resolver;
+    let started = Instant::now();
+    let timestamp = current_timestamp();
+    webview.clone().on_message(
+        request,
+        Box::new(move |webview, _cmd, response, _callback, _error| {
+            let (result, error) = match &response {
+                InvokeResponse::Ok(body) => (Some(response_to_json(body)), None),
+                InvokeResponse::Err(InvokeError(error)) => (None, Some(error_message(error))),
+            };
+            record(
+                &webview,
+                IPCEvent {
+                    timestamp,
+                    command,
+                    args,
+                    result,
+                    error,
+                    duration_ms: Some(started.elapsed().as_secs_f64() * 1000.0),
+                    source: Some(BACKEND_SOURCE.to_string()),
+                    fault,
+                    window: None,
+                },
+            );
+
+            match response {
+                InvokeResponse::Ok(body) => resolver.respond(Ok(body)),
+                InvokeResponse::Err(error) => resolver.respond::<InvokeResponseBody>(Err(error)),
+            }
+        }),
+    );
+    true
+}
+
+/// Answers a call with a fixed response and records it.
+fn respond_with<R: Runtime>(
+    invoke: Invoke<R>,
+    response: Result<Value, Value>,
+    started: Instant,
+    source: &str,
+    fault: Option<InjectedFault>,
+) {
+    let (result, error) = match &response {
+        Ok(result) => (Some(result.clone()), None),
+        Err(error) => (None, Some(error_message(error))),
+    };
+    record(
+        invoke.message.webview_ref(),
+        IPCEvent {
+            timestamp: current_timestamp(),
+            command: invoke.message.command().to_string(),
+            args: body_to_json(invoke.message.payload()),
+            result,
+            error,
+            duration_ms: Some(started.elapsed().as_secs_f64() * 1000.0),
+            source: Some(source.to_string()),
+            fault,
+            window: None,
+        },
+    );
+
+    match response {
+        Ok(result) => invoke.resolver.resolve(result),
+        Err(error) => invoke.resolver.reject(error),
+    }
+}
+
+/// Records a call that is left unanswered.
+fn record_fault<R: Runtime>(invoke: Invoke<R>, fault: InjectedFault) {
+    record(
+        invoke.message.webview_ref(),
+        IPCEvent {
+            timestamp: current_timestamp(),
+            command: invoke.message.command().to_string(),
+            args: body_to_json(invoke.message.payload()),
+            result: None,
+            error: None,
+            duration_ms: None,
+            source: Some(FAULT_SOURCE.to_string()),
+            fault: Some(fault),
+            window: None,
+        },
+    );
+}
+
+/// Invokes a command from `webview` as the frontend would, bypassing mock
+/// and fault rules and the IPC monitor, and returns its response.
+pub(crate) async fn invoke_command<R: Runtime>(
+    webview: &Webview<R>,
+    command: &str,
+    args: Value,
+) -> Result<Value, Value> {
+    let url = webview
+        .url()
+        .map_err(|e| Value::from(format!("Failed to get webview URL: {e}")))?;
+    let mut headers = tauri::http::HeaderMap::new();
+    headers.insert(CAPTURED_HEADER, HeaderValue::from_static("1"));
+    let request = InvokeRequest {
+        cmd: command.to_string(),
+        callback: CallbackFn(0),
+        error: CallbackFn(0),
+        url,
+        body: InvokeBody::Json(args),
+        headers,
+        invoke_key: webview.app_handle().invoke_key().to_string(),
+    };
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    webview.clone().on_message(
+        request,
+        Box::new(move |_webview, _cmd, response, _callback, _error| {
+            let _ = tx.send(match response {
+                InvokeResponse::Ok(body) => Ok(response_to_json(&body)),
+                InvokeResponse::Err(InvokeError(error)) => Err(error),
+            });
+        }),
+    );
+    rx.await
+        .unwrap_or_else(|_| Err(Value::from(format!("Command {command} did not respond"))))
+}
+
+/// Adds an event to the IPC monitor, if it is running.
+fn record<R: Runtime>(webview: &Webview<R>, mut event: IPCEvent) {
+    event.window = Some(webview.label().to_string());
+    if let Some(monitor) = webview.try_state::<IPCMonitorState>() {
+        if let Ok(mut monitor) = monitor.lock() {
+            monitor.add_event(event);
+        }
+    }
+}
+
+/// Converts an invoke payload to JSON, summarizing raw bytes.
+fn body_to_json(body: &InvokeBody) -> Value {
+    match body {
+        InvokeBody::Json(value) => value.clone(),
+        InvokeBody::Raw(bytes) => Value::String(format!("<{} bytes>", bytes.len())),
+    }
+}
+
+/// Converts an invoke response to JSON, summarizing raw bytes.
+fn response_to_json(body: &InvokeResponseBody) -> Value {
+    match body {
+        InvokeResponseBody::Json(json) => {
+            serde_json::from_str(json).unwrap_or_else(|_| Value::String(json.clone()))
+        }
+        InvokeResponseBody::Raw(bytes) => Value::String(format!("<{} bytes>", bytes.len())),
+    }
+}
+
+/// Converts an invoke error to the message recorded in [`IPCEvent::error`].
+pub(crate) fn error_message(error: &Value) -> String {
+    match error {
+        Value::String(message) => message.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc_faults::create_shared_fault_injector;
+    use crate::ipc_mock::create_shared_mock_registry;
+    use crate::monitor::IPCMonitor;
+    use std::sync::{Arc, Mutex};
+    use tauri::test::{get_ipc_response, mock_builder, mock_context, noop_assets, INVOKE_KEY};
+    use tauri::WebviewUrl;
+
+    #[tauri::command]
+    fn greet(name: String) -> String {
+        format!("Hello, {name}!")
+    }
+
+    #[tauri::command]
+    fn fail() -> Result<(), String> {
+        Err("boom".to_string())
+    }
+
+    fn request(cmd: &str, body: Value) -> InvokeRequest {
+        InvokeRequest {
+            cmd: cmd.into(),
+            callback: CallbackFn(0),
+            error: CallbackFn(1),
+            url: "http://tauri.localhost".parse().unwrap(),
+            body: InvokeBody::Json(body),
+            headers: Default::default(),
+            invoke_key: INVOKE_KEY.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_capture_records_backend_calls() {
+        let app = mock_builder()
+            .invoke_handler(capture(tauri::generate_handler![greet, fail]))
+            .build(mock_context(noop_assets()))
+            .unwrap();
+        let monitor: IPCMonitorState = Arc::new(Mutex::new(IPCMonitor::new()));
+        app.manage(monitor.clone());
+        let webview = tauri::WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
+            .build()
+            .unwrap();
+
+        assert!(!is_installed(&app));
+
+        // Not recorded while the monitor is stopped
+        get_ipc_response(
+            &webview,
+            request("greet", serde_json::json!({ "name": "a" })),
+        )
+        .unwrap();
+        monitor.lock().unwrap().start();
+
+        let response = get_ipc_response(
+            &webview,
+            request("greet", serde_json::json!({ "name": "b" })),
+        )
+        .unwrap();
+        assert_eq!(response.deserialize::<String>().unwrap(), "Hello, b!");
+        assert!(get_ipc_response(&webview, request("fail", serde_json::json!({}))).is_err());
+
+        let events = monitor.lock().unwrap().get_events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].command, "greet");
+        assert_eq!(events[0].args["name"], "b");
+        assert_eq!(events[0].result, Some(Value::from("Hello, b!")));
+        assert_eq!(events[0].source.as_deref(), Some(BACKEND_SOURCE));
+        assert!(events[0].duration_ms.is_some());
+        assert_eq!(events[1].error.as_deref(), Some("boom"));
+        assert!(is_installed(&app));
+        assert!(!is_installed(tauri::test::mock_app().handle()));
+    }
+
+    #[test]
+    fn test_mock_rules_bypass_the_command() {
+        let app = mock_builder()
+            .invoke_handler(capture(tauri::generate_handler![greet, fail]))
+            .build(mock_context(noop_assets()))
+            .unwrap();
+        let mocks = create_shared_mock_registry();
+        app.manage(mocks.clone());
+        let webview = tauri::WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
+            .build()
+            .unwrap();
+
+        let add = |rule: Value| {
+            mocks
+                .lock()
+                .unwrap()
+                .add(serde_json::from_value(rule).unwrap())
+                .unwrap()
+        };
+        add(
+            serde_json::json!({ "id": "b", "command": "greet", "args": { "name": "b" }, "result": "mocked" }),
+        );
+        add(
+            serde_json::json!({ "id": "c", "command": "greet", "args": { "name": "c" }, "error": "denied" }),
+        );
+        add(serde_json::json!({ "id": "slow", "command": "greet", "delayMs": 20 }));
+
+        let greet_as = |name: &str| {
+            get_ipc_response(
+                &webview,
+                request("greet", serde_json::json!({ "name": name })),
+            )
+        };
+        assert_eq!(
+            greet_as("b").unwrap().deserialize::<String>().unwrap(),
+            "mocked"
+        );
+        assert_eq!(greet_as("c").unwrap_err(), Value::from("denied"));
+        assert_eq!(
+            greet_as("d").unwrap().deserialize::<String>().unwrap(),
+            "Hello, d!"
+        );
+
+        let hits: Vec<u64> = mocks
+            .lock()
+            .unwrap()
+            .get_all()
+            .iter()
+            .map(|r| r.hits)
+            .collect();
+        assert_eq!(hits, vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_fault_rules_delay_fail_and_hang() {
+        let app = mock_builder()
+            .invoke_handler(capture(tauri::generate_handler![greet, fail]))
+            .build(mock_context(noop_assets()))
+            .unwrap();
+        let monitor: IPCMonitorState = Arc::new(Mutex::new(IPCMonitor::new()));
+        app.manage(monitor.clone());
+        let faults = create_shared_fault_injector();
+        app.manage(faults.clone());
+        let webview = tauri::WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
+            .build()
+            .unwrap();
+        monitor.lock().unwrap().start();
+
+        let set_rule = |rule: Value| {
+            let mut faults = faults.lock().unwrap();
+            faults.clear();
+            faults.add(serde_json::from_value(rule).unwrap()).unwrap();
+        };
+        let greet_request = || request("greet", serde_json::json!({ "name": "a" }));
+
+        set_rule(serde_json::json!({ "id": "slow", "command": "gr*", "latencyMs": 20 }));
+        let started = Instant::now();
+        let response = get_ipc_response(&webview, greet_request()).unwrap();
+        assert_eq!(response.deserialize::<String>().unwrap(), "Hello, a!");
+        assert!(started.elapsed() >= Duration::from_millis(20));
+
+        set_rule(
+            serde_json::json!({ "id": "down", "command": "greet", "errorRate": 1.0, "error": "offline" }),
+        );
+        assert_eq!(
+            get_ipc_response(&webview, greet_request()).unwrap_err(),
+            Value::from("offline")
+        );
+
+        set_rule(serde_json::json!({ "id": "stuck", "command": "greet", "hangRate": 1.0 }));
+        let (tx, rx) = std::sync::mpsc::channel();
+        webview.as_ref().clone().on_message(
+            greet_request(),
+            Box::new(move |_webview, _cmd, response, _callback, _error| {
+                let _ = tx.send(response);
+            }),
+        );
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+        assert_eq!(faults.lock().unwrap().get_all()[0].stats.hung, 1);
+
+        let events = monitor.lock().unwrap().get_events();
+        let faults: Vec<_> = events
+            .iter()
+            .map(|e| {
+                (
+                    e.source.as_deref(),
+                    e.fault.as_ref().map(|f| f.rule.as_str()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            faults,
+            vec![
+                (Some(BACKEND_SOURCE), Some("slow")),
+                (Some(FAULT_SOURCE), Some("down")),
+                (Some(FAULT_SOURCE), Some("stuck")),
+            ]
+        );
+        assert!(events[2].fault.as_ref().unwrap().hang);
+    }
+
+    #[test]
+    fn test_fault_stats_count_with_monitor_stopped() {
+        let app = mock_builder()
+            .invoke_handler(capture(tauri::generate_handler![greet, fail]))
+            .build(mock_context(noop_assets()))
+            .unwrap();
+        let monitor: IPCMonitorState = Arc::new(Mutex::new(IPCMonitor::new()));
+        app.manage(monitor.clone());
+        let faults = create_shared_fault_injector();
+        app.manage(faults.clone());
+        let webview = tauri::WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
+            .build()
+            .unwrap();
+        faults
+            .lock()
+            .unwrap()
+            .add(
+                serde_json::from_value(
+                    serde_json::json!({ "id": "down", "command": "greet", "errorRate": 1.0 }),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        assert!(get_ipc_response(
+            &webview,
+            request("greet", serde_json::json!({ "name": "a" }))
+        )
+        .is_err());
+
+        let stats = faults.lock().unwrap().get_all()[0].stats.clone();
+        assert_eq!((stats.calls, stats.failed), (1, 1));
+        assert!(monitor.lock().unwrap().get_events().is_empty());
+    }
+}
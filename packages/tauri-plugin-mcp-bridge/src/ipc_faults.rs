@@ -0,0 +1,287 @@
+//! Fault injection for IPC calls.
+//!
+//! Fault rules add latency to app commands, fail a share of them with an
+//! error, or leave a share of them unresolved so the frontend's timeout
+//! handling can be exercised. Like [mock rules](crate::ipc_mock), they are
+//! enforced by the [`ipc_capture`](crate::ipc_capture) invoke handler wrapper.
+//! Calls that received a fault carry it in [`IPCEvent::fault`](crate::monitor::IPCEvent).
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+use crate::event_tap::matches_pattern;
+
+/// Error returned by failed calls when a rule does not set one.
+pub const DEFAULT_FAULT_ERROR: &str = "Injected fault";
+
+/// Counters for the faults a rule has injected.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FaultStats {
+    /// Calls the rule matched
+    pub calls: u64,
+    /// Calls that were delayed
+    pub delayed: u64,
+    /// Calls that were failed with the rule's error
+    pub failed: u64,
+    /// Calls that were left unresolved
+    pub hung: u64,
+}
+
+/// The faults one rule has injected, as summarized by `get_ipc_events`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FaultSummary {
+    /// ID of the rule
+    pub rule: String,
+    /// App command the rule affects
+    pub command: String,
+    /// Faults injected so far
+    #[serde(flatten)]
+    pub stats: FaultStats,
+}
+
+/// A fault injection rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FaultRule {
+    /// Unique identifier for this rule
+    pub id: String,
+    /// App command to affect; `*` matches any sequence of characters
+    pub command: String,
+    /// Delay every matching call by this many milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Share of matching calls (0.0 to 1.0) to fail with `error`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_rate: Option<f64>,
+    /// Error to reject failed calls with (defaults to "Injected fault")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+    /// Share of matching calls (0.0 to 1.0) that never resolve
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hang_rate: Option<f64>,
+    /// Faults injected so far
+    #[serde(default)]
+    pub stats: FaultStats,
+}
+
+/// The fault applied to a single call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InjectedFault {
+    /// ID of the rule that injected the fault
+    pub rule: String,
+    /// Latency added before the call was answered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Error the call was failed with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+    /// Whether the call was left unresolved
+    #[serde(default)]
+    pub hang: bool,
+}
+
+impl FaultRule {
+    /// Checks that the rule is well-formed.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.is_empty() || self.command.is_empty() {
+            return Err("Fault rules need an id and a command".to_string());
+        }
+        if self.command.starts_with("plugin:") {
+            return Err(format!(
+                "Cannot inject faults into '{}': only app commands go through the invoke handler",
+                self.command
+            ));
+        }
+        for (name, rate) in [("errorRate", self.error_rate), ("hangRate", self.hang_rate)] {
+            if rate.is_some_and(|rate| !(0.0..=1.0).contains(&rate)) {
+                return Err(format!("{name} must be between 0 and 1"));
+            }
+        }
+        if self.latency_ms.is_none() && self.error_rate.is_none() && self.hang_rate.is_none() {
+            return Err("A fault rule needs latencyMs, errorRate or hangRate".to_string());
+        }
+        Ok(())
+    }
+
+    /// Decides the fault for one call using `roll` to draw numbers in
+    /// `[0, 1)`, and counts it.
+    fn inject(&mut self, mut roll: impl FnMut() -> f64) -> InjectedFault {
+        self.stats.calls += 1;
+        let mut fault = InjectedFault {
+            rule: self.id.clone(),
+            latency_ms: self.latency_ms.filter(|ms| *ms > 0),
+            error: None,
+            hang: false,
+        };
+
+        if self.hang_rate.is_some_and(|rate| roll() < rate) {
+            self.stats.hung += 1;
+            fault.hang = true;
+            fault.latency_ms = None;
+            return fault;
+        }
+        if fault.latency_ms.is_some() {
+            self.stats.delayed += 1;
+        }
+        if self.error_rate.is_some_and(|rate| roll() < rate) {
+            self.stats.failed += 1;
+            fault.error = Some(
+                self.error
+                    .clone()
+                    .unwrap_or_else(|| Value::from(DEFAULT_FAULT_ERROR)),
+            );
+        }
+        fault
+    }
+}
+
+/// Fault rules, checked in the order they were added.
+#[derive(Debug, Default)]
+pub struct FaultInjector {
+    rules: Vec<FaultRule>,
+}
+
+impl FaultInjector {
+    /// Creates an injector without rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule, replacing (in place) any rule with the same ID.
+    pub fn add(&mut self, mut rule: FaultRule) -> Result<(), String> {
+        rule.validate()?;
+        rule.stats = FaultStats::default();
+        match self.rules.iter_mut().find(|r| r.id == rule.id) {
+            Some(existing) => *existing = rule,
+            None => self.rules.push(rule),
+        }
+        Ok(())
+    }
+
+    /// Removes a rule by ID, returning it if it existed.
+    pub fn remove(&mut self, id: &str) -> Option<FaultRule> {
+        let index = self.rules.iter().position(|r| r.id == id)?;
+        Some(self.rules.remove(index))
+    }
+
+    /// Removes all rules, returning how many there were.
+    pub fn clear(&mut self) -> usize {
+        let count = self.rules.len();
+        self.rules.clear();
+        count
+    }
+
+    /// Returns all rules with their statistics.
+    pub fn get_all(&self) -> Vec<FaultRule> {
+        self.rules.clone()
+    }
+
+    /// Returns the faults each rule has injected.
+    pub fn summary(&self) -> Vec<FaultSummary> {
+        self.rules
+            .iter()
+            .map(|rule| FaultSummary {
+                rule: rule.id.clone(),
+                command: rule.command.clone(),
+                stats: rule.stats.clone(),
+            })
+            .collect()
+    }
+
+    /// Decides the fault for a call to `command` from the first matching rule.
+    pub fn inject(&mut self, command: &str) -> Option<InjectedFault> {
+        let rule = self
+            .rules
+            .iter_mut()
+            .find(|r| matches_pattern(&r.command, command))?;
+        Some(rule.inject(fastrand::f64))
+    }
+}
+
+/// Thread-safe wrapper for the fault injector.
+pub type SharedFaultInjector = Arc<Mutex<FaultInjector>>;
+
+/// Creates a new shared fault injector.
+pub fn create_shared_fault_injector() -> SharedFaultInjector {
+    Arc::new(Mutex::new(FaultInjector::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(value: Value) -> FaultRule {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(rule(json!({ "id": "a", "command": "save" }))
+            .validate()
+            .is_err());
+        assert!(
+            rule(json!({ "id": "a", "command": "save", "errorRate": 1.5 }))
+                .validate()
+                .is_err()
+        );
+        assert!(rule(json!({ "id": "a", "command": "*", "latencyMs": 100 }))
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_inject_counts_faults() {
+        let mut fault_rule = rule(json!({
+            "id": "flaky",
+            "command": "save_*",
+            "latencyMs": 50,
+            "errorRate": 0.5,
+            "error": { "code": 503 },
+            "hangRate": 0.1
+        }));
+
+        // Rolls below the hang rate hang the call
+        let fault = fault_rule.inject(|| 0.05);
+        assert!(fault.hang);
+        assert_eq!(fault.latency_ms, None);
+
+        let mut rolls = [0.9, 0.2].into_iter();
+        let fault = fault_rule.inject(|| rolls.next().unwrap());
+        assert_eq!(fault.latency_ms, Some(50));
+        assert_eq!(fault.error, Some(json!({ "code": 503 })));
+
+        let fault = fault_rule.inject(|| 0.9);
+        assert_eq!(fault.error, None);
+
+        assert_eq!(
+            fault_rule.stats,
+            FaultStats {
+                calls: 3,
+                delayed: 2,
+                failed: 1,
+                hung: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_injector_matches_patterns() {
+        let mut injector = FaultInjector::new();
+        injector
+            .add(rule(
+                json!({ "id": "all-saves", "command": "save_*", "errorRate": 1.0 }),
+            ))
+            .unwrap();
+
+        let fault = injector.inject("save_settings").unwrap();
+        assert_eq!(fault.error, Some(Value::from(DEFAULT_FAULT_ERROR)));
+        assert!(injector.inject("load_settings").is_none());
+        assert_eq!(injector.get_all()[0].stats.failed, 1);
+        assert_eq!(injector.clear(), 1);
+    }
+}
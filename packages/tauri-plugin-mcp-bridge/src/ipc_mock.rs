@@ -0,0 +1,228 @@
+//! Mock rules for IPC calls.
+//!
+//! A rule maps an app command, optionally narrowed by an args matcher, to a
+//! fixed result, a fixed error or a delay. Rules are enforced by the
+//! [`ipc_capture`](crate::ipc_capture) invoke handler wrapper, so matching
+//! calls from the frontend never reach the real command (delay-only rules
+//! run it afterwards).
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+use crate::commands::wait_for_event::is_subset;
+
+/// A mock rule for an app command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MockRule {
+    /// Unique identifier for this rule.
+    pub id: String,
+    /// Command to mock, as passed to `invoke` (e.g. "save_settings").
+    pub command: String,
+    /// Only match calls whose args contain these values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<Value>,
+    /// Resolve the call with this value (may be `null`).
+    #[serde(
+        default,
+        deserialize_with = "present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub result: Option<Value>,
+    /// Reject the call with this value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+    /// Wait this long before responding, or before running the real command
+    /// when neither `result` nor `error` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay_ms: Option<u64>,
+    /// Number of calls this rule has matched.
+    #[serde(default)]
+    pub hits: u64,
+}
+
+/// Keeps an explicit `null` as `Some(Value::Null)`.
+fn present<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+impl MockRule {
+    /// Checks that the rule is well-formed.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.is_empty() || self.command.is_empty() {
+            return Err("Mock rules need an id and a command".to_string());
+        }
+        if self.command.starts_with("plugin:") {
+            return Err(format!(
+                "Cannot mock '{}': only app commands go through the invoke handler",
+                self.command
+            ));
+        }
+        match (&self.result, &self.error, self.delay_ms) {
+            (Some(_), Some(_), _) => {
+                Err("A mock rule cannot set both result and error".to_string())
+            }
+            (None, None, None) => {
+                Err("A mock rule needs a result, an error or a delay".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns whether the rule applies to a call.
+    pub fn matches(&self, command: &str, args: &Value) -> bool {
+        self.command == command && self.args.as_ref().map_or(true, |a| is_subset(a, args))
+    }
+
+    /// Returns the mocked response, or `None` if the real command should run.
+    pub fn response(&self) -> Option<Result<Value, Value>> {
+        match (&self.result, &self.error) {
+            (_, Some(error)) => Some(Err(error.clone())),
+            (Some(result), None) => Some(Ok(result.clone())),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Registry of mock rules, checked in the order they were added.
+#[derive(Debug, Default)]
+pub struct MockRegistry {
+    rules: Vec<MockRule>,
+}
+
+impl MockRegistry {
+    /// Creates a new empty mock registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule, replacing (in place) any rule with the same ID.
+    pub fn add(&mut self, mut rule: MockRule) -> Result<(), String> {
+        rule.validate()?;
+        rule.hits = 0;
+        match self.rules.iter_mut().find(|r| r.id == rule.id) {
+            Some(existing) => *existing = rule,
+            None => self.rules.push(rule),
+        }
+        Ok(())
+    }
+
+    /// Removes a rule by ID, returning it if it existed.
+    pub fn remove(&mut self, id: &str) -> Option<MockRule> {
+        let index = self.rules.iter().position(|r| r.id == id)?;
+        Some(self.rules.remove(index))
+    }
+
+    /// Removes all rules, returning how many there were.
+    pub fn clear(&mut self) -> usize {
+        let count = self.rules.len();
+        self.rules.clear();
+        count
+    }
+
+    /// Returns all rules with their hit counts.
+    pub fn get_all(&self) -> Vec<MockRule> {
+        self.rules.clone()
+    }
+
+    /// Finds the first rule matching a call and counts the hit.
+    pub fn take_match(&mut self, command: &str, args: &Value) -> Option<MockRule> {
+        let rule = self.rules.iter_mut().find(|r| r.matches(command, args))?;
+        rule.hits += 1;
+        Some(rule.clone())
+    }
+
+    /// Returns the number of rules in the registry.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns true if the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// Thread-safe wrapper for the mock registry.
+pub type SharedMockRegistry = Arc<Mutex<MockRegistry>>;
+
+/// Creates a new shared mock registry.
+pub fn create_shared_mock_registry() -> SharedMockRegistry {
+    Arc::new(Mutex::new(MockRegistry::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(id: &str, value: Value) -> MockRule {
+        let mut value = value;
+        value["id"] = id.into();
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_null_result_is_kept() {
+        let mock = rule("a", json!({ "command": "save", "result": null }));
+        assert_eq!(mock.response(), Some(Ok(Value::Null)));
+        let delay = rule("b", json!({ "command": "save", "delayMs": 100 }));
+        assert_eq!(delay.response(), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut registry = MockRegistry::new();
+        assert!(registry
+            .add(rule("a", json!({ "command": "save" })))
+            .is_err());
+        assert!(registry
+            .add(rule(
+                "a",
+                json!({ "command": "save", "result": 1, "error": "x" })
+            ))
+            .is_err());
+        assert!(registry
+            .add(rule(
+                "a",
+                json!({ "command": "plugin:fs|read", "result": 1 })
+            ))
+            .is_err());
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn test_first_match_wins_and_counts_hits() {
+        let mut registry = MockRegistry::new();
+        registry
+            .add(rule(
+                "admin",
+                json!({ "command": "login", "args": { "user": "admin" }, "error": "locked" }),
+            ))
+            .unwrap();
+        registry
+            .add(rule("any", json!({ "command": "login", "result": true })))
+            .unwrap();
+
+        let hit = registry
+            .take_match("login", &json!({ "user": "admin", "password": "x" }))
+            .unwrap();
+        assert_eq!(hit.id, "admin");
+        assert_eq!(hit.hits, 1);
+        assert_eq!(
+            registry
+                .take_match("login", &json!({ "user": "bob" }))
+                .unwrap()
+                .id,
+            "any"
+        );
+        assert!(registry.take_match("logout", &json!({})).is_none());
+
+        let hits: Vec<u64> = registry.get_all().iter().map(|r| r.hits).collect();
+        assert_eq!(hits, vec![1, 1]);
+
+        assert!(registry.remove("admin").is_some());
+        assert_eq!(registry.clear(), 1);
+    }
+}
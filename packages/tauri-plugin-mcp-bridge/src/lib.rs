@@ -17,6 +17,7 @@
 //! - **Backend State**: Access application backend state and metadata
 //! - **Event Emission**: Trigger custom events for testing and automation
 //! - **WebSocket Server**: Real-time event streaming on dynamically allocated port
+//!   or a local socket (Unix domain socket / named pipe)
 //!
 //! ## Usage
 //!
@@ -78,25 +79,38 @@
 //! - `allow-start-ipc-monitor` / `deny-start-ipc-monitor`
 //! - `allow-stop-ipc-monitor` / `deny-stop-ipc-monitor`
 
+pub mod backend_logs;
+pub mod client;
 pub mod commands;
 pub mod config;
 pub mod discovery;
+pub mod event_tap;
+pub mod ipc_capture;
+pub mod ipc_faults;
+pub mod ipc_mock;
+pub mod local_socket;
 mod logging;
+pub mod mcp;
+pub mod mdns;
 pub mod monitor;
+mod page_load;
+pub mod redaction;
 pub mod screenshot;
 pub mod script_registry;
+pub mod status;
 pub mod utils;
 pub mod websocket;
+pub mod window_events;
 
-pub use config::{Builder, Config};
+pub use config::{Builder, Config, McpEndpoint, Transport};
+pub use status::BridgeStatus;
 
 use commands::ScriptExecutor;
-use discovery::find_available_port;
 use logging::{mcp_log_error, mcp_log_info};
 use monitor::IPCMonitor;
 use script_registry::create_shared_registry;
 use std::sync::{Arc, Mutex};
-use tauri::{plugin::Builder as PluginBuilder, plugin::TauriPlugin, Manager, Runtime};
+use tauri::{plugin::Builder as PluginBuilder, plugin::TauriPlugin, Manager, RunEvent, Runtime};
 use tokio::sync::broadcast;
 
 /// Initializes the MCP Bridge plugin.
@@ -155,8 +169,27 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
 ///     .expect("error while running tauri application");
 /// ```
 pub fn init_with_config<R: Runtime>(config: Config) -> TauriPlugin<R> {
+    logging::set_max_level(config.log_level);
+
     let bind_address = config.bind_address.clone();
     let base_port = config.base_port;
+    let port_count = config.port_count;
+    let transport = config.transport;
+    let socket_path_override = config.socket_path.clone();
+    let exit_socket_path_override = config.socket_path;
+    let advertise_mdns = config.advertise_mdns;
+    let mcp_endpoint = config.mcp_endpoint;
+    let mcp_http = mcp_endpoint == McpEndpoint::Http;
+    let slow_call_threshold_ms = config.slow_call_threshold_ms;
+    let redaction_rules = config.redaction_rules;
+    let console_redaction_keys = redaction::console_key_patterns(&redaction_rules);
+
+    // Install log capture first so records from plugin setup are kept
+    if let Some(level) = config.backend_log_level {
+        if let Err(e) = backend_logs::install(level) {
+            mcp_log_error("BACKEND_LOGS", &e);
+        }
+    }
 
     PluginBuilder::<R>::new("mcp-bridge")
         .invoke_handler(tauri::generate_handler![
@@ -174,21 +207,42 @@ pub fn init_with_config<R: Runtime>(config: Config) -> TauriPlugin<R> {
             commands::list_windows::list_windows,
             commands::script_injection::request_script_injection,
         ])
-        .js_init_script(include_str!("bridge.js").to_string())
+        .js_init_script(format!(
+            "{}\n{}",
+            redaction::console_patterns_script(&console_redaction_keys),
+            include_str!("bridge.js")
+        ))
         .setup(move |app, _api| {
             // Initialize script executor state
             app.manage(ScriptExecutor::new());
 
             // Initialize IPC monitor state
-            let monitor = Arc::new(Mutex::new(IPCMonitor::new()));
+            let mut ipc_monitor = IPCMonitor::new();
+            for rule in redaction_rules {
+                if let Err(e) = ipc_monitor.add_redaction_rule(rule) {
+                    mcp_log_error("REDACTION", &format!("Ignoring redaction rule: {e}"));
+                }
+            }
+            let monitor = Arc::new(Mutex::new(ipc_monitor));
             app.manage(monitor.clone());
 
             // Initialize script registry for persistent script injection
             let script_registry = create_shared_registry();
             app.manage(script_registry);
 
-            // Find an available port for WebSocket server
-            let port = find_available_port(&bind_address, base_port);
+            // Initialize mock rules for IPC calls, enforced by ipc_capture
+            app.manage(ipc_mock::create_shared_mock_registry());
+
+            // Initialize fault injection rules for IPC calls, also enforced by ipc_capture
+            app.manage(ipc_faults::create_shared_fault_injector());
+
+            // Initialize event tap state for capturing Tauri events
+            app.manage::<event_tap::EventTapState>(Arc::new(
+                Mutex::new(event_tap::EventTap::new()),
+            ));
+
+            // Track page-ready reports from bridge.js for navigation commands
+            app.manage(Arc::new(page_load::PageLoadTracker::new()));
 
             // Log app information for debugging
             let app_name = app
@@ -202,28 +256,207 @@ pub fn init_with_config<R: Runtime>(config: Config) -> TauriPlugin<R> {
             // Create broadcast channel externally so it can be shared with
             // the element picker event forwarder
             let (event_tx, _event_rx) = broadcast::channel::<String>(100);
+            app.manage(window_events::BroadcastSender(event_tx.clone()));
+
+            // Stream backend log records when a client asks for them
+            if let Ok(mut buffer) = backend_logs::log_buffer().lock() {
+                buffer.set_sender(event_tx.clone());
+            }
+
+            // Broadcast IPC calls slower than the configured threshold
+            if let Ok(mut monitor) = monitor.lock() {
+                monitor.set_alert_sender(event_tx.clone());
+                monitor.set_slow_threshold(slow_call_threshold_ms.map(|ms| ms as f64));
+            }
 
             // Set up element picker event listeners (forwards Tauri events to WS broadcast)
             commands::element_picker::setup_element_picker_listeners(app, event_tx.clone());
 
-            // Start WebSocket server in background
-            let app_handle = app.clone();
-            let ws_server =
-                websocket::WebSocketServer::new(port, &bind_address, app_handle, event_tx);
+            // Start stdio MCP first so later log output is kept off stdout
+            if mcp_endpoint == McpEndpoint::Stdio {
+                mcp::stdio::spawn(app.clone());
+            }
+
+            let mut status = BridgeStatus::default();
 
-            tauri::async_runtime::spawn(async move {
-                if let Err(e) = ws_server.start().await {
-                    mcp_log_error("PLUGIN", &format!("WebSocket server error: {e}"));
+            // Bind listeners before setup returns so failures are reported
+            // here rather than from inside a background task
+            let _runtime = tauri::async_runtime::handle().inner().enter();
+
+            if transport.uses_tcp() {
+                match discovery::bind_available_port(&bind_address, base_port, port_count)
+                    .map_err(|e| e.to_string())
+                    .and_then(|listener| {
+                        websocket::WebSocketServer::from_tcp_listener(
+                            listener,
+                            app.clone(),
+                            event_tx.clone(),
+                        )
+                        .map(|server| server.mcp_http(mcp_http))
+                        .map_err(|e| format!("Failed to start WebSocket server: {e}"))
+                    }) {
+                    Ok(ws_server) => {
+                        if let websocket::Endpoint::Tcp(addr) = ws_server.endpoint() {
+                            status.tcp_address = Some(*addr);
+                            if advertise_mdns {
+                                start_mdns_advertiser(app, &bind_address, addr.port());
+                            }
+                        }
+                        spawn_server(ws_server);
+                    }
+                    Err(e) => status.errors.push(e),
                 }
-            });
+            }
 
-            mcp_log_info(
-                "PLUGIN",
-                &format!(
-                    "MCP Bridge plugin initialized for '{app_name}' ({identifier}) on {bind_address}:{port}"
-                ),
-            );
+            if transport.uses_local_socket() {
+                let path = socket_path_override
+                    .clone()
+                    .unwrap_or_else(|| local_socket::socket_path(&identifier, std::process::id()));
+                let mut ws_server =
+                    websocket::WebSocketServer::new_local(path.clone(), app.clone(), event_tx)
+                        .mcp_http(mcp_http);
+                match ws_server.bind() {
+                    Ok(()) => {
+                        status.socket_path = Some(path);
+                        spawn_server(ws_server);
+                    }
+                    Err(e) => status.errors.push(format!(
+                        "Failed to bind local socket {}: {e}",
+                        path.display()
+                    )),
+                }
+            }
+
+            for error in &status.errors {
+                mcp_log_error("PLUGIN", &format!("MCP Bridge is not listening: {error}"));
+            }
+
+            if status.is_listening() {
+                // Publish a discovery record so clients can find this instance
+                // without scanning ports
+                let instances_dir = discovery::instances_dir();
+                discovery::prune_stale_instances(&instances_dir);
+                let record = discovery::InstanceRecord {
+                    identifier: identifier.clone(),
+                    product_name: app_name.clone(),
+                    pid: std::process::id(),
+                    cwd: std::env::current_dir()
+                        .ok()
+                        .map(|p| p.display().to_string()),
+                    port: status.port(),
+                    socket_path: status.socket_path.clone(),
+                    protocol_version: websocket::PROTOCOL_VERSION,
+                    started_at: monitor::current_timestamp(),
+                };
+                if let Err(e) = record.write(&instances_dir) {
+                    mcp_log_error(
+                        "PLUGIN",
+                        &format!(
+                            "Failed to write discovery record to {}: {e}",
+                            instances_dir.display()
+                        ),
+                    );
+                }
+
+                let endpoints: Vec<String> = status
+                    .tcp_address
+                    .iter()
+                    .map(ToString::to_string)
+                    .chain(status.socket_path.iter().map(|p| p.display().to_string()))
+                    .collect();
+                mcp_log_info(
+                    "PLUGIN",
+                    &format!(
+                        "MCP Bridge plugin initialized for '{app_name}' ({identifier}) on {}",
+                        endpoints.join(", ")
+                    ),
+                );
+            }
+
+            app.manage(status);
             Ok(())
         })
+        .on_window_ready(|window| {
+            window_events::publish(
+                &window,
+                window.label(),
+                window_events::WindowEventKind::Created,
+            );
+        })
+        .on_event(move |app, event| {
+            if let RunEvent::WindowEvent { label, event, .. } = event {
+                if let Some(kind) = window_events::WindowEventKind::from_tauri(event) {
+                    window_events::publish(app, label, kind);
+                }
+            }
+
+            if let RunEvent::Exit = event {
+                if let Some(advertiser) = app.try_state::<mdns::Advertiser>() {
+                    advertiser.shutdown();
+                }
+
+                discovery::remove_instance_record(
+                    &discovery::instances_dir(),
+                    &app.config().identifier,
+                    std::process::id(),
+                );
+
+                if transport.uses_local_socket() {
+                    let path = exit_socket_path_override.clone().unwrap_or_else(|| {
+                        local_socket::socket_path(&app.config().identifier, std::process::id())
+                    });
+                    local_socket::cleanup(&path);
+                }
+            }
+        })
         .build()
 }
+
+/// Advertises the TCP endpoint via DNS-SD and keeps the advertiser in managed
+/// state until the app exits.
+fn start_mdns_advertiser<R: Runtime>(app: &tauri::AppHandle<R>, bind_address: &str, port: u16) {
+    let Ok(bind_address) = bind_address.parse() else {
+        mcp_log_error(
+            "MDNS",
+            &format!("Cannot advertise: invalid bind address '{bind_address}'"),
+        );
+        return;
+    };
+
+    let config = app.config();
+    let service = mdns::ServiceDescription {
+        identifier: config.identifier.clone(),
+        product_name: config
+            .product_name
+            .clone()
+            .unwrap_or_else(|| "Tauri App".to_string()),
+        version: config
+            .version
+            .clone()
+            .unwrap_or_else(|| "0.0.0".to_string()),
+        protocol_version: websocket::PROTOCOL_VERSION,
+        pid: std::process::id(),
+        bind_address,
+        port,
+    };
+
+    match mdns::Advertiser::start(&service) {
+        Ok(advertiser) => {
+            mcp_log_info(
+                "MDNS",
+                &format!("Advertising {} on port {port}", advertiser.fullname()),
+            );
+            app.manage(advertiser);
+        }
+        Err(e) => mcp_log_error("MDNS", &format!("Failed to start mDNS advertiser: {e}")),
+    }
+}
+
+/// Runs a WebSocket server in the background, logging if it stops with an error.
+fn spawn_server<R: Runtime>(ws_server: websocket::WebSocketServer<R>) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = ws_server.start().await {
+            mcp_log_error("PLUGIN", &format!("WebSocket server error: {e}"));
+        }
+    });
+}
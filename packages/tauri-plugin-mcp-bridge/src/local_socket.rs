@@ -0,0 +1,321 @@
+//! Local socket transport for the WebSocket server.
+//!
+//! This module lets the bridge accept WebSocket connections over a Unix domain
+//! socket (Linux/macOS) or a named pipe (Windows) instead of, or in addition to,
+//! a TCP port. Local sockets are only reachable from the same machine and never
+//! collide with ports used by other tools.
+//!
+//! The socket path is derived from the app's bundle identifier and the process
+//! ID, so clients can find a specific instance without scanning ports.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Maximum number of identifier characters kept in the socket file name.
+///
+/// Unix socket paths are limited to roughly 104 bytes on macOS and 108 on
+/// Linux, and macOS temp directories are already long.
+const MAX_IDENTIFIER_LEN: usize = 48;
+
+/// Returns the default local socket path for an app instance.
+///
+/// # Arguments
+///
+/// * `identifier` - The app's bundle identifier (e.g., "com.example.app")
+/// * `pid` - The process ID of the running instance
+///
+/// # Returns
+///
+/// * Unix: `$XDG_RUNTIME_DIR/tauri-mcp-{identifier}-{pid}.sock`, falling back to
+///   the system temp directory when `XDG_RUNTIME_DIR` is not set
+/// * Windows: `\\.\pipe\tauri-mcp-{identifier}-{pid}`
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri_plugin_mcp_bridge::local_socket::socket_path;
+///
+/// let path = socket_path("com.example.app", 4242);
+/// assert!(path.to_string_lossy().contains("tauri-mcp-com.example.app-4242"));
+/// ```
+pub fn socket_path(identifier: &str, pid: u32) -> PathBuf {
+    let name = format!("tauri-mcp-{}-{pid}", sanitize_identifier(identifier));
+
+    #[cfg(windows)]
+    {
+        PathBuf::from(format!(r"\\.\pipe\{name}"))
+    }
+
+    #[cfg(not(windows))]
+    {
+        runtime_dir().join(format!("{name}.sock"))
+    }
+}
+
+/// Returns the per-user directory used for runtime files such as sockets.
+#[cfg(not(windows))]
+fn runtime_dir() -> PathBuf {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Replaces characters that are not safe in file or pipe names and truncates
+/// the result to [`MAX_IDENTIFIER_LEN`].
+fn sanitize_identifier(identifier: &str) -> String {
+    let sanitized: String = identifier
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_IDENTIFIER_LEN)
+        .collect();
+
+    if sanitized.is_empty() {
+        "app".to_string()
+    } else {
+        sanitized
+    }
+}
+
+/// Removes a socket file left behind by this instance.
+///
+/// Named pipes on Windows are released by the OS when the last handle closes,
+/// so this is a no-op there.
+pub fn cleanup(path: &Path) {
+    #[cfg(unix)]
+    {
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+    }
+}
+
+/// A stream accepted from a [`LocalListener`].
+#[cfg(unix)]
+pub type LocalStream = tokio::net::UnixStream;
+
+/// A stream accepted from a [`LocalListener`].
+#[cfg(windows)]
+pub type LocalStream = tokio::net::windows::named_pipe::NamedPipeServer;
+
+/// Removes the socket at `path` if no process is listening on it.
+#[cfg(unix)]
+fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    let metadata = match std::fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+
+    let stale = metadata.file_type().is_socket()
+        && matches!(
+            std::os::unix::net::UnixStream::connect(path),
+            Err(e) if e.kind() == io::ErrorKind::ConnectionRefused
+        );
+    if !stale {
+        return Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            format!("{} is in use", path.display()),
+        ));
+    }
+
+    std::fs::remove_file(path)
+}
+
+/// Listener that accepts connections on a local socket.
+#[cfg(unix)]
+pub struct LocalListener {
+    inner: tokio::net::UnixListener,
+}
+
+#[cfg(unix)]
+impl LocalListener {
+    /// Binds a Unix domain socket at `path`.
+    ///
+    /// A stale socket at the same path (e.g. from a crashed process whose PID
+    /// was reused) is removed first. Anything else at the path, including a
+    /// socket another process is still listening on, fails with
+    /// [`io::ErrorKind::AddrInUse`].
+    ///
+    /// The socket is bound in a private directory and only moved to `path`
+    /// once its permissions are restricted to the current user, so it is never
+    /// reachable by other users.
+    pub fn bind(path: &Path) -> io::Result<Self> {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+        remove_stale_socket(path)?;
+
+        let parent = path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let private_dir = parent.join(format!(".tauri-mcp-{:016x}", fastrand::u64(..)));
+        std::fs::DirBuilder::new()
+            .mode(0o700)
+            .create(&private_dir)?;
+
+        let staged = private_dir.join("bridge.sock");
+        let result = (|| {
+            let inner = tokio::net::UnixListener::bind(&staged)?;
+            // Restrict access to the current user
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))?;
+            std::fs::rename(&staged, path)?;
+            Ok(Self { inner })
+        })();
+
+        let _ = std::fs::remove_file(&staged);
+        let _ = std::fs::remove_dir(&private_dir);
+        result
+    }
+
+    /// Waits for the next client connection.
+    pub async fn accept(&mut self) -> io::Result<LocalStream> {
+        let (stream, _) = self.inner.accept().await?;
+        Ok(stream)
+    }
+}
+
+/// Listener that accepts connections on a named pipe.
+#[cfg(windows)]
+pub struct LocalListener {
+    name: std::ffi::OsString,
+    next: tokio::net::windows::named_pipe::NamedPipeServer,
+}
+
+#[cfg(windows)]
+impl LocalListener {
+    /// Creates the first instance of the named pipe at `path`.
+    pub fn bind(path: &Path) -> io::Result<Self> {
+        use tokio::net::windows::named_pipe::ServerOptions;
+
+        let name = path.as_os_str().to_os_string();
+        let next = ServerOptions::new()
+            .first_pipe_instance(true)
+            .create(&name)?;
+
+        Ok(Self { name, next })
+    }
+
+    /// Waits for the next client connection.
+    ///
+    /// Each named pipe instance serves a single client, so a fresh instance is
+    /// created for the following connection before this one is handed out.
+    pub async fn accept(&mut self) -> io::Result<LocalStream> {
+        use tokio::net::windows::named_pipe::ServerOptions;
+
+        self.next.connect().await?;
+        let next = ServerOptions::new().create(&self.name)?;
+        Ok(std::mem::replace(&mut self.next, next))
+    }
+}
+
+/// A client connection to a [`LocalListener`].
+#[cfg(unix)]
+pub type LocalClientStream = tokio::net::UnixStream;
+
+/// A client connection to a [`LocalListener`].
+#[cfg(windows)]
+pub type LocalClientStream = tokio::net::windows::named_pipe::NamedPipeClient;
+
+/// Connects to a bridge listening on a local socket.
+#[cfg(unix)]
+pub async fn connect(path: &Path) -> io::Result<LocalClientStream> {
+    tokio::net::UnixStream::connect(path).await
+}
+
+/// Connects to a bridge listening on a named pipe.
+///
+/// All pipe instances can be busy while the server is creating the next one,
+/// so this retries for a short while before giving up.
+#[cfg(windows)]
+pub async fn connect(path: &Path) -> io::Result<LocalClientStream> {
+    use tokio::net::windows::named_pipe::ClientOptions;
+    use windows::Win32::Foundation::ERROR_PIPE_BUSY;
+
+    let mut attempts = 0;
+    loop {
+        match ClientOptions::new().open(path) {
+            Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY.0 as i32) && attempts < 50 => {
+                attempts += 1;
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_socket_path_contains_identifier_and_pid() {
+        let path = socket_path("com.example.app", 1234);
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("tauri-mcp-com.example.app-1234"));
+    }
+
+    #[test]
+    fn test_socket_path_sanitizes_identifier() {
+        let path = socket_path("com/example app", 1);
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("tauri-mcp-com_example_app-1"));
+    }
+
+    #[test]
+    fn test_sanitize_identifier_truncates() {
+        let long = "a".repeat(200);
+        assert_eq!(sanitize_identifier(&long).len(), MAX_IDENTIFIER_LEN);
+        assert_eq!(sanitize_identifier(""), "app");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_bind_replaces_stale_socket() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path =
+            std::env::temp_dir().join(format!("tauri-mcp-test-stale-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        let listener = LocalListener::bind(&path).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(connect(&path).await.is_ok());
+
+        // A live socket is left alone
+        let err = LocalListener::bind(&path).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::AddrInUse);
+
+        drop(listener);
+        cleanup(&path);
+        assert!(!path.exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_bind_keeps_regular_file() {
+        let path =
+            std::env::temp_dir().join(format!("tauri-mcp-test-file-{}.sock", std::process::id()));
+        std::fs::write(&path, b"not a socket").unwrap();
+
+        let err = LocalListener::bind(&path).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::AddrInUse);
+        assert_eq!(std::fs::read(&path).unwrap(), b"not a socket");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,7 +1,73 @@
+//! Plugin diagnostics, routed through the `log` facade.
+//!
+//! Records use targets of the form `tauri_plugin_mcp_bridge::<scope>` (e.g.
+//! `tauri_plugin_mcp_bridge::ws_server`), so the app's logger decides where
+//! they go and can filter them per scope. Records more verbose than the level
+//! set with [`Builder::log_level`](crate::Builder::log_level) are dropped.
+
+use log::{Level, LevelFilter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Most verbose level the plugin logs at, as a `LevelFilter` discriminant.
+static MAX_LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Info as usize);
+
+/// Sets the most verbose level the plugin logs at.
+pub fn set_max_level(level: LevelFilter) {
+    MAX_LEVEL.store(level as usize, Ordering::Relaxed);
+}
+
+/// Returns whether records at `level` pass the plugin's level.
+fn enabled(level: Level) -> bool {
+    level as usize <= MAX_LEVEL.load(Ordering::Relaxed)
+}
+
+/// Returns the `log` target for a scope, e.g. `tauri_plugin_mcp_bridge::ws_server`.
+fn target(scope: &str) -> String {
+    format!("tauri_plugin_mcp_bridge::{}", scope.to_lowercase())
+}
+
+fn mcp_log(level: Level, scope: &str, msg: &str) {
+    if !enabled(level) {
+        return;
+    }
+    log::log!(target: &target(scope), level, "{msg}");
+}
+
+pub fn mcp_log_debug(scope: &str, msg: &str) {
+    mcp_log(Level::Debug, scope, msg);
+}
+
 pub fn mcp_log_info(scope: &str, msg: &str) {
-    println!("[MCP][{scope}][INFO] {msg}");
+    mcp_log(Level::Info, scope, msg);
+}
+
+pub fn mcp_log_warn(scope: &str, msg: &str) {
+    mcp_log(Level::Warn, scope, msg);
 }
 
 pub fn mcp_log_error(scope: &str, msg: &str) {
-    eprintln!("[MCP][{scope}][ERROR] {msg}");
+    mcp_log(Level::Error, scope, msg);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_level_gates_records() {
+        set_max_level(LevelFilter::Warn);
+        assert!(enabled(Level::Error));
+        assert!(enabled(Level::Warn));
+        assert!(!enabled(Level::Info));
+
+        set_max_level(LevelFilter::Off);
+        assert!(!enabled(Level::Error));
+
+        set_max_level(LevelFilter::Debug);
+        assert!(enabled(Level::Debug));
+        assert!(!enabled(Level::Trace));
+
+        set_max_level(LevelFilter::Info);
+        assert_eq!(target("WS_SERVER"), "tauri_plugin_mcp_bridge::ws_server");
+    }
 }
@@ -0,0 +1,342 @@
+//! Streamable HTTP transport for the MCP endpoint.
+//!
+//! The endpoint shares the bridge's listener: the first request on each
+//! connection is inspected, WebSocket upgrades are handed to the WebSocket
+//! server unchanged, and `POST` requests to [`MCP_PATH`] are answered with a
+//! single JSON-RPC response. The server does not open SSE streams, so `GET`
+//! requests get `405 Method Not Allowed` as the MCP specification allows.
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+
+/// Path the MCP endpoint is served on.
+pub const MCP_PATH: &str = "/mcp";
+
+/// Maximum size of a request head.
+const MAX_HEAD_LEN: usize = 16 * 1024;
+
+/// Maximum size of a request body.
+const MAX_BODY_LEN: usize = 8 * 1024 * 1024;
+
+/// How long a client may take to send its request head.
+const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Reads from a stream until the end of the HTTP request head.
+///
+/// Returns every byte read, which may include the start of the body. An empty
+/// buffer means the client disconnected before sending anything.
+pub(crate) async fn read_head<S: AsyncRead + Unpin>(stream: &mut S) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+
+    tokio::time::timeout(HEAD_TIMEOUT, async {
+        while find_head_end(&buf).is_none() {
+            if buf.len() > MAX_HEAD_LEN {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Request head too large",
+                ));
+            }
+            let n = stream.read(&mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        Ok(buf)
+    })
+    .await
+    .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "Timed out reading request head"))?
+}
+
+/// Returns the length of the request head, including the blank line.
+fn find_head_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|w| w == b"\r\n\r\n").map(|i| i + 4)
+}
+
+/// Returns true if the request head asks for a WebSocket upgrade.
+pub(crate) fn is_websocket_upgrade(head: &[u8]) -> bool {
+    let mut headers = [httparse::EMPTY_HEADER; 64];
+    let mut request = httparse::Request::new(&mut headers);
+    if request.parse(head).is_err() {
+        // Let the WebSocket handshake report malformed requests
+        return true;
+    }
+
+    request.headers.iter().any(|h| {
+        h.name.eq_ignore_ascii_case("upgrade")
+            && String::from_utf8_lossy(h.value).eq_ignore_ascii_case("websocket")
+    })
+}
+
+/// Serves a single MCP HTTP request whose head has already been read.
+pub(crate) async fn serve<R, S>(app: &AppHandle<R>, head: Vec<u8>, mut stream: S) -> io::Result<()>
+where
+    R: Runtime,
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (status, body) = match handle_request(app, &head, &mut stream).await {
+        Ok(Some(body)) => (200, Some(body)),
+        Ok(None) => (202, None),
+        Err((status, message)) => (status, Some(message)),
+    };
+
+    let mut response = format!(
+        "HTTP/1.1 {status} {}\r\nConnection: close\r\n",
+        reason_phrase(status)
+    );
+    if status == 405 {
+        response.push_str("Allow: POST\r\n");
+    }
+    let body = body.unwrap_or_default();
+    if !body.is_empty() {
+        response.push_str("Content-Type: application/json\r\n");
+    }
+    response.push_str(&format!("Content-Length: {}\r\n\r\n", body.len()));
+    response.push_str(&body);
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Parses and runs a request, returning the JSON-RPC response body, `None`
+/// for an accepted notification, or an HTTP status and error body.
+async fn handle_request<R, S>(
+    app: &AppHandle<R>,
+    head: &[u8],
+    stream: &mut S,
+) -> Result<Option<String>, (u16, String)>
+where
+    R: Runtime,
+    S: AsyncRead + Unpin,
+{
+    let mut headers = [httparse::EMPTY_HEADER; 64];
+    let mut request = httparse::Request::new(&mut headers);
+    let head_len = match request.parse(head) {
+        Ok(httparse::Status::Complete(len)) => len,
+        _ => return Err((400, error_body("Malformed HTTP request"))),
+    };
+
+    let path = request.path.unwrap_or("/");
+    if path.split('?').next() != Some(MCP_PATH) {
+        return Err((404, error_body("Not found")));
+    }
+
+    let header = |name: &str| {
+        request
+            .headers
+            .iter()
+            .find(|h| h.name.eq_ignore_ascii_case(name))
+            .map(|h| String::from_utf8_lossy(h.value).to_string())
+    };
+
+    // Reject cross-origin browser requests to guard against DNS rebinding
+    if let Some(origin) = header("origin") {
+        if !is_local_origin(&origin) {
+            return Err((403, error_body("Origin not allowed")));
+        }
+    }
+
+    if request.method != Some("POST") {
+        return Err((405, error_body("Only POST is supported")));
+    }
+
+    let Some(content_length) = header("content-length").and_then(|v| v.trim().parse().ok()) else {
+        return Err((411, error_body("Content-Length required")));
+    };
+    if content_length > MAX_BODY_LEN {
+        return Err((413, error_body("Request body too large")));
+    }
+
+    let mut body = head[head_len..].to_vec();
+    body.truncate(content_length);
+    if body.len() < content_length {
+        let mut rest = vec![0u8; content_length - body.len()];
+        stream
+            .read_exact(&mut rest)
+            .await
+            .map_err(|e| (400, error_body(&format!("Failed to read body: {e}"))))?;
+        body.extend_from_slice(&rest);
+    }
+
+    let payload = match serde_json::from_slice(&body) {
+        Ok(payload) => payload,
+        Err(e) => return Ok(Some(super::parse_error_response(e).to_string())),
+    };
+
+    Ok(super::handle_payload(app, payload)
+        .await
+        .map(|response| response.to_string()))
+}
+
+/// Returns true for `Origin` values pointing at this machine.
+fn is_local_origin(origin: &str) -> bool {
+    let host = origin
+        .split_once("://")
+        .map_or(origin, |(_, rest)| rest)
+        .split('/')
+        .next()
+        .unwrap_or("");
+    let host = match host.rsplit_once(':') {
+        Some((h, port)) if !h.is_empty() && port.chars().all(|c| c.is_ascii_digit()) => h,
+        _ => host,
+    };
+
+    matches!(
+        host,
+        "localhost" | "127.0.0.1" | "[::1]" | "tauri.localhost"
+    ) || host.ends_with(".localhost")
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        _ => "Error",
+    }
+}
+
+/// A stream that replays already-read bytes before reading from the inner
+/// stream, so a sniffed request can still be handed to the WebSocket server.
+pub(crate) struct Rewind<S> {
+    prefix: Vec<u8>,
+    pos: usize,
+    inner: S,
+}
+
+impl<S> Rewind<S> {
+    pub(crate) fn new(prefix: Vec<u8>, inner: S) -> Self {
+        Self {
+            prefix,
+            pos: 0,
+            inner,
+        }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for Rewind<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if self.pos < self.prefix.len() {
+            let n = buf.remaining().min(self.prefix.len() - self.pos);
+            let start = self.pos;
+            buf.put_slice(&self.prefix[start..start + n]);
+            self.pos += n;
+            return Poll::Ready(Ok(()));
+        }
+
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for Rewind<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::BridgeClient;
+    use crate::script_registry::create_shared_registry;
+    use crate::websocket::{Endpoint, WebSocketServer};
+    use tauri::Manager;
+    use tokio::sync::broadcast;
+
+    #[test]
+    fn test_is_local_origin() {
+        assert!(is_local_origin("http://localhost:1420"));
+        assert!(is_local_origin("http://127.0.0.1"));
+        assert!(is_local_origin("tauri://localhost"));
+        assert!(is_local_origin("http://tauri.localhost"));
+        assert!(!is_local_origin("https://evil.example"));
+        assert!(!is_local_origin("http://localhost.evil.example"));
+    }
+
+    #[test]
+    fn test_is_websocket_upgrade() {
+        let upgrade =
+            b"GET / HTTP/1.1\r\nHost: x\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\r\n";
+        assert!(is_websocket_upgrade(upgrade));
+        let post = b"POST /mcp HTTP/1.1\r\nHost: x\r\nContent-Length: 2\r\n\r\n{}";
+        assert!(!is_websocket_upgrade(post));
+    }
+
+    #[tokio::test]
+    async fn test_mcp_and_websocket_share_listener() {
+        let app = tauri::test::mock_app();
+        app.manage(create_shared_registry());
+
+        let (event_tx, _) = broadcast::channel::<String>(16);
+        let mut server =
+            WebSocketServer::new(0, "127.0.0.1", app.handle().clone(), event_tx).mcp_http(true);
+        server.bind().unwrap();
+        let Endpoint::Tcp(addr) = server.endpoint().clone() else {
+            panic!("expected a TCP endpoint");
+        };
+        tokio::spawn(async move {
+            let _ = server.start().await;
+        });
+
+        let body = r#"{"jsonrpc":"2.0","id":7,"method":"ping"}"#;
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "POST /mcp HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\n\
+             Accept: application/json, text/event-stream\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["id"], 7);
+        assert_eq!(body["result"], serde_json::json!({}));
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET /mcp HTTP/1.1\r\nHost: {addr}\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 405"));
+
+        let client = BridgeClient::connect(&format!("ws://{addr}"))
+            .await
+            .unwrap();
+        assert!(client.get_scripts().await.unwrap().is_empty());
+    }
+}
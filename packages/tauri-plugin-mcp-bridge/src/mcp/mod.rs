@@ -0,0 +1,1013 @@
+//! Built-in MCP (Model Context Protocol) endpoint.
+//!
+//! When enabled with [`Builder::mcp_endpoint`](crate::Builder::mcp_endpoint),
+//! MCP clients can attach to the app directly, without the Node MCP server:
+//!
+//! - [`McpEndpoint::Http`](crate::McpEndpoint::Http) serves Streamable HTTP on
+//!   the bridge's existing listener at [`http::MCP_PATH`]
+//! - [`McpEndpoint::Stdio`](crate::McpEndpoint::Stdio) speaks newline-delimited
+//!   JSON-RPC on the process's stdin/stdout, for sidecar mode
+//!
+//! Tools and resources are thin wrappers that build a bridge command and run
+//! it through the same dispatcher as the WebSocket server.
+
+pub mod http;
+pub mod stdio;
+
+use crate::websocket::dispatch_command;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Runtime};
+
+/// The newest MCP protocol revision supported.
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Protocol revisions accepted during initialization, newest first.
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &[PROTOCOL_VERSION, "2025-03-26", "2024-11-05"];
+
+/// JSON-RPC error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+/// A JSON-RPC error.
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Builds the JSON-RPC response for a message that was not valid JSON.
+pub fn parse_error_response(error: impl std::fmt::Display) -> Value {
+    error_response(
+        Value::Null,
+        RpcError::new(PARSE_ERROR, format!("Parse error: {error}")),
+    )
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message }
+    })
+}
+
+/// Handles a JSON-RPC message or batch.
+///
+/// # Returns
+///
+/// * `Some(Value)` - The response (or batch of responses) to send back
+/// * `None` - If the payload only contained notifications or responses
+pub async fn handle_payload<R: Runtime>(app: &AppHandle<R>, payload: Value) -> Option<Value> {
+    match payload {
+        Value::Array(messages) => {
+            if messages.is_empty() {
+                return Some(error_response(
+                    Value::Null,
+                    RpcError::new(INVALID_REQUEST, "Empty batch"),
+                ));
+            }
+
+            let mut responses = Vec::new();
+            for message in messages {
+                if let Some(response) = handle_message(app, message).await {
+                    responses.push(response);
+                }
+            }
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        message => handle_message(app, message).await,
+    }
+}
+
+/// Handles a single JSON-RPC message.
+async fn handle_message<R: Runtime>(app: &AppHandle<R>, message: Value) -> Option<Value> {
+    let id = message.get("id").cloned();
+    let method = message.get("method").and_then(|v| v.as_str());
+
+    let (Some(id), Some(method)) = (id.clone(), method) else {
+        // Notifications and client responses need no reply; anything else
+        // without a method is malformed
+        return match (id, method) {
+            (Some(id), None)
+                if message.get("result").is_none() && message.get("error").is_none() =>
+            {
+                Some(error_response(
+                    id,
+                    RpcError::new(INVALID_REQUEST, "Missing method"),
+                ))
+            }
+            _ => None,
+        };
+    };
+
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    let result = match method {
+        "initialize" => Ok(initialize(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(app, &params).await,
+        "resources/list" => Ok(json!({ "resources": resource_definitions() })),
+        "resources/read" => read_resource(app, &params).await,
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Method not found: {method}"),
+        )),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => error_response(id, error),
+    })
+}
+
+/// Negotiates the protocol version and describes the server.
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(|v| v.as_str());
+    let version = requested
+        .filter(|v| SUPPORTED_PROTOCOL_VERSIONS.contains(v))
+        .unwrap_or(PROTOCOL_VERSION);
+
+    json!({
+        "protocolVersion": version,
+        "capabilities": {
+            "tools": {},
+            "resources": {}
+        },
+        "serverInfo": {
+            "name": "tauri-plugin-mcp-bridge",
+            "version": env!("CARGO_PKG_VERSION")
+        },
+        "instructions": "Tools operate on the Tauri app hosting this endpoint. \
+            Window-scoped tools default to the 'main' window; pass windowId to target another."
+    })
+}
+
+/// Returns the MCP tool definitions.
+fn tool_definitions() -> Vec<Value> {
+    let window_id = json!({
+        "type": "string",
+        "description": "Window label to target (defaults to 'main')"
+    });
+
+    vec![
+        json!({
+            "name": "manage_window",
+            "description": "Manage Tauri windows. Actions: \"list\" - list all windows; \
+                \"info\" - size, position, scale factor, monitor, state flags, title and URL of a window \
+                (\"list\" returns the same per window with detail: \"full\"); \
+                \"monitors\" - available monitors (size, position, scale factor, work area, primary) and cursor position; \
+                \"resize\" - resize a window (requires width/height); \
+                \"setPosition\" (requires x/y); \"minimize\", \"unminimize\", \"maximize\", \"unmaximize\"; \
+                \"setFullscreen\" (toggles when fullscreen is omitted); \"focus\", \"show\", \"hide\", \"close\"; \
+                \"setAlwaysOnTop\" (requires alwaysOnTop); \"setTitle\" (requires title); \"center\".",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": [
+                            "list", "info", "monitors", "resize", "setPosition", "minimize", "unminimize",
+                            "maximize", "unmaximize", "setFullscreen", "focus", "show", "hide",
+                            "close", "setAlwaysOnTop", "setTitle", "center"
+                        ]
+                    },
+                    "windowId": window_id,
+                    "detail": { "type": "string", "enum": ["basic", "full"], "description": "Detail level for \"list\"" },
+                    "width": { "type": "integer", "minimum": 1 },
+                    "height": { "type": "integer", "minimum": 1 },
+                    "x": { "type": "integer" },
+                    "y": { "type": "integer" },
+                    "logical": { "type": "boolean", "description": "Use logical pixels (default true)" },
+                    "fullscreen": { "type": "boolean" },
+                    "alwaysOnTop": { "type": "boolean" },
+                    "title": { "type": "string" }
+                },
+                "required": ["action"]
+            }
+        }),
+        json!({
+            "name": "create_window",
+            "description": "Open a new window, or add a webview to an existing window when attachTo is set. \
+                url may be an app route (e.g. \"/settings\") or an external http(s) URL. \
+                Registered scripts are injected into the new webview automatically.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "label": { "type": "string", "description": "Unique label for the new window or webview" },
+                    "url": { "type": "string" },
+                    "title": { "type": "string" },
+                    "width": { "type": "number" },
+                    "height": { "type": "number" },
+                    "x": { "type": "number" },
+                    "y": { "type": "number" },
+                    "decorations": { "type": "boolean" },
+                    "resizable": { "type": "boolean" },
+                    "visible": { "type": "boolean" },
+                    "focused": { "type": "boolean" },
+                    "parent": { "type": "string", "description": "Label of the parent window" },
+                    "attachTo": { "type": "string", "description": "Add a webview to this window instead of opening a new window" }
+                },
+                "required": ["label"]
+            }
+        }),
+        json!({
+            "name": "webview_navigate",
+            "description": "Navigate a webview and wait for pages to load. Actions: \"navigate\" (requires url: \
+                an app route like \"/settings\" or an absolute URL); \"reload\"; \"goBack\"; \"goForward\"; \
+                \"waitForNavigation\" - wait for the next page load, or until the loaded URL contains url. \
+                Set wait to true to wait for the page after navigate/reload/goBack/goForward. Returns the final URL.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["navigate", "reload", "goBack", "goForward", "waitForNavigation"]
+                    },
+                    "windowId": window_id,
+                    "url": { "type": "string" },
+                    "wait": { "type": "boolean" },
+                    "timeoutMs": { "type": "integer", "minimum": 0, "description": "Default 10000" }
+                },
+                "required": ["action"]
+            }
+        }),
+        json!({
+            "name": "webview_execute_js",
+            "description": "Execute JavaScript in the webview and return its JSON-serializable result. \
+                Use an IIFE to return values: \"(() => { return 5; })()\".",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "script": { "type": "string" },
+                    "windowId": window_id
+                },
+                "required": ["script"]
+            }
+        }),
+        json!({
+            "name": "webview_screenshot",
+            "description": "Capture a screenshot of the webview's visible viewport.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "format": { "type": "string", "enum": ["png", "jpeg"] },
+                    "quality": { "type": "integer", "minimum": 0, "maximum": 100 },
+                    "maxWidth": { "type": "integer", "minimum": 1 },
+                    "windowId": window_id
+                }
+            },
+            "annotations": { "readOnlyHint": true }
+        }),
+        json!({
+            "name": "webview_manage_scripts",
+            "description": "Manage scripts that are injected into the webview and re-injected on every page load. \
+                Actions: \"register\" (requires id, type, content), \"remove\" (requires id), \"clear\", \"list\".",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "action": { "type": "string", "enum": ["register", "remove", "clear", "list"] },
+                    "id": { "type": "string" },
+                    "type": { "type": "string", "enum": ["inline", "url"] },
+                    "content": { "type": "string", "description": "JavaScript code, or a script URL" },
+                    "windowId": window_id
+                },
+                "required": ["action"]
+            }
+        }),
+    ]
+    .into_iter()
+    .chain(ipc_tool_definitions())
+    .collect()
+}
+
+/// Returns the definitions of the IPC and event tools.
+fn ipc_tool_definitions() -> Vec<Value> {
+    vec![
+        json!({
+            "name": "ipc_monitor",
+            "description": "Start or stop capturing Tauri IPC calls between the frontend and Rust backend. \
+                Actions: \"start\" and \"stop\" control the default monitor, which captures every window; \
+                \"start_session\" starts a session with its own buffer capturing \"windows\" (labels; every \
+                window, including ones opened later, if omitted), restarting \"sessionId\" if it exists; \
+                \"stop_session\" keeps its calls; \"end_session\" returns and discards them; \
+                \"list_sessions\".",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["start", "stop", "start_session", "stop_session", "end_session", "list_sessions"]
+                    },
+                    "sessionId": { "type": "string" },
+                    "windows": { "type": "array", "items": { "type": "string" } }
+                },
+                "required": ["action"]
+            }
+        }),
+        json!({
+            "name": "ipc_get_captured",
+            "description": "Get IPC calls captured since ipc_monitor was started, with a per-rule \
+                summary of injected faults, or the calls captured by the monitoring session \"sessionId\".",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "sessionId": { "type": "string" } }
+            },
+            "annotations": { "readOnlyHint": true }
+        }),
+        json!({
+            "name": "ipc_emit_event",
+            "description": "Emit a Tauri event to test event handlers. By default the event goes to every \
+                listener; \"target\" sends it to one window or webview (emit_to) and \"targets\" to several \
+                (emit_filter). A target is a label (any window or webview with that label) or a Tauri \
+                EventTarget such as {\"kind\": \"WebviewWindow\", \"label\": \"main\"}. With waitForAck, \
+                waits until a listener emits the ack event (default \"<eventName>:ack\") and returns its payload.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "eventName": { "type": "string" },
+                    "payload": {},
+                    "target": {},
+                    "targets": { "type": "array" },
+                    "waitForAck": { "type": "boolean" },
+                    "ackEvent": { "type": "string" },
+                    "ackTimeoutMs": { "type": "integer", "minimum": 0 }
+                },
+                "required": ["eventName"]
+            }
+        }),
+        json!({
+            "name": "ipc_event_tap",
+            "description": "Capture Tauri events emitted by the backend or frontend (app.emit, emit_to). \
+                Actions: \"start\" - subscribe to the listed event names (exact names only, Tauri has no \
+                wildcard listeners; replaces previous ones); \"stop\"; \"get\" - captured events with \
+                payload, optionally filtered by a glob pattern (e.g. \"download:*\") and sequence number \
+                (source and target are only known for events sent with ipc_emit_event); \"clear\".",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "action": { "type": "string", "enum": ["start", "stop", "get", "clear"] },
+                    "events": { "type": "array", "items": { "type": "string" } },
+                    "stream": { "type": "boolean", "description": "Broadcast captured events to WebSocket clients" },
+                    "pattern": { "type": "string" },
+                    "since": { "type": "integer", "minimum": 0 }
+                },
+                "required": ["action"]
+            }
+        }),
+        json!({
+            "name": "ipc_mock",
+            "description": "Mock app commands called from the frontend so the real command is bypassed. \
+                Actions: \"add\" - add or replace rule \"id\" for \"command\", optionally only when the call's \
+                args contain \"args\", answering with \"result\" or \"error\" and/or after \"delayMs\" \
+                (a delay alone runs the real command afterwards); \"remove\"; \"clear\"; \"list\" - rules \
+                with hit counts. Requires the app to wrap its invoke handler with ipc_capture::capture.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "action": { "type": "string", "enum": ["add", "remove", "clear", "list"] },
+                    "id": { "type": "string" },
+                    "command": { "type": "string" },
+                    "args": { "type": "object" },
+                    "result": {},
+                    "error": {},
+                    "delayMs": { "type": "integer", "minimum": 0 }
+                },
+                "required": ["action"]
+            }
+        }),
+        json!({
+            "name": "ipc_fault",
+            "description": "Inject faults into app commands called from the frontend. \
+                Actions: \"add\" - add or replace rule \"id\" for \"command\" (\"*\" is a wildcard) that \
+                delays every call by \"latencyMs\", fails a share \"errorRate\" (0 to 1) of calls with \
+                \"error\" and/or leaves a share \"hangRate\" unresolved; \"remove\"; \"clear\"; \"list\" - \
+                rules with counts of injected faults, counted whether or not ipc_monitor is running and \
+                also summarized in \"faults\" of ipc_get_captured. While it runs, affected calls also \
+                carry a \"fault\" in ipc_get_captured. \
+                Requires the app to wrap its invoke handler with ipc_capture::capture.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "action": { "type": "string", "enum": ["add", "remove", "clear", "list"] },
+                    "id": { "type": "string" },
+                    "command": { "type": "string" },
+                    "latencyMs": { "type": "integer", "minimum": 0 },
+                    "errorRate": { "type": "number", "minimum": 0, "maximum": 1 },
+                    "error": {},
+                    "hangRate": { "type": "number", "minimum": 0, "maximum": 1 }
+                },
+                "required": ["action"]
+            }
+        }),
+        json!({
+            "name": "ipc_session",
+            "description": "Export or import a HAR-like session document of captured IPC calls, Tauri \
+                events and console logs. Actions: \"export\" - returns the document; console logs are \
+                read from \"windowLabel\" or every window unless \"console\" is false; \"import\" - loads \
+                the IPC calls of \"document\" into a new monitoring session (read them with \
+                ipc_get_captured and the returned sessionId) and returns its Tauri events and console logs.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "action": { "type": "string", "enum": ["export", "import"] },
+                    "windowLabel": { "type": "string" },
+                    "console": { "type": "boolean" },
+                    "document": { "type": "object" }
+                },
+                "required": ["action"]
+            }
+        }),
+        json!({
+            "name": "ipc_replay",
+            "description": "Re-issue recorded IPC calls in order against the live backend and report \
+                where responses diverge from the recording. Replays the IPC monitor's calls, or those of a \
+                session document (\"session\") from ipc_session export or of a monitoring session \
+                (\"sessionId\", e.g. an imported one). \"timing\" is \
+                \"compressed\" (default; gaps capped at \"maxGapMs\", default 0) or \"original\"; \
+                \"commands\" is a glob filter and \"ignore\" lists JSON pointers into results to skip.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "session": { "type": "object" },
+                    "sessionId": { "type": "string" },
+                    "windowLabel": { "type": "string" },
+                    "commands": { "type": "string" },
+                    "timing": { "type": "string", "enum": ["original", "compressed"] },
+                    "maxGapMs": { "type": "integer", "minimum": 0 },
+                    "timeoutMs": { "type": "integer", "minimum": 0 },
+                    "ignore": { "type": "array", "items": { "type": "string" } }
+                }
+            }
+        }),
+        json!({
+            "name": "ipc_stats",
+            "description": "Latency statistics for the IPC calls captured since ipc_monitor started. \
+                Actions: \"get\" - per-command count, error count, slow-call count and p50/p90/p99/max \
+                duration in ms; \"set_threshold\" - report calls slower than \"thresholdMs\" (omit to \
+                disable), which are also broadcast as ipc_slow_call messages.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "action": { "type": "string", "enum": ["get", "set_threshold"] },
+                    "thresholdMs": { "type": "number", "minimum": 0 }
+                },
+                "required": ["action"]
+            }
+        }),
+        json!({
+            "name": "ipc_redaction",
+            "description": "Mask sensitive values in captured IPC calls and console logs. Actions: \
+                \"add\" - add or replace rule \"id\" masking values whose key matches a \"keys\" glob \
+                (e.g. \"*token*\", case-insensitive, any depth) or that sit at a \"paths\" JSON path \
+                (e.g. \"$.args.user.password\", \"$.result.items[*].secret\", \"$.error\"), optionally \
+                only for commands matching \"command\"; \"remove\"; \"clear\"; \"list\". Rules apply \
+                to events already captured; keys of rules without \"command\" also mask console logs.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "action": { "type": "string", "enum": ["add", "remove", "clear", "list"] },
+                    "id": { "type": "string" },
+                    "command": { "type": "string" },
+                    "keys": { "type": "array", "items": { "type": "string" } },
+                    "paths": { "type": "array", "items": { "type": "string" } }
+                },
+                "required": ["action"]
+            }
+        }),
+        json!({
+            "name": "backend_logs",
+            "description": "Read log records from the app's Rust backend (tracing and log crates), to \
+                correlate UI actions with backend behavior. Requires Builder::capture_backend_logs or \
+                backend_logs::layer() in the app. Actions: \"get\" - records with level, target, message, \
+                fields and span context, filtered by minimum \"level\", \"target\" glob, \"contains\" \
+                text, sequence number \"since\" and the most recent \"limit\"; \"clear\"; \"stream\" - \
+                broadcast new records as backend_log messages while \"enabled\".",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "action": { "type": "string", "enum": ["get", "clear", "stream"] },
+                    "level": { "type": "string", "enum": ["error", "warn", "info", "debug", "trace"] },
+                    "target": { "type": "string" },
+                    "contains": { "type": "string" },
+                    "since": { "type": "integer", "minimum": 0 },
+                    "limit": { "type": "integer", "minimum": 1 },
+                    "enabled": { "type": "boolean" }
+                },
+                "required": ["action"]
+            }
+        }),
+        json!({
+            "name": "ipc_wait_for_event",
+            "description": "Wait until a Tauri event fires and return its payload, or fail after timeoutMs \
+                (default 10000). \"pointer\" selects a value in the payload with a JSON pointer (e.g. \"/status\"); \
+                \"matches\" is the expected value there (or of the whole payload), where objects match if \
+                every key they list matches.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "event": { "type": "string" },
+                    "pointer": { "type": "string" },
+                    "matches": {},
+                    "timeoutMs": { "type": "integer", "minimum": 0 }
+                },
+                "required": ["event"]
+            },
+            "annotations": { "readOnlyHint": true }
+        }),
+        json!({
+            "name": "ipc_get_backend_state",
+            "description": "Get Tauri backend state: app metadata, Tauri version, environment and windows.",
+            "inputSchema": { "type": "object", "properties": {} },
+            "annotations": { "readOnlyHint": true }
+        }),
+    ]
+}
+
+/// Builds the bridge command for a tool call.
+fn tool_command(name: &str, args: &Value) -> Result<Value, RpcError> {
+    let window_id = args.get("windowId").cloned().unwrap_or(Value::Null);
+    let action = args.get("action").and_then(|v| v.as_str()).unwrap_or("");
+    let invoke = |command: &str, args: Value| {
+        json!({
+            "command": "invoke_tauri",
+            "args": { "command": format!("plugin:mcp-bridge|{command}"), "args": args }
+        })
+    };
+
+    let command = match (name, action) {
+        ("manage_window", "list") => json!({
+            "command": "list_windows",
+            "args": { "detail": args.get("detail").cloned().unwrap_or(Value::Null) }
+        }),
+        ("manage_window", "info") => json!({
+            "command": "get_window_info",
+            "args": { "windowId": window_id }
+        }),
+        ("manage_window", "monitors") => json!({ "command": "list_monitors" }),
+        ("manage_window", "resize") => json!({ "command": "resize_window", "args": args }),
+        ("manage_window", "") => {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                "Missing action for tool 'manage_window'",
+            ))
+        }
+        ("manage_window", _) => json!({ "command": "manage_window", "args": args }),
+        ("create_window", _) => match args.get("attachTo") {
+            Some(window) => {
+                let mut webview_args = args.clone();
+                webview_args["windowId"] = window.clone();
+                json!({ "command": "create_webview", "args": webview_args })
+            }
+            None => json!({ "command": "create_window", "args": args }),
+        },
+        ("webview_navigate", action) => {
+            let action: crate::commands::NavigationAction =
+                serde_json::from_value(Value::String(action.to_string())).map_err(|_| {
+                    RpcError::new(
+                        INVALID_PARAMS,
+                        format!("Unknown action '{action}' for tool 'webview_navigate'"),
+                    )
+                })?;
+            json!({ "command": action.command(), "args": args })
+        }
+        ("webview_execute_js", _) => json!({
+            "command": "execute_js",
+            "args": { "script": args.get("script"), "windowLabel": window_id }
+        }),
+        ("webview_screenshot", _) => json!({
+            "command": "capture_native_screenshot",
+            "args": {
+                "format": args.get("format"),
+                "quality": args.get("quality"),
+                "maxWidth": args.get("maxWidth"),
+                "windowLabel": window_id
+            }
+        }),
+        ("webview_manage_scripts", "register") => json!({
+            "command": "register_script",
+            "args": {
+                "id": args.get("id"),
+                "type": args.get("type").cloned().unwrap_or_else(|| json!("inline")),
+                "content": args.get("content"),
+                "windowLabel": window_id
+            }
+        }),
+        ("webview_manage_scripts", "remove") => json!({
+            "command": "remove_script",
+            "args": { "id": args.get("id"), "windowLabel": window_id }
+        }),
+        ("webview_manage_scripts", "clear") => json!({
+            "command": "clear_scripts",
+            "args": { "windowLabel": window_id }
+        }),
+        ("webview_manage_scripts", "list") => json!({ "command": "get_scripts" }),
+        ("ipc_monitor", "start") => invoke("start_ipc_monitor", Value::Null),
+        ("ipc_monitor", "stop") => invoke("stop_ipc_monitor", Value::Null),
+        ("ipc_monitor", "start_session") => json!({
+            "command": "start_monitor_session",
+            "args": { "sessionId": args.get("sessionId"), "windows": args.get("windows") }
+        }),
+        ("ipc_monitor", "stop_session") => json!({
+            "command": "stop_monitor_session",
+            "args": { "sessionId": args.get("sessionId") }
+        }),
+        ("ipc_monitor", "end_session") => json!({
+            "command": "end_monitor_session",
+            "args": { "sessionId": args.get("sessionId") }
+        }),
+        ("ipc_monitor", "list_sessions") => json!({ "command": "list_monitor_sessions" }),
+        ("ipc_get_captured", _) => match args.get("sessionId") {
+            Some(session_id) => json!({
+                "command": "get_monitor_session_events",
+                "args": { "sessionId": session_id }
+            }),
+            None => invoke("get_ipc_events", Value::Null),
+        },
+        ("ipc_emit_event", _) => invoke("emit_event", args.clone()),
+        ("ipc_event_tap", "start") => json!({ "command": "start_event_tap", "args": args }),
+        ("ipc_event_tap", "stop") => json!({ "command": "stop_event_tap" }),
+        ("ipc_event_tap", "get") => json!({ "command": "get_tauri_events", "args": args }),
+        ("ipc_event_tap", "clear") => json!({ "command": "clear_tauri_events" }),
+        ("ipc_mock", "add") => {
+            let mut rule = args.clone();
+            if let Some(rule) = rule.as_object_mut() {
+                rule.remove("action");
+            }
+            json!({ "command": "add_ipc_mock", "args": rule })
+        }
+        ("ipc_mock", "remove") => {
+            json!({ "command": "remove_ipc_mock", "args": { "id": args.get("id") } })
+        }
+        ("ipc_mock", "clear") => json!({ "command": "clear_ipc_mocks" }),
+        ("ipc_mock", "list") => json!({ "command": "get_ipc_mocks" }),
+        ("ipc_fault", "add") => {
+            let mut rule = args.clone();
+            if let Some(rule) = rule.as_object_mut() {
+                rule.remove("action");
+            }
+            json!({ "command": "add_ipc_fault", "args": rule })
+        }
+        ("ipc_fault", "remove") => {
+            json!({ "command": "remove_ipc_fault", "args": { "id": args.get("id") } })
+        }
+        ("ipc_fault", "clear") => json!({ "command": "clear_ipc_faults" }),
+        ("ipc_fault", "list") => json!({ "command": "get_ipc_faults" }),
+        ("ipc_session", "export" | "import") => {
+            let mut params = args.clone();
+            if let Some(params) = params.as_object_mut() {
+                params.remove("action");
+            }
+            json!({ "command": format!("{action}_session"), "args": params })
+        }
+        ("ipc_stats", "get") => json!({ "command": "get_ipc_stats" }),
+        ("ipc_stats", "set_threshold") => json!({
+            "command": "set_ipc_slow_threshold",
+            "args": { "thresholdMs": args.get("thresholdMs") }
+        }),
+        ("ipc_redaction", "add") => {
+            let mut rule = args.clone();
+            if let Some(rule) = rule.as_object_mut() {
+                rule.remove("action");
+            }
+            json!({ "command": "add_redaction_rule", "args": rule })
+        }
+        ("ipc_redaction", "remove") => {
+            json!({ "command": "remove_redaction_rule", "args": { "id": args.get("id") } })
+        }
+        ("ipc_redaction", "clear") => json!({ "command": "clear_redaction_rules" }),
+        ("ipc_redaction", "list") => json!({ "command": "get_redaction_rules" }),
+        ("backend_logs", "get") => {
+            let mut query = args.clone();
+            if let Some(query) = query.as_object_mut() {
+                query.remove("action");
+            }
+            json!({ "command": "get_backend_logs", "args": query })
+        }
+        ("backend_logs", "clear") => json!({ "command": "clear_backend_logs" }),
+        ("backend_logs", "stream") => json!({
+            "command": "stream_backend_logs",
+            "args": { "enabled": args.get("enabled").cloned().unwrap_or(json!(true)) }
+        }),
+        ("ipc_replay", _) => json!({ "command": "replay_ipc", "args": args }),
+        ("ipc_wait_for_event", _) => json!({ "command": "wait_for_event", "args": args }),
+        ("ipc_get_backend_state", _) => invoke("get_backend_state", Value::Null),
+        (
+            "webview_manage_scripts"
+            | "ipc_monitor"
+            | "ipc_event_tap"
+            | "ipc_mock"
+            | "ipc_fault"
+            | "ipc_session"
+            | "ipc_stats"
+            | "ipc_redaction"
+            | "backend_logs",
+            _,
+        ) => {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                format!("Invalid action '{action}' for tool '{name}'"),
+            ))
+        }
+        _ => {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                format!("Unknown tool: {name}"),
+            ))
+        }
+    };
+
+    Ok(command)
+}
+
+/// Runs a tool and converts the bridge response into a `CallToolResult`.
+async fn call_tool<R: Runtime>(app: &AppHandle<R>, params: &Value) -> Result<Value, RpcError> {
+    let Some(name) = params.get("name").and_then(|v| v.as_str()) else {
+        return Err(RpcError::new(INVALID_PARAMS, "Missing tool name"));
+    };
+    let args = params
+        .get("arguments")
+        .cloned()
+        .unwrap_or_else(|| json!({}));
+
+    let mut command = tool_command(name, &args)?;
+    command["id"] = json!(format!("mcp-{name}"));
+    let response = dispatch_command(app, &command).await;
+
+    let success = response
+        .get("success")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let mut content = Vec::new();
+
+    if success {
+        let data = response.get("data").cloned().unwrap_or(Value::Null);
+        content.push(match data.as_str().and_then(parse_image_data_url) {
+            Some((mime_type, data)) => {
+                json!({ "type": "image", "data": data, "mimeType": mime_type })
+            }
+            None => text_content(&data),
+        });
+    } else {
+        let error = response
+            .get("error")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown error");
+        content.push(json!({ "type": "text", "text": error }));
+    }
+
+    if let Some(warning) = response
+        .get("windowContext")
+        .and_then(|c| c.get("warning"))
+        .and_then(|v| v.as_str())
+    {
+        content.push(json!({ "type": "text", "text": warning }));
+    }
+
+    Ok(json!({ "content": content, "isError": !success }))
+}
+
+/// Formats a JSON value as text content, leaving strings unquoted.
+fn text_content(data: &Value) -> Value {
+    let text = match data {
+        Value::String(s) => s.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    };
+    json!({ "type": "text", "text": text })
+}
+
+/// Splits a `data:image/...;base64,` URL into its MIME type and payload.
+fn parse_image_data_url(url: &str) -> Option<(&str, &str)> {
+    let rest = url.strip_prefix("data:")?;
+    let (mime_type, data) = rest.split_once(";base64,")?;
+    mime_type.starts_with("image/").then_some((mime_type, data))
+}
+
+/// Read-only resources and the bridge command that produces each.
+const RESOURCES: &[(&str, &str, &str)] = &[
+    (
+        "tauri://backend-state",
+        "Backend state",
+        "App metadata, Tauri version, environment and windows",
+    ),
+    ("tauri://windows", "Windows", "All open webview windows"),
+    (
+        "tauri://monitors",
+        "Monitors",
+        "Available monitors and the cursor position",
+    ),
+    (
+        "tauri://ipc-events",
+        "Captured IPC events",
+        "IPC calls captured by the IPC monitor, with a summary of injected faults",
+    ),
+    (
+        "tauri://tauri-events",
+        "Captured Tauri events",
+        "Tauri events captured by the event tap",
+    ),
+    (
+        "tauri://scripts",
+        "Registered scripts",
+        "Scripts re-injected on every page load",
+    ),
+];
+
+/// Returns the MCP resource definitions.
+fn resource_definitions() -> Vec<Value> {
+    RESOURCES
+        .iter()
+        .map(|(uri, name, description)| {
+            json!({
+                "uri": uri,
+                "name": name,
+                "description": description,
+                "mimeType": "application/json"
+            })
+        })
+        .collect()
+}
+
+/// Reads a resource by URI.
+async fn read_resource<R: Runtime>(app: &AppHandle<R>, params: &Value) -> Result<Value, RpcError> {
+    let Some(uri) = params.get("uri").and_then(|v| v.as_str()) else {
+        return Err(RpcError::new(INVALID_PARAMS, "Missing resource uri"));
+    };
+
+    let invoke = |command: &str| {
+        json!({
+            "command": "invoke_tauri",
+            "args": { "command": format!("plugin:mcp-bridge|{command}"), "args": null }
+        })
+    };
+    let mut command = match uri {
+        "tauri://backend-state" => invoke("get_backend_state"),
+        "tauri://windows" => json!({ "command": "list_windows" }),
+        "tauri://monitors" => json!({ "command": "list_monitors" }),
+        "tauri://ipc-events" => invoke("get_ipc_events"),
+        "tauri://tauri-events" => json!({ "command": "get_tauri_events" }),
+        "tauri://scripts" => json!({ "command": "get_scripts" }),
+        _ => {
+            return Err(RpcError::new(
+                RESOURCE_NOT_FOUND,
+                format!("Resource not found: {uri}"),
+            ))
+        }
+    };
+    command["id"] = json!("mcp-resource");
+
+    let response = dispatch_command(app, &command).await;
+    if response.get("success").and_then(|v| v.as_bool()) != Some(true) {
+        let error = response
+            .get("error")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown error");
+        return Err(RpcError::new(INVALID_PARAMS, error));
+    }
+
+    let data = response.get("data").cloned().unwrap_or(Value::Null);
+    Ok(json!({
+        "contents": [{
+            "uri": uri,
+            "mimeType": "application/json",
+            "text": serde_json::to_string_pretty(&data).unwrap_or_default()
+        }]
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::IPCMonitor;
+    use crate::script_registry::create_shared_registry;
+    use std::sync::{Arc, Mutex};
+    use tauri::Manager;
+
+    fn mock_app() -> tauri::App<tauri::test::MockRuntime> {
+        let app = tauri::test::mock_app();
+        app.manage(Arc::new(Mutex::new(IPCMonitor::new())));
+        app.manage(create_shared_registry());
+        app.manage(crate::ipc_faults::create_shared_fault_injector());
+        app
+    }
+
+    async fn request(
+        app: &tauri::App<tauri::test::MockRuntime>,
+        method: &str,
+        params: Value,
+    ) -> Value {
+        let message = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        handle_payload(app.handle(), message).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_initialize_negotiates_version() {
+        let app = mock_app();
+
+        let response = request(
+            &app,
+            "initialize",
+            json!({ "protocolVersion": "2025-03-26" }),
+        )
+        .await;
+        assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+
+        let response = request(
+            &app,
+            "initialize",
+            json!({ "protocolVersion": "1999-01-01" }),
+        )
+        .await;
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSION);
+    }
+
+    #[tokio::test]
+    async fn test_tools_list_and_call() {
+        let app = mock_app();
+
+        let response = request(&app, "tools/list", Value::Null).await;
+        let tools = response["result"]["tools"].as_array().unwrap();
+        assert!(tools.iter().any(|t| t["name"] == "webview_execute_js"));
+
+        let response = request(
+            &app,
+            "tools/call",
+            json!({ "name": "webview_manage_scripts", "arguments": { "action": "list" } }),
+        )
+        .await;
+        assert_eq!(response["result"]["isError"], false);
+        assert!(response["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("scripts"));
+
+        // Tool failures are reported in the result, not as protocol errors
+        let response = request(
+            &app,
+            "tools/call",
+            json!({ "name": "manage_window", "arguments": { "action": "info", "windowId": "nope" } }),
+        )
+        .await;
+        assert_eq!(response["result"]["isError"], true);
+    }
+
+    #[tokio::test]
+    async fn test_protocol_errors() {
+        let app = mock_app();
+
+        let response = request(&app, "does/not/exist", Value::Null).await;
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = request(&app, "tools/call", json!({ "name": "nope" })).await;
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        let response = request(&app, "resources/read", json!({ "uri": "tauri://nope" })).await;
+        assert_eq!(response["error"]["code"], RESOURCE_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_notifications_get_no_response() {
+        let app = mock_app();
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(handle_payload(app.handle(), notification.clone())
+            .await
+            .is_none());
+        assert!(handle_payload(app.handle(), json!([notification]))
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_resource() {
+        let app = mock_app();
+        let response = request(
+            &app,
+            "resources/read",
+            json!({ "uri": "tauri://ipc-events" }),
+        )
+        .await;
+        let text = response["result"]["contents"][0]["text"].as_str().unwrap();
+        let data: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(data, json!({ "events": [], "faults": [] }));
+    }
+
+    #[test]
+    fn test_parse_image_data_url() {
+        assert_eq!(
+            parse_image_data_url("data:image/png;base64,AAAA"),
+            Some(("image/png", "AAAA"))
+        );
+        assert_eq!(parse_image_data_url("data:text/plain;base64,AAAA"), None);
+        assert_eq!(parse_image_data_url("hello"), None);
+    }
+}
@@ -0,0 +1,103 @@
+//! stdio transport for the MCP endpoint.
+//!
+//! In sidecar mode the MCP client spawns the Tauri app and talks to it over
+//! the process's stdin and stdout, one JSON-RPC message per line. The plugin
+//! never writes to stdout itself, but the app's logger must not either, or it
+//! will corrupt the stream.
+
+use crate::logging::{mcp_log_error, mcp_log_info};
+use tauri::{AppHandle, Runtime};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+
+/// Serves MCP on the process's stdin/stdout in the background.
+pub fn spawn<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = serve(app, tokio::io::stdin(), tokio::io::stdout()).await {
+            mcp_log_error("MCP", &format!("stdio transport error: {e}"));
+        }
+    });
+}
+
+/// Reads newline-delimited JSON-RPC messages from `input` and writes the
+/// responses to `output` until `input` is closed.
+///
+/// Requests are handled concurrently, so a slow tool call does not hold up
+/// other requests; responses are written as they complete.
+pub async fn serve<R, I, O>(app: AppHandle<R>, input: I, mut output: O) -> std::io::Result<()>
+where
+    R: Runtime,
+    I: AsyncRead + Unpin,
+    O: AsyncWrite + Unpin + Send + 'static,
+{
+    let (response_tx, mut response_rx) = mpsc::unbounded_channel::<String>();
+
+    let writer = tokio::spawn(async move {
+        while let Some(response) = response_rx.recv().await {
+            output.write_all(response.as_bytes()).await?;
+            output.write_all(b"\n").await?;
+            output.flush().await?;
+        }
+        Ok::<_, std::io::Error>(())
+    });
+
+    mcp_log_info("MCP", "Serving MCP on stdio");
+
+    let mut lines = BufReader::new(input).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let payload = match serde_json::from_str(&line) {
+            Ok(payload) => payload,
+            Err(e) => {
+                let _ = response_tx.send(super::parse_error_response(e).to_string());
+                continue;
+            }
+        };
+
+        let app = app.clone();
+        let response_tx = response_tx.clone();
+        tokio::spawn(async move {
+            if let Some(response) = super::handle_payload(&app, payload).await {
+                let _ = response_tx.send(response.to_string());
+            }
+        });
+    }
+
+    drop(response_tx);
+    writer.await.unwrap_or(Ok(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[tokio::test]
+    async fn test_serve_stdio_lines() {
+        let app = tauri::test::mock_app();
+        let (mut client, server) = tokio::io::duplex(4096);
+        let (server_read, server_write) = tokio::io::split(server);
+        let handle = app.handle().clone();
+        tokio::spawn(async move { serve(handle, server_read, server_write).await });
+
+        client
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"ping\"}\nnot json\n")
+            .await
+            .unwrap();
+
+        let mut lines = BufReader::new(client).lines();
+        let mut responses = Vec::new();
+        for _ in 0..2 {
+            let line = lines.next_line().await.unwrap().unwrap();
+            responses.push(serde_json::from_str::<Value>(&line).unwrap());
+        }
+
+        assert!(responses
+            .iter()
+            .any(|r| r["id"] == 1 && r["result"].is_object()));
+        assert!(responses.iter().any(|r| r["error"]["code"] == -32700));
+    }
+}
@@ -0,0 +1,202 @@
+//! mDNS/DNS-SD advertisement of the bridge.
+//!
+//! When enabled with [`Builder::advertise_mdns`](crate::Builder::advertise_mdns),
+//! the plugin registers a `_tauri-mcp._tcp` service so devices and VMs on the
+//! LAN can discover the WebSocket server's address and port instead of
+//! guessing them. TXT records carry the app identifier, app version and
+//! bridge protocol version.
+
+use mdns_sd::{IfKind, ServiceDaemon, ServiceInfo};
+use std::net::IpAddr;
+
+/// DNS-SD service type advertised by the bridge.
+pub const SERVICE_TYPE: &str = "_tauri-mcp._tcp.local.";
+
+/// TXT record key for the app's bundle identifier.
+pub const TXT_IDENTIFIER: &str = "identifier";
+/// TXT record key for the app version.
+pub const TXT_VERSION: &str = "version";
+/// TXT record key for the bridge WebSocket protocol version.
+pub const TXT_PROTOCOL: &str = "protocol";
+/// TXT record key for the process ID.
+pub const TXT_PID: &str = "pid";
+
+/// Describes the bridge instance to advertise.
+#[derive(Debug, Clone)]
+pub struct ServiceDescription {
+    /// The app's bundle identifier
+    pub identifier: String,
+    /// The app's product name, used as the DNS-SD instance name
+    pub product_name: String,
+    /// The app version
+    pub version: String,
+    /// Version of the bridge WebSocket protocol
+    pub protocol_version: u32,
+    /// Process ID of the running instance
+    pub pid: u32,
+    /// Address the WebSocket server is bound to
+    pub bind_address: IpAddr,
+    /// Port the WebSocket server is listening on
+    pub port: u16,
+}
+
+/// A running DNS-SD advertisement.
+///
+/// The service is unregistered when [`Advertiser::shutdown`] is called or the
+/// advertiser is dropped.
+pub struct Advertiser {
+    daemon: ServiceDaemon,
+    fullname: String,
+}
+
+impl Advertiser {
+    /// Starts advertising the bridge on the interfaces matching its bind
+    /// address.
+    ///
+    /// * `0.0.0.0` / `::` - all non-loopback interfaces
+    /// * a loopback address - the loopback interface only
+    /// * any other address - only the interface with that address
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Advertiser)` - The service is registered
+    /// * `Err(mdns_sd::Error)` - If the responder could not be started
+    pub fn start(service: &ServiceDescription) -> Result<Self, mdns_sd::Error> {
+        let daemon = ServiceDaemon::new()?;
+
+        if service.bind_address.is_loopback() {
+            daemon.disable_interface(IfKind::All)?;
+            daemon.enable_interface(if service.bind_address.is_ipv4() {
+                IfKind::LoopbackV4
+            } else {
+                IfKind::LoopbackV6
+            })?;
+        } else if !service.bind_address.is_unspecified() {
+            daemon.disable_interface(IfKind::All)?;
+            daemon.enable_interface(IfKind::Addr(service.bind_address))?;
+        }
+
+        let instance_name = instance_name(&service.product_name, service.pid);
+        let host_name = format!("tauri-mcp-{}.local.", service.pid);
+        let version = service.version.clone();
+        let protocol = service.protocol_version.to_string();
+        let pid = service.pid.to_string();
+        let properties = [
+            (TXT_IDENTIFIER, service.identifier.as_str()),
+            (TXT_VERSION, version.as_str()),
+            (TXT_PROTOCOL, protocol.as_str()),
+            (TXT_PID, pid.as_str()),
+        ];
+
+        let info = ServiceInfo::new(
+            SERVICE_TYPE,
+            &instance_name,
+            &host_name,
+            (),
+            service.port,
+            &properties[..],
+        )?
+        .enable_addr_auto();
+
+        let fullname = info.get_fullname().to_string();
+        daemon.register(info)?;
+
+        Ok(Self { daemon, fullname })
+    }
+
+    /// Returns the full DNS-SD name of the advertised service.
+    pub fn fullname(&self) -> &str {
+        &self.fullname
+    }
+
+    /// Unregisters the service and stops the responder.
+    pub fn shutdown(&self) {
+        if let Ok(status) = self.daemon.unregister(&self.fullname) {
+            let _ = status.recv_timeout(std::time::Duration::from_secs(1));
+        }
+        let _ = self.daemon.shutdown();
+    }
+}
+
+impl Drop for Advertiser {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Builds a DNS-SD instance name that is unique per process.
+///
+/// Instance labels are limited to 63 bytes, so long product names are
+/// truncated.
+fn instance_name(product_name: &str, pid: u32) -> String {
+    let suffix = format!(" ({pid})");
+    let max_name_len = 63 - suffix.len();
+    let mut name = String::new();
+
+    for c in product_name.chars().filter(|c| *c != '.') {
+        if name.len() + c.len_utf8() > max_name_len {
+            break;
+        }
+        name.push(c);
+    }
+
+    format!("{name}{suffix}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mdns_sd::ServiceEvent;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_instance_name_is_truncated() {
+        assert_eq!(instance_name("My App", 42), "My App (42)");
+        assert!(instance_name(&"x".repeat(100), 42).len() <= 63);
+        assert_eq!(instance_name("a.b", 1), "ab (1)");
+    }
+
+    #[test]
+    fn test_advertise_on_loopback() {
+        let service = ServiceDescription {
+            identifier: "com.example.mdns-test".to_string(),
+            product_name: "MDNS Test".to_string(),
+            version: "1.2.3".to_string(),
+            protocol_version: 1,
+            pid: std::process::id(),
+            bind_address: "127.0.0.1".parse().unwrap(),
+            port: 9555,
+        };
+        let advertiser = Advertiser::start(&service).unwrap();
+
+        let browser = ServiceDaemon::new().unwrap();
+        browser.disable_interface(IfKind::All).unwrap();
+        browser.enable_interface(IfKind::LoopbackV4).unwrap();
+        let events = browser.browse(SERVICE_TYPE).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let resolved = loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match events.recv_timeout(remaining) {
+                Ok(ServiceEvent::ServiceResolved(info))
+                    if info.get_fullname() == advertiser.fullname() =>
+                {
+                    break info;
+                }
+                Ok(_) => continue,
+                Err(e) => panic!("service was not resolved: {e}"),
+            }
+        };
+
+        assert_eq!(resolved.get_port(), 9555);
+        assert_eq!(
+            resolved.get_property_val_str(TXT_IDENTIFIER),
+            Some("com.example.mdns-test")
+        );
+        assert_eq!(resolved.get_property_val_str(TXT_VERSION), Some("1.2.3"));
+        assert_eq!(resolved.get_property_val_str(TXT_PROTOCOL), Some("1"));
+
+        let _ = browser.shutdown();
+        advertiser.shutdown();
+    }
+}
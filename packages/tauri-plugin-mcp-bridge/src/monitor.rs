@@ -2,9 +2,21 @@
 //!
 //! This module provides functionality to monitor and capture Tauri IPC events,
 //! including command invocations, arguments, results, and timing information.
+//! Captured durations are aggregated per command by [`IPCMonitor::stats`], and
+//! calls slower than a threshold are broadcast as `ipc_slow_call` messages.
+//! [Redaction rules](crate::redaction) are applied before events are stored.
+//!
+//! Besides the default monitor, named [`MonitorSession`]s capture the calls of
+//! chosen windows (or of every window, including ones opened later) into their
+//! own buffers, so independent clients do not clear each other's captures.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+use crate::ipc_faults::InjectedFault;
+use crate::redaction::{RedactionRule, Redactor};
 
 /// Represents a captured IPC event.
 ///
@@ -20,6 +32,13 @@ use std::sync::{Arc, Mutex};
 /// * `result` - Optional JSON result returned by the command
 /// * `error` - Optional error message if the command failed
 /// * `duration_ms` - Optional execution duration in milliseconds
+/// * `source` - Where the call was captured: `"backend"` for
+///   [`ipc_capture`](crate::ipc_capture), `"mock"` for calls answered by a
+///   [mock rule](crate::ipc_mock), `"fault"` for calls failed or left
+///   unanswered by a [fault rule](crate::ipc_faults), `"frontend"` for bridge.js
+/// * `fault` - The fault a [fault rule](crate::ipc_faults) injected into the
+///   call, if any
+/// * `window` - Label of the window that made the call, if known
 ///
 /// # Examples
 ///
@@ -27,16 +46,12 @@ use std::sync::{Arc, Mutex};
 /// use tauri_plugin_mcp_bridge::monitor::IPCEvent;
 /// use serde_json::json;
 ///
-/// let event = IPCEvent {
-///     timestamp: 1234567890,
-///     command: "greet".to_string(),
-///     args: json!({"name": "World"}),
-///     result: Some(json!({"message": "Hello, World!"})),
-///     error: None,
-///     duration_ms: Some(5.2),
-/// };
+/// let mut event = IPCEvent::new(1234567890, "greet", json!({"name": "World"}));
+/// event.result = Some(json!({"message": "Hello, World!"}));
+/// event.duration_ms = Some(5.2);
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct IPCEvent {
     pub timestamp: u64,
     pub command: String,
@@ -44,6 +59,30 @@ pub struct IPCEvent {
     pub result: Option<serde_json::Value>,
     pub error: Option<String>,
     pub duration_ms: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault: Option<InjectedFault>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<String>,
+}
+
+impl IPCEvent {
+    /// Creates an event for a call to `command`, with no result, error,
+    /// timing or other details.
+    pub fn new(timestamp: u64, command: impl Into<String>, args: serde_json::Value) -> Self {
+        Self {
+            timestamp,
+            command: command.into(),
+            args,
+            result: None,
+            error: None,
+            duration_ms: None,
+            source: None,
+            fault: None,
+            window: None,
+        }
+    }
 }
 
 /// IPC monitor for capturing Tauri command invocations.
@@ -71,6 +110,136 @@ pub struct IPCEvent {
 pub struct IPCMonitor {
     pub enabled: bool,
     pub events: Vec<IPCEvent>,
+    windows: Option<Vec<String>>,
+    sessions: Vec<MonitorSession>,
+    slow_threshold_ms: Option<f64>,
+    alerts: Option<broadcast::Sender<String>>,
+    redactor: Redactor,
+}
+
+/// A named monitoring session with its own event buffer.
+#[derive(Debug, Clone)]
+pub struct MonitorSession {
+    /// Unique identifier for this session
+    pub id: String,
+    /// Labels of the captured windows, or `None` for every window
+    pub windows: Option<Vec<String>>,
+    /// Whether the session is capturing
+    pub active: bool,
+    /// Unix timestamp in milliseconds when the session started
+    pub started_at: u64,
+    /// Calls captured by this session
+    pub events: Vec<IPCEvent>,
+}
+
+/// Summary of a [`MonitorSession`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorSessionInfo {
+    /// Unique identifier for the session
+    pub session_id: String,
+    /// Labels of the captured windows, or `None` for every window
+    pub windows: Option<Vec<String>>,
+    /// Whether the session is capturing
+    pub active: bool,
+    /// Unix timestamp in milliseconds when the session started
+    pub started_at: u64,
+    /// Number of calls captured
+    pub event_count: usize,
+}
+
+impl MonitorSession {
+    /// Returns a summary of the session.
+    pub fn info(&self) -> MonitorSessionInfo {
+        MonitorSessionInfo {
+            session_id: self.id.clone(),
+            windows: self.windows.clone(),
+            active: self.active,
+            started_at: self.started_at,
+            event_count: self.events.len(),
+        }
+    }
+}
+
+/// Returns whether a monitor targeting `windows` captures calls from `window`.
+///
+/// Calls from an unknown window are only captured by monitors targeting every
+/// window.
+fn targets(windows: &Option<Vec<String>>, window: Option<&str>) -> bool {
+    match (windows, window) {
+        (None, _) => true,
+        (Some(windows), Some(window)) => windows.iter().any(|w| w == window),
+        (Some(_), None) => false,
+    }
+}
+
+/// Message type of slow-call alert broadcasts.
+pub const SLOW_CALL_TYPE: &str = "ipc_slow_call";
+
+/// An `ipc_slow_call` broadcast message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowCallAlert {
+    /// Always `"ipc_slow_call"`
+    #[serde(rename = "type")]
+    pub message_type: String,
+    /// Name of the slow command
+    pub command: String,
+    /// How long the call took, in milliseconds
+    pub duration_ms: f64,
+    /// Threshold the call exceeded, in milliseconds
+    pub threshold_ms: f64,
+    /// Where the call was captured (see [`IPCEvent`])
+    pub source: Option<String>,
+    /// Unix timestamp in milliseconds when the call was made
+    pub timestamp: u64,
+}
+
+/// Duration statistics for one command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandStats {
+    /// Command name
+    pub command: String,
+    /// Number of calls
+    pub count: usize,
+    /// Number of calls that failed
+    pub errors: usize,
+    /// Number of calls slower than the slow-call threshold
+    pub slow: usize,
+    /// Median duration in milliseconds
+    pub p50_ms: Option<f64>,
+    /// 90th percentile duration in milliseconds
+    pub p90_ms: Option<f64>,
+    /// 99th percentile duration in milliseconds
+    pub p99_ms: Option<f64>,
+    /// Longest duration in milliseconds
+    pub max_ms: Option<f64>,
+}
+
+/// Duration statistics for the calls captured since the monitor started.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IPCStats {
+    /// Number of calls
+    pub count: usize,
+    /// Number of calls that failed
+    pub errors: usize,
+    /// Number of calls slower than the threshold
+    pub slow: usize,
+    /// Slow-call threshold in milliseconds, if one is set
+    pub slow_threshold_ms: Option<f64>,
+    /// Per-command statistics, sorted by command name
+    pub commands: Vec<CommandStats>,
+}
+
+/// Returns the nearest-rank percentile `p` (0 to 100) of sorted values.
+fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
 }
 
 impl Default for IPCMonitor {
@@ -98,7 +267,61 @@ impl IPCMonitor {
         Self {
             enabled: false,
             events: Vec::new(),
+            windows: None,
+            sessions: Vec::new(),
+            slow_threshold_ms: None,
+            alerts: None,
+            redactor: Redactor::new(),
+        }
+    }
+
+    /// Sets the duration above which calls count as slow, or `None` to
+    /// disable slow-call detection.
+    pub fn set_slow_threshold(&mut self, threshold_ms: Option<f64>) {
+        self.slow_threshold_ms = threshold_ms;
+    }
+
+    /// Returns the slow-call threshold in milliseconds, if one is set.
+    pub fn slow_threshold(&self) -> Option<f64> {
+        self.slow_threshold_ms
+    }
+
+    /// Sets the channel slow-call alerts are broadcast on.
+    pub fn set_alert_sender(&mut self, sender: broadcast::Sender<String>) {
+        self.alerts = Some(sender);
+    }
+
+    /// Returns the redaction rules applied to captured events.
+    pub fn redaction_rules(&self) -> Vec<RedactionRule> {
+        self.redactor.get_all()
+    }
+
+    /// Returns the key patterns bridge.js masks in console logs.
+    pub fn console_redaction_keys(&self) -> Vec<String> {
+        self.redactor.console_key_patterns()
+    }
+
+    /// Adds a redaction rule, replacing any rule with the same ID, and
+    /// applies it to the events captured so far.
+    pub fn add_redaction_rule(&mut self, rule: RedactionRule) -> Result<(), String> {
+        self.redactor.add(rule.clone())?;
+        let session_events = self.sessions.iter_mut().flat_map(|s| s.events.iter_mut());
+        for event in self.events.iter_mut().chain(session_events) {
+            rule.apply(event);
         }
+        Ok(())
+    }
+
+    /// Removes a redaction rule by ID, returning it if it existed.
+    ///
+    /// Values already masked stay masked.
+    pub fn remove_redaction_rule(&mut self, id: &str) -> Option<RedactionRule> {
+        self.redactor.remove(id)
+    }
+
+    /// Removes all redaction rules, returning how many there were.
+    pub fn clear_redaction_rules(&mut self) -> usize {
+        self.redactor.clear()
     }
 
     /// Starts IPC monitoring and clears previous events.
@@ -116,10 +339,143 @@ impl IPCMonitor {
     /// assert!(monitor.enabled);
     /// ```
     pub fn start(&mut self) {
+        self.start_for(None);
+    }
+
+    /// Starts IPC monitoring of the given windows, or of every window when
+    /// `windows` is `None`, and clears previous events.
+    pub fn start_for(&mut self, windows: Option<Vec<String>>) {
         self.enabled = true;
+        self.windows = windows;
         self.events.clear();
     }
 
+    /// Starts a monitoring session capturing the given windows, or every
+    /// window (including ones opened later) when `windows` is `None`.
+    ///
+    /// Starting a session with the ID of an existing one restarts it with an
+    /// empty buffer. A session ID is generated if `id` is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tauri_plugin_mcp_bridge::monitor::IPCMonitor;
+    ///
+    /// let mut monitor = IPCMonitor::new();
+    /// let id = monitor
+    ///     .start_session(None, Some(vec!["main".to_string()]))
+    ///     .session_id;
+    /// assert!(monitor.is_capturing("main"));
+    /// assert!(!monitor.is_capturing("settings"));
+    /// monitor.end_session(&id).unwrap();
+    /// ```
+    pub fn start_session(
+        &mut self,
+        id: Option<String>,
+        windows: Option<Vec<String>>,
+    ) -> MonitorSessionInfo {
+        let id = id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let session = MonitorSession {
+            id: id.clone(),
+            windows,
+            active: true,
+            started_at: current_timestamp(),
+            events: Vec::new(),
+        };
+        let info = session.info();
+        match self.sessions.iter_mut().find(|s| s.id == id) {
+            Some(existing) => *existing = session,
+            None => self.sessions.push(session),
+        }
+        info
+    }
+
+    /// Adds a stopped monitoring session holding previously recorded calls,
+    /// such as those of an imported session document.
+    ///
+    /// The calls are redacted like captured ones, and the default monitor and
+    /// other sessions are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tauri_plugin_mcp_bridge::monitor::{IPCEvent, IPCMonitor};
+    /// use serde_json::json;
+    ///
+    /// let mut monitor = IPCMonitor::new();
+    /// let info = monitor.load_session(vec![IPCEvent::new(1234567890, "greet", json!({}))]);
+    /// assert!(!info.active);
+    /// assert_eq!(monitor.session_events(&info.session_id).unwrap().len(), 1);
+    /// assert!(monitor.get_events().is_empty());
+    /// ```
+    pub fn load_session(&mut self, mut events: Vec<IPCEvent>) -> MonitorSessionInfo {
+        for event in &mut events {
+            self.redactor.redact(event);
+        }
+        let session = MonitorSession {
+            id: uuid::Uuid::new_v4().to_string(),
+            windows: None,
+            active: false,
+            started_at: events
+                .iter()
+                .map(|e| e.timestamp)
+                .min()
+                .unwrap_or_else(current_timestamp),
+            events,
+        };
+        let info = session.info();
+        self.sessions.push(session);
+        info
+    }
+
+    /// Stops a monitoring session, keeping its events.
+    pub fn stop_session(&mut self, id: &str) -> Result<MonitorSessionInfo, String> {
+        let session = self.session_mut(id)?;
+        session.active = false;
+        Ok(session.info())
+    }
+
+    /// Ends a monitoring session, returning its events.
+    pub fn end_session(&mut self, id: &str) -> Result<Vec<IPCEvent>, String> {
+        let index = self
+            .sessions
+            .iter()
+            .position(|s| s.id == id)
+            .ok_or_else(|| unknown_session(id))?;
+        Ok(self.sessions.remove(index).events)
+    }
+
+    /// Returns a copy of the events a monitoring session captured.
+    pub fn session_events(&self, id: &str) -> Result<Vec<IPCEvent>, String> {
+        self.sessions
+            .iter()
+            .find(|s| s.id == id)
+            .map(|s| s.events.clone())
+            .ok_or_else(|| unknown_session(id))
+    }
+
+    /// Returns a summary of every monitoring session.
+    pub fn sessions(&self) -> Vec<MonitorSessionInfo> {
+        self.sessions.iter().map(MonitorSession::info).collect()
+    }
+
+    fn session_mut(&mut self, id: &str) -> Result<&mut MonitorSession, String> {
+        self.sessions
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| unknown_session(id))
+    }
+
+    /// Returns whether the default monitor or an active session captures
+    /// calls from the window labeled `window`.
+    pub fn is_capturing(&self, window: &str) -> bool {
+        (self.enabled && targets(&self.windows, Some(window)))
+            || self
+                .sessions
+                .iter()
+                .any(|s| s.active && targets(&s.windows, Some(window)))
+    }
+
     /// Stops IPC monitoring.
     ///
     /// Disables the monitor, preventing new events from being captured.
@@ -142,7 +498,11 @@ impl IPCMonitor {
     /// Adds an IPC event to the monitor if monitoring is enabled.
     ///
     /// Events are only added when the monitor is enabled. If disabled,
-    /// the event is silently ignored.
+    /// the event is silently ignored. Redaction rules mask the event's
+    /// arguments, result and error before it is stored.
+    ///
+    /// The event is also added to every active session that targets the
+    /// event's window.
     ///
     /// # Arguments
     ///
@@ -157,24 +517,111 @@ impl IPCMonitor {
     /// let mut monitor = IPCMonitor::new();
     /// monitor.start();
     ///
-    /// let event = IPCEvent {
-    ///     timestamp: 1234567890,
-    ///     command: "test".to_string(),
-    ///     args: json!({}),
-    ///     result: None,
-    ///     error: None,
-    ///     duration_ms: None,
-    /// };
+    /// let event = IPCEvent::new(1234567890, "test", json!({}));
     ///
     /// monitor.add_event(event);
     /// assert_eq!(monitor.get_events().len(), 1);
     /// ```
-    pub fn add_event(&mut self, event: IPCEvent) {
-        if self.enabled {
+    pub fn add_event(&mut self, mut event: IPCEvent) {
+        let window = event.window.clone();
+        let to_default = self.enabled && targets(&self.windows, window.as_deref());
+        let capturing = self
+            .sessions
+            .iter()
+            .any(|s| s.active && targets(&s.windows, window.as_deref()));
+        if !to_default && !capturing {
+            return;
+        }
+        self.redactor.redact(&mut event);
+        if let (Some(threshold_ms), Some(duration_ms), Some(alerts)) =
+            (self.slow_threshold_ms, event.duration_ms, &self.alerts)
+        {
+            if duration_ms > threshold_ms {
+                let alert = SlowCallAlert {
+                    message_type: SLOW_CALL_TYPE.to_string(),
+                    command: event.command.clone(),
+                    duration_ms,
+                    threshold_ms,
+                    source: event.source.clone(),
+                    timestamp: event.timestamp,
+                };
+                if let Ok(message) = serde_json::to_string(&alert) {
+                    // No receivers just means no client is connected
+                    let _ = alerts.send(message);
+                }
+            }
+        }
+        for session in &mut self.sessions {
+            if session.active && targets(&session.windows, window.as_deref()) {
+                session.events.push(event.clone());
+            }
+        }
+        if to_default {
             self.events.push(event);
         }
     }
 
+    /// Aggregates the captured events per command.
+    ///
+    /// Percentiles use the nearest-rank method over calls with a recorded
+    /// duration; calls without one (such as hung calls) only add to the counts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tauri_plugin_mcp_bridge::monitor::{IPCEvent, IPCMonitor};
+    /// use serde_json::json;
+    ///
+    /// let mut monitor = IPCMonitor::new();
+    /// monitor.start();
+    /// for duration_ms in [4.0, 12.0] {
+    ///     let mut event = IPCEvent::new(1234567890, "greet", json!({}));
+    ///     event.duration_ms = Some(duration_ms);
+    ///     monitor.add_event(event);
+    /// }
+    ///
+    /// let stats = monitor.stats();
+    /// assert_eq!(stats.commands[0].count, 2);
+    /// assert_eq!(stats.commands[0].max_ms, Some(12.0));
+    /// ```
+    pub fn stats(&self) -> IPCStats {
+        let is_slow = |event: &IPCEvent| match (self.slow_threshold_ms, event.duration_ms) {
+            (Some(threshold_ms), Some(duration_ms)) => duration_ms > threshold_ms,
+            _ => false,
+        };
+
+        let mut by_command: BTreeMap<&str, Vec<&IPCEvent>> = BTreeMap::new();
+        for event in &self.events {
+            by_command.entry(&event.command).or_default().push(event);
+        }
+
+        let commands: Vec<CommandStats> = by_command
+            .into_iter()
+            .map(|(command, events)| {
+                let mut durations: Vec<f64> = events.iter().filter_map(|e| e.duration_ms).collect();
+                durations.sort_by(f64::total_cmp);
+                CommandStats {
+                    command: command.to_string(),
+                    count: events.len(),
+                    errors: events.iter().filter(|e| e.error.is_some()).count(),
+                    slow: events.iter().filter(|e| is_slow(e)).count(),
+                    p50_ms: percentile(&durations, 50.0),
+                    p90_ms: percentile(&durations, 90.0),
+                    p99_ms: percentile(&durations, 99.0),
+                    max_ms: durations.last().copied(),
+                }
+            })
+            .collect();
+
+        IPCStats {
+            count: self.events.len(),
+            errors: commands.iter().map(|c| c.errors).sum(),
+            slow: commands.iter().map(|c| c.slow).sum(),
+            slow_threshold_ms: self.slow_threshold_ms,
+            commands,
+        }
+    }
+
     /// Returns a copy of all captured events.
     ///
     /// # Returns
@@ -195,6 +642,10 @@ impl IPCMonitor {
     }
 }
 
+fn unknown_session(id: &str) -> String {
+    format!("Unknown monitor session '{id}'")
+}
+
 /// Type alias for thread-safe IPC monitor state.
 ///
 /// This type wraps `IPCMonitor` in an `Arc<Mutex<>>` to allow safe
@@ -222,3 +673,110 @@ pub fn current_timestamp() -> u64 {
         .unwrap()
         .as_millis() as u64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(command: &str, duration_ms: f64, error: bool) -> IPCEvent {
+        IPCEvent {
+            timestamp: 1,
+            command: command.to_string(),
+            args: json!({}),
+            result: None,
+            error: error.then(|| "failed".to_string()),
+            duration_ms: Some(duration_ms),
+            source: Some("backend".to_string()),
+            fault: None,
+            window: None,
+        }
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let sorted: Vec<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(percentile(&sorted, 50.0), Some(50.0));
+        assert_eq!(percentile(&sorted, 99.0), Some(99.0));
+        assert_eq!(percentile(&[7.0], 90.0), Some(7.0));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn test_stats_and_slow_call_alerts() {
+        let (tx, mut rx) = broadcast::channel(8);
+        let mut monitor = IPCMonitor::new();
+        monitor.set_alert_sender(tx);
+        monitor.set_slow_threshold(Some(100.0));
+        monitor.start();
+
+        monitor.add_event(event("save", 20.0, false));
+        monitor.add_event(event("save", 250.0, true));
+        monitor.add_event(event("load", 5.0, false));
+
+        let stats = monitor.stats();
+        assert_eq!((stats.count, stats.errors, stats.slow), (3, 1, 1));
+        let save = &stats.commands[1];
+        assert_eq!(save.command, "save");
+        assert_eq!((save.p50_ms, save.max_ms), (Some(20.0), Some(250.0)));
+
+        let alert: SlowCallAlert = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(alert.message_type, SLOW_CALL_TYPE);
+        assert_eq!((alert.command.as_str(), alert.duration_ms), ("save", 250.0));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_redaction_before_and_after_capture() {
+        let mut monitor = IPCMonitor::new();
+        monitor.start();
+        let mut login = event("login", 1.0, false);
+        login.args = json!({ "password": "hunter2" });
+        monitor.add_event(login.clone());
+
+        let rule = RedactionRule {
+            id: "password".into(),
+            keys: vec!["password".into()],
+            ..Default::default()
+        };
+        monitor.add_redaction_rule(rule).unwrap();
+        monitor.add_event(login);
+        for event in monitor.get_events() {
+            assert_eq!(event.args["password"], crate::redaction::REDACTED);
+        }
+        assert_eq!(monitor.console_redaction_keys(), vec!["password"]);
+        assert_eq!(monitor.clear_redaction_rules(), 1);
+    }
+
+    #[test]
+    fn test_sessions_capture_their_windows() {
+        let in_window = |window: &str| IPCEvent {
+            window: Some(window.to_string()),
+            ..event("save", 1.0, false)
+        };
+        let mut monitor = IPCMonitor::new();
+        let main = monitor
+            .start_session(Some("main-only".into()), Some(vec!["main".into()]))
+            .session_id;
+        let all = monitor.start_session(None, None).session_id;
+
+        monitor.add_event(in_window("main"));
+        monitor.add_event(in_window("settings"));
+        monitor.add_event(event("load", 1.0, false));
+        assert!(monitor.get_events().is_empty());
+        assert_eq!(monitor.session_events(&main).unwrap().len(), 1);
+        assert_eq!(monitor.session_events(&all).unwrap().len(), 3);
+
+        // Restarting the default monitor leaves sessions alone
+        monitor.start();
+        monitor.stop_session(&main).unwrap();
+        monitor.add_event(in_window("main"));
+        assert_eq!(monitor.get_events().len(), 1);
+        assert_eq!(monitor.session_events(&main).unwrap().len(), 1);
+        assert!(!monitor.sessions()[0].active);
+
+        assert_eq!(monitor.end_session(&all).unwrap().len(), 4);
+        assert!(monitor.session_events(&all).is_err());
+        assert_eq!(monitor.sessions().len(), 1);
+    }
+}
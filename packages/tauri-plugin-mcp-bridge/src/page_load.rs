@@ -0,0 +1,76 @@
+//! Page-ready tracking for webviews.
+//!
+//! bridge.js calls `request_script_injection` once the bridge is ready on a
+//! page (and again on `popstate`). Each call is recorded here so navigation
+//! commands can wait for the next page to finish loading.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::watch;
+
+/// The most recent page-ready report for a webview.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageLoad {
+    /// Number of page-ready reports so far
+    pub count: u64,
+    /// URL of the page at the time of the report
+    pub url: Option<String>,
+}
+
+/// Tracks page-ready reports per webview label.
+#[derive(Debug, Default)]
+pub struct PageLoadTracker {
+    webviews: Mutex<HashMap<String, watch::Sender<PageLoad>>>,
+}
+
+impl PageLoadTracker {
+    /// Creates an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that the bridge became ready on a page in `label`.
+    pub fn page_loaded(&self, label: &str, url: Option<String>) {
+        let mut webviews = self.webviews.lock().unwrap_or_else(|e| e.into_inner());
+        let sender = webviews
+            .entry(label.to_string())
+            .or_insert_with(|| watch::channel(PageLoad::default()).0);
+        sender.send_modify(|load| {
+            load.count += 1;
+            load.url = url;
+        });
+    }
+
+    /// Subscribes to page-ready reports for `label`.
+    ///
+    /// The receiver's current value is marked as seen, so `changed()` resolves
+    /// on the next report.
+    pub fn subscribe(&self, label: &str) -> watch::Receiver<PageLoad> {
+        let mut webviews = self.webviews.lock().unwrap_or_else(|e| e.into_inner());
+        webviews
+            .entry(label.to_string())
+            .or_insert_with(|| watch::channel(PageLoad::default()).0)
+            .subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscribe_sees_next_load() {
+        let tracker = PageLoadTracker::new();
+        tracker.page_loaded("main", Some("tauri://localhost/".to_string()));
+
+        let mut rx = tracker.subscribe("main");
+        assert_eq!(rx.borrow().count, 1);
+        assert!(!rx.has_changed().unwrap());
+
+        tracker.page_loaded("main", Some("tauri://localhost/settings".to_string()));
+        rx.changed().await.unwrap();
+        let load = rx.borrow_and_update().clone();
+        assert_eq!(load.count, 2);
+        assert_eq!(load.url.as_deref(), Some("tauri://localhost/settings"));
+    }
+}
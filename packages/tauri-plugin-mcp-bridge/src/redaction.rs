@@ -0,0 +1,365 @@
+//! Redaction of sensitive values in captured IPC calls and console logs.
+//!
+//! A rule masks values by key name (glob patterns such as `*token*`, matched
+//! case-insensitively at any depth) or by JSON path (such as
+//! `$.args.card.number` or `$.result.sessions[*].secret`), optionally only for
+//! commands matching a glob. The IPC monitor applies the rules before events
+//! are stored or streamed, and bridge.js applies the key patterns of rules
+//! without a command filter to objects logged to the console.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::event_tap::matches_pattern;
+use crate::monitor::IPCEvent;
+
+/// Value that replaces redacted data.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A redaction rule.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedactionRule {
+    /// Unique identifier for this rule
+    pub id: String,
+    /// Only redact calls to commands matching this glob pattern
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Mask values whose key matches one of these glob patterns, ignoring case
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<String>,
+    /// Mask values at these JSON paths, rooted at the event (`$.args`,
+    /// `$.result` or `$.error`); `*` matches any key or index
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+}
+
+/// A step in a JSON path.
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+    Any,
+}
+
+/// Parses a JSON path such as `$.args.items[*].token`.
+fn parse_path(path: &str) -> Result<Vec<Segment>, String> {
+    let invalid = || format!("Invalid JSON path '{path}': expected e.g. $.args.user.password");
+    let mut rest = path.strip_prefix('$').ok_or_else(invalid)?;
+    let mut segments = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            let key = &after[..end];
+            if key.is_empty() {
+                return Err(invalid());
+            }
+            segments.push(if key == "*" {
+                Segment::Any
+            } else {
+                Segment::Key(key.to_string())
+            });
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(invalid)?;
+            let index = after[..end].trim_matches(|c| c == '\'' || c == '"');
+            segments.push(match index {
+                "*" => Segment::Any,
+                _ => match index.parse::<usize>() {
+                    Ok(index) if index.to_string() == after[..end] => Segment::Index(index),
+                    _ => Segment::Key(index.to_string()),
+                },
+            });
+            rest = &after[end + 1..];
+        } else {
+            return Err(invalid());
+        }
+    }
+
+    if segments.is_empty() {
+        return Err(invalid());
+    }
+    Ok(segments)
+}
+
+/// Masks the values at `segments` below `value`.
+fn redact_path(value: &mut Value, segments: &[Segment]) {
+    let Some((segment, rest)) = segments.split_first() else {
+        *value = Value::from(REDACTED);
+        return;
+    };
+    match (segment, value) {
+        (Segment::Key(key), Value::Object(map)) => {
+            if let Some(child) = map.get_mut(key) {
+                redact_path(child, rest);
+            }
+        }
+        (Segment::Index(index), Value::Array(items)) => {
+            if let Some(child) = items.get_mut(*index) {
+                redact_path(child, rest);
+            }
+        }
+        (Segment::Any, Value::Object(map)) => {
+            map.values_mut().for_each(|child| redact_path(child, rest));
+        }
+        (Segment::Any, Value::Array(items)) => {
+            items.iter_mut().for_each(|child| redact_path(child, rest));
+        }
+        _ => {}
+    }
+}
+
+/// Returns whether `key` matches a key pattern, ignoring case.
+pub fn key_matches(pattern: &str, key: &str) -> bool {
+    matches_pattern(&pattern.to_lowercase(), &key.to_lowercase())
+}
+
+/// Masks every value below `value` whose key matches one of `patterns`.
+pub fn redact_keys(value: &mut Value, patterns: &[String]) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if patterns.iter().any(|p| key_matches(p, key)) {
+                    *child = Value::from(REDACTED);
+                } else {
+                    redact_keys(child, patterns);
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| redact_keys(item, patterns)),
+        _ => {}
+    }
+}
+
+/// Returns whether any value below `value` was masked.
+pub fn contains_redacted(value: &Value) -> bool {
+    match value {
+        Value::String(s) => s == REDACTED,
+        Value::Array(items) => items.iter().any(contains_redacted),
+        Value::Object(map) => map.values().any(contains_redacted),
+        _ => false,
+    }
+}
+
+impl RedactionRule {
+    /// Checks that the rule is well-formed.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.is_empty() {
+            return Err("Redaction rules need an id".to_string());
+        }
+        if self.keys.is_empty() && self.paths.is_empty() {
+            return Err("A redaction rule needs keys or paths".to_string());
+        }
+        for path in &self.paths {
+            parse_path(path)?;
+        }
+        Ok(())
+    }
+
+    /// Masks the values this rule covers in an event.
+    pub fn apply(&self, event: &mut IPCEvent) {
+        if let Some(command) = &self.command {
+            if !matches_pattern(command, &event.command) {
+                return;
+            }
+        }
+
+        redact_keys(&mut event.args, &self.keys);
+        if let Some(result) = event.result.as_mut() {
+            redact_keys(result, &self.keys);
+        }
+
+        for segments in self.paths.iter().filter_map(|p| parse_path(p).ok()) {
+            match segments.split_first() {
+                Some((Segment::Key(root), rest)) if root == "args" => {
+                    redact_path(&mut event.args, rest);
+                }
+                Some((Segment::Key(root), rest)) if root == "result" => {
+                    if let Some(result) = event.result.as_mut() {
+                        redact_path(result, rest);
+                    }
+                }
+                Some((Segment::Key(root), [])) if root == "error" && event.error.is_some() => {
+                    event.error = Some(REDACTED.to_string());
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Redaction rules, applied in the order they were added.
+#[derive(Debug, Default)]
+pub struct Redactor {
+    rules: Vec<RedactionRule>,
+}
+
+impl Redactor {
+    /// Creates a redactor without rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule, replacing (in place) any rule with the same ID.
+    pub fn add(&mut self, rule: RedactionRule) -> Result<(), String> {
+        rule.validate()?;
+        match self.rules.iter_mut().find(|r| r.id == rule.id) {
+            Some(existing) => *existing = rule,
+            None => self.rules.push(rule),
+        }
+        Ok(())
+    }
+
+    /// Removes a rule by ID, returning it if it existed.
+    pub fn remove(&mut self, id: &str) -> Option<RedactionRule> {
+        let index = self.rules.iter().position(|r| r.id == id)?;
+        Some(self.rules.remove(index))
+    }
+
+    /// Removes all rules, returning how many there were.
+    pub fn clear(&mut self) -> usize {
+        let count = self.rules.len();
+        self.rules.clear();
+        count
+    }
+
+    /// Returns all rules.
+    pub fn get_all(&self) -> Vec<RedactionRule> {
+        self.rules.clone()
+    }
+
+    /// Returns true if there are no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Masks the values every rule covers in an event.
+    pub fn redact(&self, event: &mut IPCEvent) {
+        for rule in &self.rules {
+            rule.apply(event);
+        }
+    }
+
+    /// Returns the key patterns bridge.js uses to redact console logs.
+    pub fn console_key_patterns(&self) -> Vec<String> {
+        console_key_patterns(&self.rules)
+    }
+}
+
+/// Returns the key patterns of rules that apply to every command.
+pub fn console_key_patterns(rules: &[RedactionRule]) -> Vec<String> {
+    rules
+        .iter()
+        .filter(|rule| rule.command.is_none())
+        .flat_map(|rule| rule.keys.iter().cloned())
+        .collect()
+}
+
+/// Builds the script that hands console key patterns to bridge.js.
+pub(crate) fn console_patterns_script(patterns: &[String]) -> String {
+    format!(
+        "window.__MCP_REDACT_KEYS__ = {};",
+        serde_json::to_string(patterns).unwrap_or_else(|_| "[]".to_string())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(command: &str, args: Value, result: Value) -> IPCEvent {
+        IPCEvent {
+            timestamp: 1,
+            command: command.to_string(),
+            args,
+            result: Some(result),
+            error: Some("token abc rejected".to_string()),
+            duration_ms: None,
+            source: None,
+            fault: None,
+            window: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse_path("$.args.items[*].token").unwrap(),
+            vec![
+                Segment::Key("args".into()),
+                Segment::Key("items".into()),
+                Segment::Any,
+                Segment::Key("token".into())
+            ]
+        );
+        assert_eq!(parse_path("$.result[0]").unwrap()[1], Segment::Index(0));
+        assert!(parse_path("args.token").is_err());
+        assert!(parse_path("$").is_err());
+        assert!(parse_path("$.args[").is_err());
+    }
+
+    #[test]
+    fn test_key_patterns_mask_at_any_depth() {
+        let mut redactor = Redactor::new();
+        redactor
+            .add(RedactionRule {
+                id: "secrets".into(),
+                keys: vec!["*token*".into(), "password".into()],
+                ..Default::default()
+            })
+            .unwrap();
+
+        let mut login = event(
+            "login",
+            json!({ "user": "ann", "Password": "hunter2", "nested": [{ "refreshToken": "r" }] }),
+            json!({ "accessToken": "a", "expires": 60 }),
+        );
+        redactor.redact(&mut login);
+        assert_eq!(
+            login.args,
+            json!({ "user": "ann", "Password": REDACTED, "nested": [{ "refreshToken": REDACTED }] })
+        );
+        assert_eq!(
+            login.result,
+            Some(json!({ "accessToken": REDACTED, "expires": 60 }))
+        );
+        assert_eq!(redactor.console_key_patterns().len(), 2);
+    }
+
+    #[test]
+    fn test_paths_apply_per_command() {
+        let mut redactor = Redactor::new();
+        redactor
+            .add(RedactionRule {
+                id: "payments".into(),
+                command: Some("pay_*".into()),
+                paths: vec![
+                    "$.args.card.number".into(),
+                    "$.result.receipts[*].last4".into(),
+                    "$.error".into(),
+                ],
+                ..Default::default()
+            })
+            .unwrap();
+
+        let args = json!({ "card": { "number": "4242", "cvc": "1" } });
+        let result = json!({ "receipts": [{ "last4": "4242" }, { "last4": "1111" }] });
+        let mut payment = event("pay_invoice", args.clone(), result.clone());
+        redactor.redact(&mut payment);
+        assert_eq!(
+            payment.args["card"],
+            json!({ "number": REDACTED, "cvc": "1" })
+        );
+        assert_eq!(payment.result.unwrap()["receipts"][1]["last4"], REDACTED);
+        assert_eq!(payment.error.as_deref(), Some(REDACTED));
+
+        let mut other = event("refund", args.clone(), result);
+        redactor.redact(&mut other);
+        assert_eq!(other.args, args);
+        assert!(redactor.console_key_patterns().is_empty());
+    }
+}
@@ -0,0 +1,52 @@
+//! Startup status of the bridge.
+//!
+//! The plugin binds its listeners before setup returns and records the
+//! outcome in a [`BridgeStatus`] managed state, so the app (or its tests) can
+//! check where the bridge is listening, or why it is not, instead of relying
+//! on log output.
+
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+/// Where the bridge is listening, and any errors hit while starting it.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use tauri::Manager;
+/// use tauri_plugin_mcp_bridge::BridgeStatus;
+///
+/// let status = app.state::<BridgeStatus>();
+/// match status.tcp_address {
+///     Some(addr) => println!("MCP bridge on {addr}"),
+///     None => eprintln!("MCP bridge failed: {:?}", status.errors),
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BridgeStatus {
+    /// The address the TCP listener is bound to, including the actual port
+    pub tcp_address: Option<SocketAddr>,
+    /// The local socket path (or named pipe name) the bridge is bound to
+    pub socket_path: Option<PathBuf>,
+    /// Errors that prevented a configured listener from starting
+    pub errors: Vec<String>,
+}
+
+impl BridgeStatus {
+    /// Returns the bound TCP port, if the TCP listener started.
+    pub fn port(&self) -> Option<u16> {
+        self.tcp_address.map(|addr| addr.port())
+    }
+
+    /// Returns true if at least one listener is accepting connections.
+    pub fn is_listening(&self) -> bool {
+        self.tcp_address.is_some() || self.socket_path.is_some()
+    }
+
+    /// Returns true if every configured listener started.
+    pub fn is_healthy(&self) -> bool {
+        self.is_listening() && self.errors.is_empty()
+    }
+}
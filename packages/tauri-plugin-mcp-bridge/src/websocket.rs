@@ -5,16 +5,31 @@
 //! to all connected clients and can receive commands from them.
 
 use crate::commands::{self, resolve_window_with_context, ScriptExecutor, WindowContext};
-use crate::logging::{mcp_log_error, mcp_log_info};
+use crate::ipc_faults::{FaultRule, SharedFaultInjector};
+use crate::ipc_mock::{MockRule, SharedMockRegistry};
+use crate::local_socket::LocalListener;
+use crate::logging::{mcp_log_debug, mcp_log_error, mcp_log_info, mcp_log_warn};
+use crate::mcp;
+use crate::monitor::IPCMonitorState;
+use crate::redaction::{console_patterns_script, RedactionRule};
 use crate::script_registry::{ScriptEntry, ScriptType, SharedScriptRegistry};
 use futures_util::{SinkExt, StreamExt};
 use serde_json::{self, Value};
+use std::io;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use tauri::{AppHandle, Manager, Runtime, WebviewWindow};
-use tokio::net::{TcpListener, TcpStream};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
 use tokio::sync::{broadcast, mpsc};
 use tokio_tungstenite::{accept_async, tungstenite::Message};
 
+/// Version of the WebSocket command/response protocol spoken by the bridge.
+///
+/// Published in discovery records so clients can detect incompatible
+/// instances before connecting.
+pub const PROTOCOL_VERSION: u32 = 1;
+
 /// WebSocket server for real-time event streaming to MCP clients.
 ///
 /// The server listens on a specified port and accepts multiple concurrent
@@ -25,6 +40,9 @@ use tokio_tungstenite::{accept_async, tungstenite::Message};
 ///
 /// - Binds to 0.0.0.0 by default (all interfaces) for remote device support
 /// - Runs on port 9223 by default (or next available in range 9223-9322)
+/// - Can be bound up front (see [`WebSocketServer::bind`]) so failures surface
+///   before the accept loop is spawned
+/// - Can listen on a local socket instead (see [`WebSocketServer::new_local`])
 /// - Supports multiple concurrent client connections
 /// - Uses broadcast channels for event distribution
 /// - Handles client disconnections gracefully
@@ -48,11 +66,37 @@ use tokio_tungstenite::{accept_async, tungstenite::Message};
 /// }
 /// ```
 pub struct WebSocketServer<R: Runtime> {
-    addr: SocketAddr,
+    endpoint: Endpoint,
+    listener: Option<BoundListener>,
+    mcp_http: bool,
     event_tx: broadcast::Sender<String>,
     app: AppHandle<R>,
 }
 
+/// A listener bound ahead of [`WebSocketServer::start`].
+enum BoundListener {
+    Tcp(std::net::TcpListener),
+    Local(LocalListener),
+}
+
+/// Where a [`WebSocketServer`] accepts connections.
+#[derive(Debug, Clone)]
+pub enum Endpoint {
+    /// A TCP socket address.
+    Tcp(SocketAddr),
+    /// A Unix domain socket path, or a named pipe name on Windows.
+    Local(PathBuf),
+}
+
+impl std::fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{addr}"),
+            Self::Local(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
 impl<R: Runtime> WebSocketServer<R> {
     /// Creates a new WebSocket server on the specified port and bind address.
     ///
@@ -84,12 +128,125 @@ impl<R: Runtime> WebSocketServer<R> {
         let addr: SocketAddr = format!("{bind_address}:{port}").parse().unwrap();
 
         Self {
-            addr,
+            endpoint: Endpoint::Tcp(addr),
+            listener: None,
+            mcp_http: false,
             event_tx,
             app,
         }
     }
 
+    /// Creates a new WebSocket server from an already bound TCP listener.
+    ///
+    /// The endpoint is taken from the listener, so this reports the actual
+    /// port when the listener was bound to port 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - A bound listener, e.g. from [`crate::discovery::bind_available_port`]
+    /// * `app` - The Tauri application handle
+    /// * `event_tx` - An external broadcast sender for distributing events
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(WebSocketServer)` - The server, ready to [`start`](Self::start)
+    /// * `Err(io::Error)` - If the listener's address cannot be read
+    pub fn from_tcp_listener(
+        listener: std::net::TcpListener,
+        app: AppHandle<R>,
+        event_tx: broadcast::Sender<String>,
+    ) -> io::Result<Self> {
+        listener.set_nonblocking(true)?;
+
+        Ok(Self {
+            endpoint: Endpoint::Tcp(listener.local_addr()?),
+            listener: Some(BoundListener::Tcp(listener)),
+            mcp_http: false,
+            event_tx,
+            app,
+        })
+    }
+
+    /// Creates a new WebSocket server that listens on a local socket.
+    ///
+    /// Connections are handled exactly like TCP connections: the same command
+    /// dispatch and broadcast subscription run over the local socket.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The Unix domain socket path, or named pipe name on Windows
+    /// * `app` - The Tauri application handle
+    /// * `event_tx` - An external broadcast sender for distributing events
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use tauri_plugin_mcp_bridge::local_socket::socket_path;
+    /// use tauri_plugin_mcp_bridge::websocket::WebSocketServer;
+    ///
+    /// let (event_tx, _event_rx) = tokio::sync::broadcast::channel(100);
+    /// let path = socket_path("com.example.app", std::process::id());
+    /// let server = WebSocketServer::new_local(path, app_handle, event_tx);
+    /// ```
+    pub fn new_local(
+        path: impl Into<PathBuf>,
+        app: AppHandle<R>,
+        event_tx: broadcast::Sender<String>,
+    ) -> Self {
+        Self {
+            endpoint: Endpoint::Local(path.into()),
+            listener: None,
+            mcp_http: false,
+            event_tx,
+            app,
+        }
+    }
+
+    /// Returns the endpoint this server listens on.
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    /// Enables or disables the MCP Streamable HTTP endpoint on this listener.
+    ///
+    /// When enabled, `POST` requests to [`crate::mcp::http::MCP_PATH`] are
+    /// answered by the built-in MCP server, while WebSocket upgrades on any
+    /// path keep working as before.
+    pub fn mcp_http(mut self, enabled: bool) -> Self {
+        self.mcp_http = enabled;
+        self
+    }
+
+    /// Binds the server's listener without accepting connections yet.
+    ///
+    /// Binding up front lets callers report failures synchronously instead of
+    /// from inside the spawned [`start`](Self::start) task. Calling this on an
+    /// already bound server is a no-op. Binding a local socket must happen
+    /// within a Tokio runtime context.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The listener is bound
+    /// * `Err(io::Error)` - If the address or socket path cannot be bound
+    pub fn bind(&mut self) -> io::Result<()> {
+        if self.listener.is_some() {
+            return Ok(());
+        }
+
+        let listener = match &self.endpoint {
+            Endpoint::Tcp(addr) => {
+                let listener = std::net::TcpListener::bind(addr)?;
+                listener.set_nonblocking(true)?;
+                self.endpoint = Endpoint::Tcp(listener.local_addr()?);
+                BoundListener::Tcp(listener)
+            }
+            Endpoint::Local(path) => BoundListener::Local(LocalListener::bind(path)?),
+        };
+
+        self.listener = Some(listener);
+        Ok(())
+    }
+
     /// Starts the WebSocket server and begins accepting connections.
     ///
     /// This method runs indefinitely, accepting new WebSocket connections and
@@ -118,26 +275,58 @@ impl<R: Runtime> WebSocketServer<R> {
     ///     });
     /// }
     /// ```
-    pub async fn start(self) -> Result<(), Box<dyn std::error::Error>> {
-        let listener = TcpListener::bind(&self.addr).await?;
-        mcp_log_info(
-            "WS_SERVER",
-            &format!("WebSocket server listening on: {}", self.addr),
-        );
-
-        loop {
-            let (stream, _) = listener.accept().await?;
-            let event_tx = self.event_tx.clone();
-            let app = self.app.clone();
-
-            tokio::spawn(async move {
-                if let Err(e) = handle_connection(stream, event_tx, app).await {
-                    mcp_log_error("WS_SERVER", &format!("WebSocket connection error: {e}"));
+    pub async fn start(mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.bind()?;
+        let Some(listener) = self.listener.take() else {
+            return Err("WebSocket listener is not bound".into());
+        };
+
+        match listener {
+            BoundListener::Tcp(listener) => {
+                let listener = TcpListener::from_std(listener)?;
+                mcp_log_info(
+                    "WS_SERVER",
+                    &format!("WebSocket server listening on: {}", self.endpoint),
+                );
+
+                loop {
+                    let (stream, _) = listener.accept().await?;
+                    self.spawn_connection(stream);
+                }
+            }
+            BoundListener::Local(mut listener) => {
+                mcp_log_info(
+                    "WS_SERVER",
+                    &format!(
+                        "WebSocket server listening on local socket: {}",
+                        self.endpoint
+                    ),
+                );
+
+                loop {
+                    let stream = listener.accept().await?;
+                    self.spawn_connection(stream);
                 }
-            });
+            }
         }
     }
 
+    /// Spawns a task that serves a single accepted client stream.
+    fn spawn_connection<S>(&self, stream: S)
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let event_tx = self.event_tx.clone();
+        let app = self.app.clone();
+        let mcp_http = self.mcp_http;
+
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, event_tx, app, mcp_http).await {
+                mcp_log_error("WS_SERVER", &format!("WebSocket connection error: {e}"));
+            }
+        });
+    }
+
     /// Broadcasts a message to all connected WebSocket clients.
     ///
     /// Sends the message through the broadcast channel to all active client
@@ -206,41 +395,54 @@ async fn handle_invoke_tauri<R: Runtime>(app: &AppHandle<R>, id: &str, args: &Va
             }
         }
         "plugin:mcp-bridge|start_ipc_monitor" => {
-            let Some(window) = app.webview_windows().values().next().cloned() else {
-                return error_response(id, "No window available");
+            let Some(monitor) = app.try_state::<IPCMonitorState>() else {
+                return error_response(id, "IPC monitor is not initialized");
             };
-            match commands::start_ipc_monitor(window, app.state()).await {
-                Ok(data) => success_response(id, data),
-                Err(e) => error_response(id, e),
-            }
+            // Capture the given window, or every window
+            monitor
+                .lock()
+                .unwrap()
+                .start_for(window_label.map(|label| vec![label]));
+            commands::ipc_monitor::sync_ipc_hooks(app);
+            success_response(id, "IPC monitoring started")
         }
         "plugin:mcp-bridge|stop_ipc_monitor" => {
-            let Some(window) = app.webview_windows().values().next().cloned() else {
-                return error_response(id, "No window available");
+            let Some(monitor) = app.try_state::<IPCMonitorState>() else {
+                return error_response(id, "IPC monitor is not initialized");
             };
-            match commands::stop_ipc_monitor(window, app.state()).await {
+            monitor.lock().unwrap().stop();
+            commands::ipc_monitor::sync_ipc_hooks(app);
+            success_response(id, "IPC monitoring stopped")
+        }
+        "plugin:mcp-bridge|get_ipc_events" => {
+            match commands::get_ipc_events(app.state(), app.state()).await {
                 Ok(data) => success_response(id, data),
                 Err(e) => error_response(id, e),
             }
         }
-        "plugin:mcp-bridge|get_ipc_events" => match commands::get_ipc_events(app.state()).await {
-            Ok(data) => success_response(id, data),
-            Err(e) => error_response(id, e),
-        },
         "plugin:mcp-bridge|emit_event" => {
-            let Some(event_name) = args
-                .get("args")
-                .and_then(|a| a.get("eventName"))
-                .and_then(|v| v.as_str())
-            else {
-                return error_response(id, "Missing eventName in args");
-            };
-            let payload = args
-                .get("args")
-                .and_then(|a| a.get("payload"))
-                .cloned()
-                .unwrap_or(serde_json::json!(null));
-            match commands::emit_event(app.clone(), event_name.to_string(), payload).await {
+            let params: commands::EmitEventParams =
+                match serde_json::from_value(args.get("args").cloned().unwrap_or_default()) {
+                    Ok(params) => params,
+                    Err(e) => return error_response(id, format!("Invalid emit_event args: {e}")),
+                };
+            if params.wait_for_ack {
+                return match commands::emit_event_with_ack(app.clone(), params).await {
+                    Ok(ack) => success_response(id, ack),
+                    Err(e) => error_response(id, e),
+                };
+            }
+            let target = params.target.map(|t| serde_json::json!(t));
+            let targets = params.targets.map(|t| serde_json::json!(t));
+            match commands::emit_event(
+                app.clone(),
+                params.event_name,
+                params.payload,
+                target,
+                targets,
+            )
+            .await
+            {
                 Ok(data) => success_response(id, data),
                 Err(e) => error_response(id, e),
             }
@@ -250,13 +452,55 @@ async fn handle_invoke_tauri<R: Runtime>(app: &AppHandle<R>, id: &str, args: &Va
 }
 
 /// Handles the list_windows command.
-async fn handle_list_windows<R: Runtime>(app: &AppHandle<R>, id: &str) -> Value {
-    match commands::list_windows(app.clone()).await {
+async fn handle_list_windows<R: Runtime>(app: &AppHandle<R>, id: &str, command: &Value) -> Value {
+    let detail = match command.get("args").and_then(|a| a.get("detail")) {
+        None | Some(Value::Null) => None,
+        Some(detail) => match serde_json::from_value(detail.clone()) {
+            Ok(detail) => Some(detail),
+            Err(e) => return error_response(id, format!("Invalid detail level: {e}")),
+        },
+    };
+
+    match commands::list_windows(app.clone(), detail).await {
         Ok(data) => success_response(id, data),
         Err(e) => error_response(id, e),
     }
 }
 
+/// Handles the navigate, reload, go_back, go_forward and wait_for_navigation commands.
+async fn handle_navigation<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+    action: commands::NavigationAction,
+    args: Option<&Value>,
+) -> Value {
+    let params: commands::NavigationParams = match args {
+        Some(args) => match serde_json::from_value(args.clone()) {
+            Ok(params) => params,
+            Err(e) => return error_response(id, format!("Invalid {} args: {e}", action.command())),
+        },
+        None => commands::NavigationParams::default(),
+    };
+
+    match commands::navigate(app.clone(), action, params).await {
+        Ok(result) => serde_json::json!({
+            "id": id,
+            "success": result.success,
+            "data": result,
+            "error": result.error
+        }),
+        Err(e) => error_response(id, e),
+    }
+}
+
+/// Handles the list_monitors command.
+async fn handle_list_monitors<R: Runtime>(app: &AppHandle<R>, id: &str) -> Value {
+    match commands::list_monitors(app.clone()).await {
+        Ok(result) => success_response(id, serde_json::json!(result)),
+        Err(e) => error_response(id, e),
+    }
+}
+
 /// Handles the get_window_info command.
 async fn handle_get_window_info<R: Runtime>(
     app: &AppHandle<R>,
@@ -398,6 +642,359 @@ async fn handle_resize_window<R: Runtime>(app: &AppHandle<R>, id: &str, args: &V
     }
 }
 
+/// Handles the start_event_tap, stop_event_tap, get_tauri_events and
+/// clear_tauri_events commands.
+async fn handle_event_tap<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+    command: &str,
+    args: Option<&Value>,
+) -> Value {
+    let args = args.cloned().unwrap_or_else(|| serde_json::json!({}));
+    let result = match command {
+        "start_event_tap" => match serde_json::from_value(args) {
+            Ok(params) => commands::start_event_tap(app.clone(), params)
+                .await
+                .map(|status| serde_json::json!(status)),
+            Err(e) => Err(format!("Invalid start_event_tap args: {e}")),
+        },
+        "stop_event_tap" => commands::stop_event_tap(app.clone())
+            .await
+            .map(|status| serde_json::json!(status)),
+        "get_tauri_events" => match serde_json::from_value(args) {
+            Ok(params) => commands::get_tauri_events(app.clone(), params)
+                .await
+                .map(|events| serde_json::json!(events)),
+            Err(e) => Err(format!("Invalid get_tauri_events args: {e}")),
+        },
+        _ => commands::clear_tauri_events(app.clone())
+            .await
+            .map(|()| serde_json::json!({ "cleared": true })),
+    };
+
+    match result {
+        Ok(data) => success_response(id, data),
+        Err(e) => error_response(id, e),
+    }
+}
+
+/// Handles the export_session and import_session commands.
+async fn handle_session<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+    command: &str,
+    args: Option<&Value>,
+) -> Value {
+    let args = args.cloned().unwrap_or_else(|| serde_json::json!({}));
+    let result = if command == "export_session" {
+        match serde_json::from_value(args) {
+            Ok(params) => commands::export_session(app.clone(), params)
+                .await
+                .map(|result| serde_json::json!(result)),
+            Err(e) => Err(format!("Invalid export_session args: {e}")),
+        }
+    } else {
+        match serde_json::from_value(args) {
+            Ok(params) => commands::import_session(app.clone(), params)
+                .await
+                .map(|result| serde_json::json!(result)),
+            Err(e) => Err(format!("Invalid import_session args: {e}")),
+        }
+    };
+
+    match result {
+        Ok(data) => success_response(id, data),
+        Err(e) => error_response(id, e),
+    }
+}
+
+/// Handles the get_ipc_stats and set_ipc_slow_threshold commands.
+fn handle_ipc_stats<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+    command: &str,
+    args: Option<&Value>,
+) -> Value {
+    let Some(monitor) = app.try_state::<IPCMonitorState>() else {
+        return error_response(id, "IPC monitor is not initialized");
+    };
+    let mut monitor = monitor.lock().unwrap();
+
+    if command == "set_ipc_slow_threshold" {
+        let threshold_ms = match args.and_then(|a| a.get("thresholdMs")) {
+            None | Some(Value::Null) => None,
+            Some(value) => match value.as_f64() {
+                Some(ms) if ms >= 0.0 => Some(ms),
+                _ => return error_response(id, "thresholdMs must be a non-negative number"),
+            },
+        };
+        monitor.set_slow_threshold(threshold_ms);
+        return success_response(id, serde_json::json!({ "thresholdMs": threshold_ms }));
+    }
+    success_response(id, monitor.stats())
+}
+
+/// Handles the start_monitor_session, stop_monitor_session, end_monitor_session,
+/// get_monitor_session_events and list_monitor_sessions commands.
+fn handle_monitor_sessions<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+    command: &str,
+    args: Option<&Value>,
+) -> Value {
+    let Some(monitor) = app.try_state::<IPCMonitorState>() else {
+        return error_response(id, "IPC monitor is not initialized");
+    };
+    let session_id = args
+        .and_then(|a| a.get("sessionId"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    let mut monitor = monitor.lock().unwrap();
+    let result = match command {
+        "list_monitor_sessions" => {
+            return success_response(id, serde_json::json!({ "sessions": monitor.sessions() }))
+        }
+        "start_monitor_session" => {
+            let windows: Option<Vec<String>> = match args.and_then(|a| a.get("windows")) {
+                None | Some(Value::Null) => None,
+                Some(windows) => match serde_json::from_value(windows.clone()) {
+                    Ok(windows) => Some(windows),
+                    Err(e) => return error_response(id, format!("Invalid windows: {e}")),
+                },
+            };
+            if windows.as_ref().is_some_and(Vec::is_empty) {
+                return error_response(id, "windows must name at least one window");
+            }
+            Ok(serde_json::json!(monitor.start_session(session_id, windows)))
+        }
+        _ => {
+            let Some(session_id) = session_id else {
+                return error_response(id, "Missing sessionId");
+            };
+            match command {
+                "stop_monitor_session" => monitor
+                    .stop_session(&session_id)
+                    .map(|session| serde_json::json!(session)),
+                "end_monitor_session" => monitor
+                    .end_session(&session_id)
+                    .map(|events| serde_json::json!({ "events": events })),
+                _ => {
+                    return match monitor.session_events(&session_id) {
+                        Ok(events) => success_response(id, serde_json::json!({ "events": events })),
+                        Err(e) => error_response(id, e),
+                    }
+                }
+            }
+        }
+    };
+    drop(monitor);
+
+    match result {
+        Ok(data) => {
+            // Install or remove the bridge.js hook where capture changed
+            commands::ipc_monitor::sync_ipc_hooks(app);
+            success_response(id, data)
+        }
+        Err(e) => error_response(id, e),
+    }
+}
+
+/// Handles the get_backend_logs, clear_backend_logs and stream_backend_logs
+/// commands.
+fn handle_backend_logs(id: &str, command: &str, args: Option<&Value>) -> Value {
+    let mut buffer = crate::backend_logs::log_buffer().lock().unwrap();
+
+    match command {
+        "clear_backend_logs" => {
+            success_response(id, serde_json::json!({ "cleared": buffer.clear() }))
+        }
+        "stream_backend_logs" => {
+            buffer.stream = args
+                .and_then(|a| a.get("enabled"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            success_response(id, serde_json::json!({ "stream": buffer.stream }))
+        }
+        _ => {
+            let query: crate::backend_logs::LogQuery = match serde_json::from_value(
+                args.cloned().unwrap_or_else(|| serde_json::json!({})),
+            ) {
+                Ok(query) => query,
+                Err(e) => return error_response(id, format!("Invalid get_backend_logs args: {e}")),
+            };
+            if !crate::backend_logs::is_capturing() {
+                return error_response(
+                    id,
+                    "Backend log capture is not enabled; use Builder::capture_backend_logs \
+                     or add backend_logs::layer() to the app's tracing subscriber",
+                );
+            }
+            match buffer.query(&query) {
+                Ok(records) => success_response(id, records),
+                Err(e) => error_response(id, e),
+            }
+        }
+    }
+}
+
+/// Handles the add_redaction_rule, remove_redaction_rule, clear_redaction_rules
+/// and get_redaction_rules commands.
+fn handle_redaction<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+    command: &str,
+    args: Option<&Value>,
+) -> Value {
+    let Some(monitor) = app.try_state::<IPCMonitorState>() else {
+        return error_response(id, "IPC monitor is not initialized");
+    };
+    let mut monitor = monitor.lock().unwrap();
+    let previous_keys = monitor.console_redaction_keys();
+
+    let response = match command {
+        "add_redaction_rule" => {
+            let rule: RedactionRule = match args.map(|a| serde_json::from_value(a.clone())) {
+                Some(Ok(rule)) => rule,
+                Some(Err(e)) => return error_response(id, format!("Invalid redaction rule: {e}")),
+                None => return error_response(id, "Missing args for add_redaction_rule"),
+            };
+            let rule_id = rule.id.clone();
+            match monitor.add_redaction_rule(rule) {
+                Ok(()) => {
+                    success_response(id, serde_json::json!({ "added": true, "ruleId": rule_id }))
+                }
+                Err(e) => return error_response(id, e),
+            }
+        }
+        "remove_redaction_rule" => {
+            let Some(rule_id) = args.and_then(|a| a.get("id")).and_then(|v| v.as_str()) else {
+                return error_response(id, "Missing redaction rule id");
+            };
+            let removed = monitor.remove_redaction_rule(rule_id).is_some();
+            success_response(
+                id,
+                serde_json::json!({ "removed": removed, "ruleId": rule_id }),
+            )
+        }
+        "clear_redaction_rules" => success_response(
+            id,
+            serde_json::json!({ "cleared": monitor.clear_redaction_rules() }),
+        ),
+        _ => {
+            return success_response(
+                id,
+                serde_json::json!({ "rules": monitor.redaction_rules() }),
+            )
+        }
+    };
+
+    // Hand changed console patterns to every open window; later page loads
+    // pick them up through request_script_injection
+    let keys = monitor.console_redaction_keys();
+    if keys != previous_keys {
+        let script = console_patterns_script(&keys);
+        for window in app.webview_windows().values() {
+            let _ = window.eval(&script);
+        }
+    }
+    response
+}
+
+/// Handles the replay_ipc command.
+async fn handle_replay_ipc<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+    args: Option<&Value>,
+) -> Value {
+    let params: commands::ReplayIpcParams =
+        match serde_json::from_value(args.cloned().unwrap_or_else(|| serde_json::json!({}))) {
+            Ok(params) => params,
+            Err(e) => return error_response(id, format!("Invalid replay_ipc args: {e}")),
+        };
+    match commands::replay_ipc(app.clone(), params).await {
+        Ok(report) => success_response(id, report),
+        Err(e) => error_response(id, e),
+    }
+}
+
+/// Parses wait_for_event args and registers the listener, returning an error
+/// response if the args are invalid.
+fn register_wait_for_event<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+    args: Option<&Value>,
+) -> Result<commands::EventWaiter<R>, Value> {
+    let Some(args) = args else {
+        return Err(error_response(id, "Missing args for wait_for_event"));
+    };
+    let params: commands::WaitForEventParams = serde_json::from_value(args.clone())
+        .map_err(|e| error_response(id, format!("Invalid wait_for_event args: {e}")))?;
+    commands::EventWaiter::register(app, params).map_err(|e| error_response(id, e))
+}
+
+/// Waits for a registered wait_for_event listener and builds the response.
+async fn wait_for_event_response<R: Runtime>(id: &str, waiter: commands::EventWaiter<R>) -> Value {
+    match waiter.wait().await {
+        Ok(result) => success_response(id, result),
+        Err(e) => error_response(id, e),
+    }
+}
+
+/// Handles the create_window command.
+async fn handle_create_window<R: Runtime>(app: &AppHandle<R>, id: &str, args: &Value) -> Value {
+    let params: commands::CreateWindowParams = match serde_json::from_value(args.clone()) {
+        Ok(params) => params,
+        Err(e) => return error_response(id, format!("Invalid create_window args: {e}")),
+    };
+
+    match commands::create_window(app.clone(), params).await {
+        Ok(result) => serde_json::json!({
+            "id": id,
+            "success": result.success,
+            "data": result,
+            "error": result.error
+        }),
+        Err(e) => error_response(id, e),
+    }
+}
+
+/// Handles the create_webview command.
+async fn handle_create_webview<R: Runtime>(app: &AppHandle<R>, id: &str, args: &Value) -> Value {
+    let params: commands::CreateWebviewParams = match serde_json::from_value(args.clone()) {
+        Ok(params) => params,
+        Err(e) => return error_response(id, format!("Invalid create_webview args: {e}")),
+    };
+
+    match commands::create_webview(app.clone(), params).await {
+        Ok(result) => serde_json::json!({
+            "id": id,
+            "success": result.success,
+            "data": result,
+            "error": result.error
+        }),
+        Err(e) => error_response(id, e),
+    }
+}
+
+/// Handles the manage_window command.
+async fn handle_manage_window<R: Runtime>(app: &AppHandle<R>, id: &str, args: &Value) -> Value {
+    let params: commands::ManageWindowParams = match serde_json::from_value(args.clone()) {
+        Ok(params) => params,
+        Err(e) => return error_response(id, format!("Invalid manage_window args: {e}")),
+    };
+
+    match commands::manage_window(app.clone(), params).await {
+        Ok(result) => serde_json::json!({
+            "id": id,
+            "success": result.success,
+            "data": result,
+            "error": result.error
+        }),
+        Err(e) => error_response(id, e),
+    }
+}
+
 /// Handles the register_script command.
 fn handle_register_script<R: Runtime>(app: &AppHandle<R>, id: &str, args: &Value) -> Value {
     let script_id = args.get("id").and_then(|v| v.as_str());
@@ -475,7 +1072,10 @@ fn handle_remove_script<R: Runtime>(app: &AppHandle<R>, id: &str, args: &Value)
             }
         }),
         Err(e) => {
-            eprintln!("Failed to remove script from DOM: {e}");
+            mcp_log_warn(
+                "WS_SERVER",
+                &format!("Failed to remove script from DOM: {e}"),
+            );
             serde_json::json!({
                 "id": id,
                 "success": true,
@@ -514,7 +1114,10 @@ fn handle_clear_scripts<R: Runtime>(app: &AppHandle<R>, id: &str, command: &Valu
             }
         }),
         Err(e) => {
-            eprintln!("Failed to clear scripts from DOM: {e}");
+            mcp_log_warn(
+                "WS_SERVER",
+                &format!("Failed to clear scripts from DOM: {e}"),
+            );
             serde_json::json!({
                 "id": id,
                 "success": true,
@@ -552,8 +1155,112 @@ fn handle_get_scripts<R: Runtime>(app: &AppHandle<R>, id: &str) -> Value {
     })
 }
 
+/// Handles the add_ipc_mock, remove_ipc_mock, clear_ipc_mocks and
+/// get_ipc_mocks commands.
+fn handle_ipc_mocks<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+    command: &str,
+    args: Option<&Value>,
+) -> Value {
+    let Some(registry) = app.try_state::<SharedMockRegistry>() else {
+        return error_response(id, "IPC mocking is not initialized");
+    };
+    let mut reg = registry.lock().unwrap();
+
+    match command {
+        "add_ipc_mock" => {
+            let rule: MockRule = match args.map(|a| serde_json::from_value(a.clone())) {
+                Some(Ok(rule)) => rule,
+                Some(Err(e)) => return error_response(id, format!("Invalid mock rule: {e}")),
+                None => return error_response(id, "Missing args for add_ipc_mock"),
+            };
+            let mock_id = rule.id.clone();
+            match reg.add(rule) {
+                Ok(()) => success_response(
+                    id,
+                    serde_json::json!({
+                        "added": true,
+                        "mockId": mock_id,
+                        // Rules only apply when the app's invoke handler is wrapped
+                        "enforced": crate::ipc_capture::is_installed(app)
+                    }),
+                ),
+                Err(e) => error_response(id, e),
+            }
+        }
+        "remove_ipc_mock" => {
+            let Some(mock_id) = args.and_then(|a| a.get("id")).and_then(|v| v.as_str()) else {
+                return error_response(id, "Missing mock id");
+            };
+            let removed = reg.remove(mock_id).is_some();
+            success_response(
+                id,
+                serde_json::json!({ "removed": removed, "mockId": mock_id }),
+            )
+        }
+        "clear_ipc_mocks" => success_response(id, serde_json::json!({ "cleared": reg.clear() })),
+        _ => success_response(id, serde_json::json!({ "mocks": reg.get_all() })),
+    }
+}
+
+/// Handles the add_ipc_fault, remove_ipc_fault, clear_ipc_faults and
+/// get_ipc_faults commands.
+fn handle_ipc_faults<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+    command: &str,
+    args: Option<&Value>,
+) -> Value {
+    let Some(injector) = app.try_state::<SharedFaultInjector>() else {
+        return error_response(id, "IPC fault injection is not initialized");
+    };
+    let mut faults = injector.lock().unwrap();
+
+    match command {
+        "add_ipc_fault" => {
+            let rule: FaultRule = match args.map(|a| serde_json::from_value(a.clone())) {
+                Some(Ok(rule)) => rule,
+                Some(Err(e)) => return error_response(id, format!("Invalid fault rule: {e}")),
+                None => return error_response(id, "Missing args for add_ipc_fault"),
+            };
+            let fault_id = rule.id.clone();
+            match faults.add(rule) {
+                Ok(()) => success_response(
+                    id,
+                    serde_json::json!({
+                        "added": true,
+                        "faultId": fault_id,
+                        // Rules only apply when the app's invoke handler is wrapped
+                        "enforced": crate::ipc_capture::is_installed(app)
+                    }),
+                ),
+                Err(e) => error_response(id, e),
+            }
+        }
+        "remove_ipc_fault" => {
+            let Some(fault_id) = args.and_then(|a| a.get("id")).and_then(|v| v.as_str()) else {
+                return error_response(id, "Missing fault id");
+            };
+            let removed = faults.remove(fault_id);
+            success_response(
+                id,
+                serde_json::json!({
+                    "removed": removed.is_some(),
+                    "faultId": fault_id,
+                    "stats": removed.map(|rule| rule.stats)
+                }),
+            )
+        }
+        "clear_ipc_faults" => {
+            success_response(id, serde_json::json!({ "cleared": faults.clear() }))
+        }
+        _ => success_response(id, serde_json::json!({ "faults": faults.get_all() })),
+    }
+}
+
 /// Dispatches a WebSocket command to the appropriate handler.
-async fn dispatch_command<R: Runtime>(app: &AppHandle<R>, command: &Value) -> Value {
+pub(crate) async fn dispatch_command<R: Runtime>(app: &AppHandle<R>, command: &Value) -> Value {
     let id = command.get("id").and_then(|v| v.as_str()).unwrap_or("");
     let cmd_name = command
         .get("command")
@@ -569,8 +1276,15 @@ async fn dispatch_command<R: Runtime>(app: &AppHandle<R>, command: &Value) -> Va
                 error_response(id, "Missing args for invoke_tauri")
             }
         }
-        "list_windows" => handle_list_windows(app, id).await,
+        "list_windows" => handle_list_windows(app, id, command).await,
         "get_window_info" => handle_get_window_info(app, id, command).await,
+        "list_monitors" => handle_list_monitors(app, id).await,
+        "navigate" | "reload" | "go_back" | "go_forward" | "wait_for_navigation" => {
+            match commands::NavigationAction::from_command(cmd_name) {
+                Some(action) => handle_navigation(app, id, action, args).await,
+                None => error_response(id, format!("Unknown command: {cmd_name}")),
+            }
+        }
         "execute_js" => {
             if let Some(args) = args {
                 handle_execute_js(app, id, args).await
@@ -586,6 +1300,34 @@ async fn dispatch_command<R: Runtime>(app: &AppHandle<R>, command: &Value) -> Va
                 error_response(id, "Missing args for resize_window")
             }
         }
+        "start_event_tap" | "stop_event_tap" | "get_tauri_events" | "clear_tauri_events" => {
+            handle_event_tap(app, id, cmd_name, args).await
+        }
+        "wait_for_event" => match register_wait_for_event(app, id, args) {
+            Ok(waiter) => wait_for_event_response(id, waiter).await,
+            Err(response) => response,
+        },
+        "create_window" => {
+            if let Some(args) = args {
+                handle_create_window(app, id, args).await
+            } else {
+                error_response(id, "Missing args for create_window")
+            }
+        }
+        "create_webview" => {
+            if let Some(args) = args {
+                handle_create_webview(app, id, args).await
+            } else {
+                error_response(id, "Missing args for create_webview")
+            }
+        }
+        "manage_window" => {
+            if let Some(args) = args {
+                handle_manage_window(app, id, args).await
+            } else {
+                error_response(id, "Missing args for manage_window")
+            }
+        }
         "register_script" => {
             if let Some(args) = args {
                 handle_register_script(app, id, args)
@@ -602,6 +1344,27 @@ async fn dispatch_command<R: Runtime>(app: &AppHandle<R>, command: &Value) -> Va
         }
         "clear_scripts" => handle_clear_scripts(app, id, command),
         "get_scripts" => handle_get_scripts(app, id),
+        "add_ipc_mock" | "remove_ipc_mock" | "clear_ipc_mocks" | "get_ipc_mocks" => {
+            handle_ipc_mocks(app, id, cmd_name, args)
+        }
+        "add_ipc_fault" | "remove_ipc_fault" | "clear_ipc_faults" | "get_ipc_faults" => {
+            handle_ipc_faults(app, id, cmd_name, args)
+        }
+        "export_session" | "import_session" => handle_session(app, id, cmd_name, args).await,
+        "replay_ipc" => handle_replay_ipc(app, id, args).await,
+        "get_ipc_stats" | "set_ipc_slow_threshold" => handle_ipc_stats(app, id, cmd_name, args),
+        "start_monitor_session"
+        | "stop_monitor_session"
+        | "end_monitor_session"
+        | "get_monitor_session_events"
+        | "list_monitor_sessions" => handle_monitor_sessions(app, id, cmd_name, args),
+        "get_backend_logs" | "clear_backend_logs" | "stream_backend_logs" => {
+            handle_backend_logs(id, cmd_name, args)
+        }
+        "add_redaction_rule"
+        | "remove_redaction_rule"
+        | "clear_redaction_rules"
+        | "get_redaction_rules" => handle_redaction(app, id, cmd_name, args),
         _ => error_response(id, format!("Unknown command: {cmd_name}")),
     }
 }
@@ -627,25 +1390,72 @@ fn is_benign_handshake_error(err: &tokio_tungstenite::tungstenite::Error) -> boo
 /// Handles a single WebSocket client connection.
 ///
 /// This function manages the lifecycle of a WebSocket connection, including:
-/// - Upgrading the TCP stream to WebSocket
+/// - Upgrading the TCP or local socket stream to WebSocket
+/// - Routing plain HTTP requests to the MCP endpoint, when enabled
 /// - Forwarding broadcast events to the client
 /// - Receiving and processing messages from the client (request/response)
 /// - Handling disconnections and errors
 ///
 /// # Arguments
 ///
-/// * `stream` - The TCP stream for the client connection
+/// * `stream` - The TCP or local socket stream for the client connection
 /// * `event_tx` - Broadcast sender for distributing events
+/// * `app` - The Tauri application handle
+/// * `mcp_http` - Whether to serve the MCP Streamable HTTP endpoint
 ///
 /// # Returns
 ///
 /// * `Ok(())` - When the connection closes normally
 /// * `Err(Box<dyn std::error::Error>)` - If an error occurs during communication
-async fn handle_connection<R: Runtime>(
-    stream: TcpStream,
+async fn handle_connection<S, R>(
+    mut stream: S,
     event_tx: broadcast::Sender<String>,
     app: AppHandle<R>,
-) -> Result<(), Box<dyn std::error::Error>> {
+    mcp_http: bool,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    R: Runtime,
+{
+    if !mcp_http {
+        return serve_websocket(stream, event_tx, app).await;
+    }
+
+    // Sniff the request head to route plain HTTP requests to the MCP endpoint
+    let head = match mcp::http::read_head(&mut stream).await {
+        Ok(head) if head.is_empty() => return Ok(()),
+        Ok(head) => head,
+        Err(e)
+            if matches!(
+                e.kind(),
+                io::ErrorKind::TimedOut
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::UnexpectedEof
+                    | io::ErrorKind::BrokenPipe
+            ) =>
+        {
+            return Ok(())
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if mcp::http::is_websocket_upgrade(&head) {
+        serve_websocket(mcp::http::Rewind::new(head, stream), event_tx, app).await
+    } else {
+        Ok(mcp::http::serve(&app, head, stream).await?)
+    }
+}
+
+/// Upgrades a connection to WebSocket and serves commands and broadcasts.
+async fn serve_websocket<S, R>(
+    stream: S,
+    event_tx: broadcast::Sender<String>,
+    app: AppHandle<R>,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    R: Runtime,
+{
     let ws_stream = match accept_async(stream).await {
         Ok(ws_stream) => ws_stream,
         // A client that opens the TCP connection but drops before completing the WebSocket
@@ -665,13 +1475,13 @@ async fn handle_connection<R: Runtime>(
             tokio::select! {
                 Ok(msg) = event_rx.recv() => {
                     if let Err(e) = ws_sender.send(Message::Text(msg.into())).await {
-                        eprintln!("Failed to send broadcast: {e}");
+                        mcp_log_warn("WS_SERVER", &format!("Failed to send broadcast: {e}"));
                         break;
                     }
                 }
                 Some(response) = response_rx.recv() => {
                     if let Err(e) = ws_sender.send(Message::Text(response.into())).await {
-                        eprintln!("Failed to send response: {e}");
+                        mcp_log_warn("WS_SERVER", &format!("Failed to send response: {e}"));
                         break;
                     }
                 }
@@ -684,18 +1494,37 @@ async fn handle_connection<R: Runtime>(
         match msg {
             Ok(Message::Text(text)) => {
                 if let Ok(command) = serde_json::from_str::<Value>(&text) {
+                    if command.get("command").and_then(Value::as_str) == Some("wait_for_event") {
+                        // Register the listener now but wait in the background, so a later
+                        // command on this connection can trigger the event
+                        let id = command.get("id").and_then(Value::as_str).unwrap_or("");
+                        match register_wait_for_event(&app, id, command.get("args")) {
+                            Ok(waiter) => {
+                                let id = id.to_string();
+                                let response_tx = response_tx.clone();
+                                tokio::spawn(async move {
+                                    let response = wait_for_event_response(&id, waiter).await;
+                                    let _ = response_tx.send(response.to_string());
+                                });
+                            }
+                            Err(response) => {
+                                let _ = response_tx.send(response.to_string());
+                            }
+                        }
+                        continue;
+                    }
                     let response = dispatch_command(&app, &command).await;
                     let _ = response_tx.send(response.to_string());
                 } else {
-                    eprintln!("Failed to parse command: {text}");
+                    mcp_log_warn("WS_SERVER", &format!("Failed to parse command: {text}"));
                 }
             }
             Ok(Message::Close(_)) => {
-                println!("Client disconnected");
+                mcp_log_debug("WS_SERVER", "Client disconnected");
                 break;
             }
             Err(e) => {
-                eprintln!("WebSocket error: {e}");
+                mcp_log_warn("WS_SERVER", &format!("WebSocket error: {e}"));
                 break;
             }
             _ => {}
@@ -862,3 +1691,80 @@ pub fn inject_all_scripts<R: Runtime>(
 
     Ok(scripts.len())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::script_registry::create_shared_registry;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_local_socket_dispatch_and_broadcast() {
+        let app = tauri::test::mock_app();
+        app.manage(create_shared_registry());
+
+        let path =
+            std::env::temp_dir().join(format!("tauri-mcp-test-ws-{}.sock", std::process::id()));
+        let (event_tx, _event_rx) = broadcast::channel::<String>(16);
+        let server =
+            WebSocketServer::new_local(path.clone(), app.handle().clone(), event_tx.clone());
+        tokio::spawn(async move {
+            let _ = server.start().await;
+        });
+
+        let stream = loop {
+            match tokio::net::UnixStream::connect(&path).await {
+                Ok(stream) => break stream,
+                Err(_) => tokio::time::sleep(std::time::Duration::from_millis(10)).await,
+            }
+        };
+        let (mut ws, _) = tokio_tungstenite::client_async("ws://localhost/", stream)
+            .await
+            .unwrap();
+
+        let request = serde_json::json!({ "id": "1", "command": "get_scripts" });
+        ws.send(Message::Text(request.to_string().into()))
+            .await
+            .unwrap();
+
+        let reply = ws.next().await.unwrap().unwrap();
+        let response: Value = serde_json::from_str(reply.to_text().unwrap()).unwrap();
+        assert_eq!(response["id"], "1");
+        assert_eq!(response["success"], true);
+        assert_eq!(response["data"]["scripts"], serde_json::json!([]));
+
+        event_tx.send(r#"{"type":"test"}"#.to_string()).unwrap();
+        let event = ws.next().await.unwrap().unwrap();
+        assert_eq!(event.to_text().unwrap(), r#"{"type":"test"}"#);
+
+        crate::local_socket::cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn test_bind_reports_os_assigned_port() {
+        let app = tauri::test::mock_app();
+        let (event_tx, _event_rx) = broadcast::channel::<String>(16);
+
+        let mut server = WebSocketServer::new(0, "127.0.0.1", app.handle().clone(), event_tx);
+        server.bind().unwrap();
+
+        let Endpoint::Tcp(addr) = server.endpoint().clone() else {
+            panic!("expected a TCP endpoint");
+        };
+        assert_ne!(addr.port(), 0);
+
+        // The port stays reserved until the server is dropped
+        assert!(std::net::TcpListener::bind(addr).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bind_fails_when_port_is_taken() {
+        let app = tauri::test::mock_app();
+        let (event_tx, _event_rx) = broadcast::channel::<String>(16);
+        let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = taken.local_addr().unwrap().port();
+
+        let mut server = WebSocketServer::new(port, "127.0.0.1", app.handle().clone(), event_tx);
+        assert!(server.bind().is_err());
+    }
+}
@@ -0,0 +1,164 @@
+//! Window lifecycle events published on the WebSocket broadcast channel.
+//!
+//! The plugin hooks window creation and Tauri window events and broadcasts
+//! each one as a `window_event` message:
+//!
+//! ```json
+//! { "type": "window_event", "label": "settings", "event": "resized",
+//!   "width": 800, "height": 600, "timestamp": 1234567890 }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, Runtime, WindowEvent};
+use tokio::sync::broadcast;
+
+use crate::monitor::current_timestamp;
+
+/// Message type of window event broadcasts.
+pub const WINDOW_EVENT_TYPE: &str = "window_event";
+
+/// A window lifecycle event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    tag = "event",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum WindowEventKind {
+    /// A window was created
+    Created,
+    /// A window was destroyed
+    Destroyed,
+    /// The window gained or lost focus
+    Focused { focused: bool },
+    /// The window moved; outer position in physical pixels
+    Moved { x: i32, y: i32 },
+    /// The window was resized; inner size in physical pixels
+    Resized { width: u32, height: u32 },
+    /// The window's scale factor changed
+    ScaleFactorChanged {
+        scale_factor: f64,
+        width: u32,
+        height: u32,
+    },
+    /// The system theme changed ("light" or "dark")
+    ThemeChanged { theme: String },
+    /// The user or the app asked the window to close
+    CloseRequested,
+}
+
+impl WindowEventKind {
+    /// Converts a Tauri window event, skipping events that are not published
+    /// (such as drag and drop).
+    pub fn from_tauri(event: &WindowEvent) -> Option<Self> {
+        Some(match event {
+            WindowEvent::Resized(size) => Self::Resized {
+                width: size.width,
+                height: size.height,
+            },
+            WindowEvent::Moved(position) => Self::Moved {
+                x: position.x,
+                y: position.y,
+            },
+            WindowEvent::CloseRequested { .. } => Self::CloseRequested,
+            WindowEvent::Destroyed => Self::Destroyed,
+            WindowEvent::Focused(focused) => Self::Focused { focused: *focused },
+            WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                new_inner_size,
+                ..
+            } => Self::ScaleFactorChanged {
+                scale_factor: *scale_factor,
+                width: new_inner_size.width,
+                height: new_inner_size.height,
+            },
+            WindowEvent::ThemeChanged(theme) => Self::ThemeChanged {
+                theme: theme.to_string(),
+            },
+            _ => return None,
+        })
+    }
+}
+
+/// A `window_event` broadcast message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowEventMessage {
+    /// Always `"window_event"`
+    #[serde(rename = "type")]
+    pub message_type: String,
+    /// Label of the window the event belongs to
+    pub label: String,
+    /// The event and its data
+    #[serde(flatten)]
+    pub event: WindowEventKind,
+    /// Unix timestamp in milliseconds
+    pub timestamp: u64,
+}
+
+impl WindowEventMessage {
+    /// Creates a message for `label`, timestamped now.
+    pub fn new(label: &str, event: WindowEventKind) -> Self {
+        Self {
+            message_type: WINDOW_EVENT_TYPE.to_string(),
+            label: label.to_string(),
+            event,
+            timestamp: current_timestamp(),
+        }
+    }
+}
+
+/// Sender for the WebSocket broadcast channel, kept in managed state so
+/// plugin hooks can publish events.
+#[derive(Clone)]
+pub struct BroadcastSender(pub broadcast::Sender<String>);
+
+/// Broadcasts a window event if the bridge has been set up.
+pub fn publish<R: Runtime, M: Manager<R>>(manager: &M, label: &str, event: WindowEventKind) {
+    let Some(sender) = manager.try_state::<BroadcastSender>() else {
+        return;
+    };
+    if let Ok(message) = serde_json::to_string(&WindowEventMessage::new(label, event)) {
+        // No receivers just means no client is connected
+        let _ = sender.0.send(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tauri::PhysicalSize;
+
+    #[test]
+    fn test_message_serialization() {
+        let message = WindowEventMessage::new(
+            "settings",
+            WindowEventKind::from_tauri(&WindowEvent::Resized(PhysicalSize::new(800, 600)))
+                .unwrap(),
+        );
+        let value = serde_json::to_value(&message).unwrap();
+        assert_eq!(value["type"], "window_event");
+        assert_eq!(value["label"], "settings");
+        assert_eq!(value["event"], "resized");
+        assert_eq!(value["width"], 800);
+
+        let parsed: WindowEventMessage = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, message);
+    }
+
+    #[test]
+    fn test_publish_sends_to_broadcast() {
+        let app = tauri::test::mock_app();
+        let (tx, mut rx) = broadcast::channel(4);
+        app.manage(BroadcastSender(tx));
+
+        publish(
+            app.handle(),
+            "main",
+            WindowEventKind::Focused { focused: true },
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(value["event"], "focused");
+        assert_eq!(value["focused"], true);
+    }
+}